@@ -33,6 +33,8 @@ CREATE TABLE org_orgcomunitarias (
     org_fechaconst DATE NOT NULL,
     org_perjuridica VARCHAR(255) NOT NULL,
     org_email VARCHAR(255),
+    org_fecha_registro TIMESTAMP DEFAULT now(),
+    org_fecha_modificacion TIMESTAMP,
     CONSTRAINT fk_org_uv FOREIGN KEY (org_uvid) REFERENCES uv_unidadesvecinales (uv_id),
     -- Validación simple de email (no exhaustiva)
     CONSTRAINT chk_org_email_formato CHECK (org_email IS NULL OR org_email ~* '^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\\.[A-Za-z]{2,}$')
@@ -71,6 +73,8 @@ CREATE TABLE per_personasmayores (
     per_direccion VARCHAR(255) NOT NULL,
     per_email VARCHAR(255),
     per_uvid INT NOT NULL,
+    per_fecha_registro TIMESTAMP DEFAULT now(),
+    per_fecha_modificacion TIMESTAMP,
     CONSTRAINT fk_per_gen FOREIGN KEY (per_genid) REFERENCES gen_generos (gen_id),
     CONSTRAINT fk_per_nac FOREIGN KEY (per_nacid) REFERENCES nac_nacionalidades (nac_id),
     CONSTRAINT fk_per_uv FOREIGN KEY (per_uvid) REFERENCES uv_unidadesvecinales (uv_id),
@@ -107,6 +111,8 @@ CREATE TABLE act_actividades (
     act_fecha_ini DATE NOT NULL,
     act_fecha_fin DATE,
     act_descripcion TEXT,
+    act_fecha_registro TIMESTAMP DEFAULT now(),
+    act_fecha_modificacion TIMESTAMP,
     CONSTRAINT fk_act_uv FOREIGN KEY (act_uvid) REFERENCES uv_unidadesvecinales (uv_id),
     CONSTRAINT uq_act_nombre_fecha_uv UNIQUE (act_nombre, act_fecha_ini, act_uvid),
     CONSTRAINT chk_act_fechas CHECK (act_fecha_fin IS NULL OR act_fecha_fin >= act_fecha_ini)
@@ -0,0 +1,12 @@
+-- Fechas de registro y última modificación usadas por los listados
+ALTER TABLE per_personasmayores
+    ADD COLUMN IF NOT EXISTS per_fecha_registro TIMESTAMP DEFAULT now(),
+    ADD COLUMN IF NOT EXISTS per_fecha_modificacion TIMESTAMP;
+
+ALTER TABLE org_orgcomunitarias
+    ADD COLUMN IF NOT EXISTS org_fecha_registro TIMESTAMP DEFAULT now(),
+    ADD COLUMN IF NOT EXISTS org_fecha_modificacion TIMESTAMP;
+
+ALTER TABLE act_actividades
+    ADD COLUMN IF NOT EXISTS act_fecha_registro TIMESTAMP DEFAULT now(),
+    ADD COLUMN IF NOT EXISTS act_fecha_modificacion TIMESTAMP;
@@ -0,0 +1,8 @@
+-- Valores iniciales de los catálogos; solo se cargan si el catálogo está vacío
+INSERT INTO gen_generos (gen_genero)
+SELECT v.genero FROM (VALUES ('Femenino'), ('Masculino'), ('Otro')) AS v(genero)
+WHERE NOT EXISTS (SELECT 1 FROM gen_generos);
+
+INSERT INTO nac_nacionalidades (nac_nacionalidad)
+SELECT v.nacionalidad FROM (VALUES ('Chilena'), ('Extranjera')) AS v(nacionalidad)
+WHERE NOT EXISTS (SELECT 1 FROM nac_nacionalidades);
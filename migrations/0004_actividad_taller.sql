@@ -0,0 +1,5 @@
+-- Taller al que pertenece una actividad (opcional); al borrar el taller la actividad queda sin él
+ALTER TABLE act_actividades
+    ADD COLUMN IF NOT EXISTS act_talid INT CONSTRAINT fk_act_tal REFERENCES tal_talleres (tal_id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_act_tal ON act_actividades (act_talid);
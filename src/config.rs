@@ -0,0 +1,336 @@
+// Preferencias de la aplicación persistidas junto al ejecutable
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use crate::import::ColumnMapping;
+use crate::models::{ActividadFilter, DatabaseConfig, OrganizacionFilter, PersonaFilter};
+use crate::ui::insertions::InsertionType;
+use crate::ui::queries::QueryType;
+use crate::ui::theme::UiDensity;
+
+const CONFIG_FILE: &str = "app_config.json";
+
+// Intervalo del keep-alive de la conexión si la configuración no indica otro
+pub const DEFAULT_KEEPALIVE_SECS: u64 = 60;
+
+// Edad mínima para registrar una persona mayor si la configuración no indica otra
+pub const DEFAULT_MIN_AGE: i32 = 60;
+
+// Filas a partir de las cuales se pide confirmación antes de cargar una consulta sin paginar
+pub const DEFAULT_ROW_LIMIT_WARNING: i64 = 1000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub query_type: Option<QueryType>,
+    pub insertion_type: Option<InsertionType>,
+    pub recent_uvs: Vec<i32>,
+    // Filas por página en los listados de consultas
+    pub page_size: Option<i64>,
+    // Perfiles de conexión guardados (nombre, configuración) y el último usado
+    pub profiles: Vec<(String, DatabaseConfig)>,
+    pub last_profile: Option<String>,
+    // Último mapeo de columnas usado al importar personas, por encabezados del archivo
+    pub persona_csv_mappings: HashMap<String, ColumnMapping>,
+    pub density: UiDensity,
+    // Últimas búsquedas por campo de filtro, la más reciente primero
+    pub search_history: HashMap<String, Vec<String>>,
+    // Edad mínima para registrar personas; cada municipio puede ajustarla
+    pub min_age: Option<i32>,
+    // Segundos entre pings a la base para mantener viva la conexión
+    pub keepalive_secs: Option<u64>,
+    // Columnas ocultas de cada tabla de resultados, por id de la tabla
+    pub hidden_columns: HashMap<String, Vec<String>>,
+    // Valores con que se pre-llena el formulario de personas
+    pub persona_defaults: PersonaDefaults,
+    // Máximo de filas que "Mostrar todos" carga sin pedir confirmación
+    pub row_limit_warning: Option<i64>,
+}
+
+// Valores por defecto elegidos por el usuario para agilizar el ingreso de personas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PersonaDefaults {
+    pub genero_id: Option<i32>,
+    pub nacionalidad_id: Option<i32>,
+    pub unidad_vecinal_id: Option<i32>,
+}
+
+impl AppConfig {
+    fn get_config_path() -> PathBuf {
+        let mut path = std::env::current_exe().unwrap_or_default();
+        path.pop(); // Remover el nombre del ejecutable
+        path.push(CONFIG_FILE);
+        path
+    }
+
+    pub fn min_age(&self) -> i32 {
+        self.min_age.unwrap_or(DEFAULT_MIN_AGE)
+    }
+
+    pub fn row_limit_warning(&self) -> i64 {
+        self.row_limit_warning.unwrap_or(DEFAULT_ROW_LIMIT_WARNING).max(0)
+    }
+
+    pub fn keepalive_interval(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.keepalive_secs.unwrap_or(DEFAULT_KEEPALIVE_SECS).max(1))
+    }
+
+    // Indica si ya se guardó alguna configuración; si no, es la primera ejecución
+    pub fn exists() -> bool {
+        Self::get_config_path().exists()
+    }
+
+    // Lee el archivo de configuración; Ok(None) si todavía no existe
+    fn read_from(path: &Path) -> Result<Option<Self>> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e).with_context(|| format!("No se pudo leer {}", path.display())),
+        };
+        let config = serde_json::from_str(&content)
+            .with_context(|| format!("{} no es una configuración válida", path.display()))?;
+        Ok(Some(config))
+    }
+
+    // Carga la configuración; si no existe o es inválida se usan los valores por defecto
+    pub fn load() -> Self {
+        match Self::read_from(&Self::get_config_path()) {
+            Ok(config) => config.unwrap_or_default(),
+            Err(e) => {
+                log::error!("Error al cargar configuración: {:#}", e);
+                Self::default()
+            }
+        }
+    }
+
+    // Aparta un archivo que no se pudo leer para no perder sus perfiles y preferencias
+    // al guardar encima los valores por defecto
+    fn backup_unreadable(path: &Path) -> Result<PathBuf> {
+        let backup = path.with_extension("json.invalido");
+        std::fs::rename(path, &backup)
+            .with_context(|| format!("No se pudo respaldar {}", path.display()))?;
+        Ok(backup)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(Self::get_config_path(), content)?;
+        Ok(())
+    }
+
+    // Carga, modifica y guarda la configuración en un solo paso
+    pub fn update(f: impl FnOnce(&mut AppConfig)) {
+        let path = Self::get_config_path();
+        let mut config = match Self::read_from(&path) {
+            Ok(config) => config.unwrap_or_default(),
+            Err(e) => {
+                log::error!("Error al cargar configuración: {:#}", e);
+                match Self::backup_unreadable(&path) {
+                    Ok(backup) => log::warn!("Configuración inválida respaldada en {}", backup.display()),
+                    Err(e) => {
+                        log::error!("No se guarda la configuración para no sobrescribir el archivo: {:#}", e);
+                        return;
+                    }
+                }
+                Self::default()
+            }
+        };
+        f(&mut config);
+        if let Err(e) = config.save() {
+            log::error!("Error al guardar configuración: {}", e);
+        }
+    }
+}
+
+// Perfil exportable para llevar la conexión y las preferencias a otro equipo
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileFile {
+    pub connection: DatabaseConfig,
+    #[serde(default)]
+    pub preferences: ProfilePreferences,
+}
+
+// Preferencias que viajan en un perfil. Solo se exportan las de esta lista: los perfiles
+// guardados pueden tener contraseñas, y las UV recientes, los valores por defecto de
+// personas y los mapeos de importación usan ids de la base de origen. El historial de
+// búsqueda contiene RUT y nombres de personas, por lo que solo se incluye si se pide.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfilePreferences {
+    pub query_type: Option<QueryType>,
+    pub insertion_type: Option<InsertionType>,
+    pub page_size: Option<i64>,
+    pub density: Option<UiDensity>,
+    pub min_age: Option<i32>,
+    pub keepalive_secs: Option<u64>,
+    pub row_limit_warning: Option<i64>,
+    pub hidden_columns: Option<HashMap<String, Vec<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_history: Option<HashMap<String, Vec<String>>>,
+}
+
+impl ProfilePreferences {
+    fn from_config(config: AppConfig, include_search_history: bool) -> Self {
+        Self {
+            query_type: config.query_type,
+            insertion_type: config.insertion_type,
+            page_size: config.page_size,
+            density: Some(config.density),
+            min_age: config.min_age,
+            keepalive_secs: config.keepalive_secs,
+            row_limit_warning: config.row_limit_warning,
+            hidden_columns: Some(config.hidden_columns),
+            search_history: include_search_history.then_some(config.search_history),
+        }
+    }
+
+    // Copia en la configuración cada preferencia presente en el perfil
+    fn apply_to(self, config: &mut AppConfig) {
+        if self.query_type.is_some() {
+            config.query_type = self.query_type;
+        }
+        if self.insertion_type.is_some() {
+            config.insertion_type = self.insertion_type;
+        }
+        if self.page_size.is_some() {
+            config.page_size = self.page_size;
+        }
+        if let Some(density) = self.density {
+            config.density = density;
+        }
+        if self.min_age.is_some() {
+            config.min_age = self.min_age;
+        }
+        if self.keepalive_secs.is_some() {
+            config.keepalive_secs = self.keepalive_secs;
+        }
+        if self.row_limit_warning.is_some() {
+            config.row_limit_warning = self.row_limit_warning;
+        }
+        if let Some(hidden_columns) = self.hidden_columns {
+            config.hidden_columns = hidden_columns;
+        }
+        if let Some(search_history) = self.search_history {
+            config.search_history = search_history;
+        }
+    }
+}
+
+impl ProfileFile {
+    // La contraseña y el historial de búsqueda se omiten salvo que se pidan explícitamente
+    pub fn export(connection: &DatabaseConfig, include_password: bool, include_search_history: bool, path: &Path) -> Result<()> {
+        let mut connection = connection.clone();
+        if !include_password {
+            connection.password.clear();
+        }
+        let preferences = ProfilePreferences::from_config(AppConfig::load(), include_search_history);
+        let profile = ProfileFile { connection, preferences };
+        let content = serde_json::to_string_pretty(&profile)?;
+        std::fs::write(path, content)
+            .with_context(|| format!("No se pudo escribir {}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn import(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("No se pudo leer {}", path.display()))?;
+        let profile = serde_json::from_str(&content)
+            .context("El archivo no es un perfil válido")?;
+        Ok(profile)
+    }
+
+    pub fn apply_preferences(&self) {
+        let preferences = self.preferences.clone();
+        AppConfig::update(|config| preferences.apply_to(config));
+    }
+}
+
+// Criterios de una búsqueda guardados en un archivo, para repetir exactamente la misma
+// consulta más adelante. El campo "consulta" indica a qué listado pertenecen.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "consulta", content = "filtros")]
+pub enum FilterCriteria {
+    Personas(PersonaFilter),
+    Organizaciones(OrganizacionFilter),
+    Actividades(ActividadFilter),
+}
+
+impl FilterCriteria {
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, content)
+            .with_context(|| format!("No se pudo escribir {}", path.display()))?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("No se pudo leer {}", path.display()))?;
+        let criteria = serde_json::from_str(&content)
+            .context("El archivo no contiene criterios de búsqueda válidos")?;
+        Ok(criteria)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configuracion_invalida_es_error_y_se_respalda() {
+        let dir = std::env::temp_dir().join(format!("config_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CONFIG_FILE);
+
+        assert!(AppConfig::read_from(&path).unwrap().is_none());
+
+        std::fs::write(&path, "{ no es json").unwrap();
+        assert!(AppConfig::read_from(&path).is_err());
+
+        let backup = AppConfig::backup_unreadable(&path).unwrap();
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_to_string(&backup).unwrap(), "{ no es json");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn perfil_omite_historial_salvo_que_se_pida() {
+        let config = AppConfig {
+            page_size: Some(50),
+            recent_uvs: vec![3],
+            search_history: HashMap::from([("rut".to_string(), vec!["12345678-9".to_string()])]),
+            ..AppConfig::default()
+        };
+
+        let preferences = ProfilePreferences::from_config(config.clone(), false);
+        assert!(preferences.search_history.is_none());
+        let json = serde_json::to_string(&preferences).unwrap();
+        assert!(!json.contains("12345678-9"));
+        assert!(!json.contains("recent_uvs"));
+
+        let preferences = ProfilePreferences::from_config(config, true);
+        assert!(preferences.search_history.is_some());
+    }
+
+    #[test]
+    fn perfil_importado_aplica_lo_exportado() {
+        let origen = AppConfig {
+            page_size: Some(25),
+            min_age: Some(65),
+            density: UiDensity::Compacta,
+            hidden_columns: HashMap::from([("personas".to_string(), vec!["Email".to_string()])]),
+            ..AppConfig::default()
+        };
+        let mut destino = AppConfig { recent_uvs: vec![7], ..AppConfig::default() };
+        ProfilePreferences::from_config(origen, false).apply_to(&mut destino);
+
+        assert_eq!(destino.page_size, Some(25));
+        assert_eq!(destino.min_age, Some(65));
+        assert_eq!(destino.density, UiDensity::Compacta);
+        assert_eq!(destino.hidden_columns["personas"], vec!["Email".to_string()]);
+        assert_eq!(destino.recent_uvs, vec![7]);
+    }
+}
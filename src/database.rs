@@ -1,44 +1,488 @@
 use anyhow::{Context, Result};
-use tokio_postgres::{Client, NoTls};
+use tokio_postgres::{CancelToken, Client, NoTls, Row, Transaction};
+use tokio_postgres::types::ToSql;
+use crate::migrations::MIGRATIONS;
 use crate::models::*;
+use crate::utils;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// Tablas que la aplicación consulta; los centros comunitarios son opcionales
+const REQUIRED_TABLES: [&str; 14] = [
+    "mac_macrosectores",
+    "uv_unidadesvecinales",
+    "gen_generos",
+    "nac_nacionalidades",
+    "org_orgcomunitarias",
+    "org_telefonos",
+    "per_personasmayores",
+    "per_telefonos",
+    "tal_talleres",
+    "act_actividades",
+    "via_viajes",
+    "ben_beneficios",
+    "per_org",
+    "asis_actividades",
+];
+
+// Reintentos para consultas de lectura ante fallas transitorias de red
+const READ_RETRY_ATTEMPTS: u32 = 3;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
+
+// Plazo para abrir la conexión y para que responda el ping del keep-alive. Sin él, una
+// conexión cortada por un firewall deja la espera colgada hasta que TCP se rinde (minutos)
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const KEEPALIVE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Tiempo máximo de una consulta de la consola SQL; mientras corre retiene la conexión
+pub const SQL_CONSOLE_TIMEOUT_SECS: u64 = 30;
+
+// Errores que `with_retry` sabe clasificar como transitorios
+trait TransientError: std::fmt::Display {
+    fn is_transient(&self) -> bool;
+}
+
+// Solo se reintentan errores de conexión/IO; los errores del servidor (constraints,
+// sintaxis, permisos) se devuelven de inmediato salvo la clase 08 (connection exception)
+impl TransientError for tokio_postgres::Error {
+    fn is_transient(&self) -> bool {
+        if let Some(code) = self.code() {
+            return code.code().starts_with("08");
+        }
+        self.is_closed()
+            || std::error::Error::source(self).is_some_and(|source| source.is::<std::io::Error>())
+    }
+}
+
+// Ejecuta `f(sql)` hasta `attempts` veces, esperando 200 ms, 400 ms, ... entre intentos.
+// Con RUST_LOG=db_manager_app=trace se registra cada consulta con su duración total.
+async fn with_retry<'q, T, E, F, Fut>(attempts: u32, sql: &'q str, mut f: F) -> Result<T, E>
+where
+    E: TransientError,
+    F: FnMut(&'q str) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let started = Instant::now();
+    let mut attempt = 1;
+    loop {
+        match f(sql).await {
+            Err(e) if attempt < attempts && e.is_transient() => {
+                log::debug!("Reintentando consulta tras error transitorio ({}/{}): {}", attempt, attempts, e);
+                tokio::time::sleep(RETRY_BASE_DELAY * 2u32.pow(attempt - 1)).await;
+                attempt += 1;
+            }
+            result => {
+                if log::log_enabled!(log::Level::Trace) {
+                    let sql = sql.split_whitespace().collect::<Vec<_>>().join(" ");
+                    log::trace!("query {} took {:?}", sql, started.elapsed());
+                }
+                return result;
+            }
+        }
+    }
+}
+
+// Consultas de un solo intento (escrituras y lecturas que no pasan por `with_retry`) con el
+// mismo registro de duración a nivel trace
+trait TracedClient {
+    async fn execute_traced(&self, sql: &str, params: &[&(dyn ToSql + Sync)]) -> Result<u64, tokio_postgres::Error>;
+    async fn query_traced(&self, sql: &str, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<Row>, tokio_postgres::Error>;
+    async fn query_one_traced(&self, sql: &str, params: &[&(dyn ToSql + Sync)]) -> Result<Row, tokio_postgres::Error>;
+    async fn query_opt_traced(&self, sql: &str, params: &[&(dyn ToSql + Sync)]) -> Result<Option<Row>, tokio_postgres::Error>;
+}
+
+impl TracedClient for Client {
+    async fn execute_traced(&self, sql: &str, params: &[&(dyn ToSql + Sync)]) -> Result<u64, tokio_postgres::Error> {
+        with_retry(1, sql, |sql| self.execute(sql, params)).await
+    }
+
+    async fn query_traced(&self, sql: &str, params: &[&(dyn ToSql + Sync)]) -> Result<Vec<Row>, tokio_postgres::Error> {
+        with_retry(1, sql, |sql| self.query(sql, params)).await
+    }
+
+    async fn query_one_traced(&self, sql: &str, params: &[&(dyn ToSql + Sync)]) -> Result<Row, tokio_postgres::Error> {
+        with_retry(1, sql, |sql| self.query_one(sql, params)).await
+    }
+
+    async fn query_opt_traced(&self, sql: &str, params: &[&(dyn ToSql + Sync)]) -> Result<Option<Row>, tokio_postgres::Error> {
+        with_retry(1, sql, |sql| self.query_opt(sql, params)).await
+    }
+}
+
+// Operación que se ejecuta dentro de una transacción (ver `DatabaseManager::with_transaction`)
+type TransactionFuture<'t, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 't>>;
+
+// Mensaje legible para violaciones de constraints conocidos del esquema.
+// `on_delete` indica que la violación de FK ocurrió al eliminar el registro referenciado.
+fn constraint_message(constraint: &str, on_delete: bool) -> Option<&'static str> {
+    let message = match constraint {
+        "chk_per_email_formato" | "chk_per_email_formato_temp" | "chk_org_email_formato" => {
+            "El email no tiene un formato válido"
+        }
+        "chk_per_rut_formato" => "El RUT debe tener el formato 12345678-9",
+        "per_personasmayores_per_rut_key" => "Ya existe una persona registrada con ese RUT",
+        "org_orgcomunitarias_org_nombre_key" => "Ya existe una organización con ese nombre",
+        "mac_macrosectores_mac_nombre_key" => "Ya existe un macrosector con ese nombre",
+        "gen_generos_gen_genero_key" => "Ya existe un género con ese nombre",
+        "nac_nacionalidades_nac_nacionalidad_key" => "Ya existe una nacionalidad con ese nombre",
+        "uv_unidadesvecinales_uv_nombre_key" => "Ya existe una unidad vecinal con ese nombre",
+        "tal_talleres_tal_nombre_key" => "Ya existe un taller con ese nombre",
+        "cen_cencomunitarios_cen_nombre_key" => "Ya existe un centro comunitario con ese nombre",
+        "ben_beneficios_ben_codigo_key" => "Ya existe un beneficio con ese código",
+        "uq_act_nombre_fecha_uv" => "Ya existe una actividad con ese nombre, fecha y unidad vecinal",
+        "chk_act_fechas" => "La fecha de fin no puede ser anterior a la fecha de inicio",
+        "fk_per_uv" | "fk_org_uv" | "fk_act_uv" | "fk_cen_uv" | "fk_via_uv" => {
+            if on_delete {
+                "La unidad vecinal tiene registros asociados y no puede eliminarse"
+            } else {
+                "La unidad vecinal seleccionada no existe"
+            }
+        }
+        "chk_pt_numero_formato" => "El número de teléfono no tiene un formato válido",
+        "uq_pt_per_tipo" | "uq_ot_org_tipo" => "Ya existe un teléfono de ese tipo registrado",
+        "fk_pt_per" => "La persona seleccionada no existe",
+        "fk_ot_org" => "La organización seleccionada no existe",
+        "fk_po_per" => "La persona seleccionada no existe",
+        "fk_po_org" => "La organización seleccionada no existe",
+        "uq_asisact" => "La persona ya participa en esta actividad",
+        "fk_asisact_per" => "La persona seleccionada no existe",
+        "fk_asisact_act" => "La actividad seleccionada no existe",
+        "fk_uv_mac" => {
+            if on_delete {
+                "El macrosector tiene unidades vecinales asociadas y no puede eliminarse"
+            } else {
+                "El macrosector seleccionado no existe"
+            }
+        }
+        _ => return None,
+    };
+    Some(message)
+}
+
+// Traduce errores de constraints a mensajes en español; el resto se devuelve sin cambios
+fn friendly_db_error(error: tokio_postgres::Error) -> anyhow::Error {
+    if let Some(db_error) = error.as_db_error() {
+        // Clase 23: violaciones de integridad (unique, foreign key, check, not null)
+        if db_error.code().code().starts_with("23") {
+            let on_delete = db_error.message().starts_with("update or delete");
+            if let Some(message) = db_error.constraint().and_then(|c| constraint_message(c, on_delete)) {
+                return anyhow::anyhow!(message);
+            }
+        }
+    }
+    error.into()
+}
+
+// Ejecuta un listado paginado: cuenta las filas que cumplen el filtro y trae solo la página
+// pedida. `query` no lleva ORDER BY; se agrega `order_by` antes de LIMIT/OFFSET
+// (LIMIT NULL equivale a sin límite).
+async fn query_page(
+    client: &Client,
+    query: &str,
+    order_by: &str,
+    params: &[&(dyn ToSql + Sync)],
+    page: &PageRequest,
+) -> Result<(Vec<Row>, i64), tokio_postgres::Error> {
+    let count_query = format!("SELECT COUNT(*) FROM ({}) AS filtrado", query);
+    let total: i64 = with_retry(READ_RETRY_ATTEMPTS, count_query.as_str(), |sql| client.query_one(sql, params)).await?.get(0);
+
+    let mut params = params.to_vec();
+    params.push(&page.limit);
+    params.push(&page.offset);
+    let paged_query = format!("{} ORDER BY {} LIMIT ${} OFFSET ${}", query, order_by, params.len() - 1, params.len());
+    let rows = with_retry(READ_RETRY_ATTEMPTS, paged_query.as_str(), |sql| client.query(sql, &params)).await?;
+    Ok((rows, total))
+}
+
+// Consultas base de los listados, compartidas por los filtros y la búsqueda global
+const PERSONA_SELECT: &str =
+    "SELECT per.per_id, per.per_rut, per.per_prinombre, per.per_segnombre, per.per_priapellido, per.per_segapellido, 
+            per.per_genid, per.per_nacid, per.per_fechadenac, per.per_direccion, per.per_email, per.per_uvid, 
+            per.per_fecha_registro, per.per_fecha_modificacion, gen.gen_genero, nac.nac_nacionalidad, uv.uv_nombre 
+     FROM per_personasmayores per 
+     LEFT JOIN gen_generos gen ON per.per_genid = gen.gen_id 
+     LEFT JOIN nac_nacionalidades nac ON per.per_nacid = nac.nac_id 
+     LEFT JOIN uv_unidadesvecinales uv ON per.per_uvid = uv.uv_id";
+
+const ORGANIZACION_SELECT: &str =
+    "SELECT org.org_id, org.org_nombre, org.org_direccion, org.org_uvid, org.org_fechaconst, org.org_perjuridica, org.org_email, 
+            org.org_fecha_registro, org.org_fecha_modificacion, uv.uv_nombre 
+     FROM org_orgcomunitarias org 
+     LEFT JOIN uv_unidadesvecinales uv ON org.org_uvid = uv.uv_id";
+
+const ACTIVIDAD_SELECT: &str =
+    "SELECT act.act_id, act.act_nombre, act.act_uvid, act.act_fecha_ini, act.act_fecha_fin, act.act_descripcion, act.act_talid, 
+            act.act_fecha_registro, act.act_fecha_modificacion, uv.uv_nombre, tal.tal_nombre 
+     FROM act_actividades act 
+     LEFT JOIN uv_unidadesvecinales uv ON act.act_uvid = uv.uv_id 
+     LEFT JOIN tal_talleres tal ON act.act_talid = tal.tal_id";
+
+// Columna de texto libre que se muestra vacía si viene NULL
+fn text_or_empty(row: &Row, column: &str) -> Result<String, tokio_postgres::Error> {
+    Ok(empty_if_null(row.try_get(column)?))
+}
+
+fn empty_if_null(value: Option<String>) -> String {
+    value.unwrap_or_default()
+}
+
+// Los mapeos de filas usan try_get: un valor inesperado (p. ej. NULL en una base con datos
+// sucios) se propaga como error visible en lugar de provocar un panic en la tarea asíncrona,
+// que dejaría la vista esperando un resultado que nunca llega
+fn persona_from_row(row: &Row) -> Result<PersonaMayor, tokio_postgres::Error> {
+    Ok(PersonaMayor {
+        per_id: row.try_get("per_id")?,
+        per_rut: row.try_get("per_rut")?,
+        per_prinombre: row.try_get("per_prinombre")?,
+        per_segnombre: row.try_get("per_segnombre")?,
+        per_priapellido: row.try_get("per_priapellido")?,
+        per_segapellido: row.try_get("per_segapellido")?,
+        per_genid: row.try_get("per_genid")?,
+        per_nacid: row.try_get("per_nacid")?,
+        per_fechadenac: row.try_get("per_fechadenac")?,
+        per_direccion: text_or_empty(row, "per_direccion")?,
+        per_email: row.try_get("per_email")?,
+        per_uvid: row.try_get("per_uvid")?,
+        per_fecha_registro: row.try_get("per_fecha_registro")?,
+        per_fecha_modificacion: row.try_get("per_fecha_modificacion")?,
+        gen_genero: row.try_get("gen_genero")?,
+        nac_nacionalidad: row.try_get("nac_nacionalidad")?,
+        uv_nombre: row.try_get("uv_nombre")?,
+    })
+}
+
+fn organizacion_from_row(row: &Row) -> Result<OrganizacionComunitaria, tokio_postgres::Error> {
+    Ok(OrganizacionComunitaria {
+        org_id: row.try_get("org_id")?,
+        org_nombre: row.try_get("org_nombre")?,
+        org_direccion: text_or_empty(row, "org_direccion")?,
+        org_uvid: row.try_get("org_uvid")?,
+        org_fechaconst: row.try_get("org_fechaconst")?,
+        org_perjuridica: text_or_empty(row, "org_perjuridica")?,
+        org_email: row.try_get("org_email")?,
+        org_fecha_registro: row.try_get("org_fecha_registro")?,
+        org_fecha_modificacion: row.try_get("org_fecha_modificacion")?,
+        uv_nombre: row.try_get("uv_nombre")?,
+    })
+}
+
+fn actividad_from_row(row: &Row) -> Result<Actividad, tokio_postgres::Error> {
+    Ok(Actividad {
+        act_id: row.try_get("act_id")?,
+        act_nombre: row.try_get("act_nombre")?,
+        act_uvid: row.try_get("act_uvid")?,
+        act_fecha_ini: row.try_get("act_fecha_ini")?,
+        act_fecha_fin: row.try_get("act_fecha_fin")?,
+        act_descripcion: row.try_get("act_descripcion")?,
+        act_talid: row.try_get("act_talid")?,
+        act_fecha_registro: row.try_get("act_fecha_registro")?,
+        act_fecha_modificacion: row.try_get("act_fecha_modificacion")?,
+        uv_nombre: row.try_get("uv_nombre")?,
+        tal_nombre: row.try_get("tal_nombre")?,
+    })
+}
 
 pub struct DatabaseManager {
     client: Option<Client>,
+    config: Option<DatabaseConfig>,
+    // Si la extensión unaccent está disponible para búsquedas sin acentos
+    unaccent: bool,
 }
 
 impl DatabaseManager {
     pub fn new() -> Self {
-        Self { client: None }
+        Self { client: None, config: None, unaccent: false }
+    }
+
+    // Condición de búsqueda por texto que ignora mayúsculas y, si se puede, acentos
+    // ("jose" encuentra "José")
+    fn text_match(&self, column: &str, param: usize) -> String {
+        if self.unaccent {
+            format!("unaccent({}) ILIKE unaccent(${})", column, param)
+        } else {
+            format!("{} ILIKE ${}", column, param)
+        }
+    }
+
+    // Nombre de la base de datos de la conexión activa
+    pub fn database_name(&self) -> Option<String> {
+        self.config.as_ref().map(|c| c.database.clone())
     }
 
-    pub async fn connect(&mut self, config: &DatabaseConfig) -> Result<()> {
-        let connection_string = format!(
-            "host={} port={} user={} password={} dbname={}",
-            config.host, config.port, config.username, config.password, config.database
-        );
+    // Token para cancelar en el servidor la consulta en curso de esta conexión
+    pub fn cancel_token(&self) -> Option<CancelToken> {
+        self.client.as_ref().map(|client| client.cancel_token())
+    }
+
+    // Pide al servidor abortar la consulta asociada al token. Es independiente del
+    // gestor porque el lock lo mantiene la tarea que ejecuta la consulta.
+    pub async fn cancel_query(token: CancelToken) -> Result<()> {
+        token.cancel_query(NoTls).await.context("No se pudo cancelar la consulta")?;
+        Ok(())
+    }
+
+    // Configuración de la conexión activa, sin la contraseña
+    pub fn connection_config(&self) -> Option<DatabaseConfig> {
+        self.config.clone().map(|config| DatabaseConfig { password: String::new(), ..config })
+    }
+
+    // Versión del servidor PostgreSQL, tal como la informa SELECT version()
+    pub async fn server_version(&self) -> Result<String> {
+        if let Some(client) = &self.client {
+            let row = with_retry(READ_RETRY_ATTEMPTS, "SELECT version()", |sql| client.query_one(sql, &[])).await?;
+            Ok(row.get(0))
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
 
-        let (client, connection) = tokio_postgres::connect(&connection_string, NoTls)
+    // Configuración de conexión armada con los setters de tokio_postgres: a diferencia de una
+    // cadena "clave=valor", los espacios, comillas o barras en las credenciales no requieren escape
+    fn pg_config(config: &DatabaseConfig) -> tokio_postgres::Config {
+        let mut pg_config = tokio_postgres::Config::new();
+        pg_config
+            .host(&config.host)
+            .port(config.port)
+            .user(&config.username)
+            .password(&config.password)
+            .dbname(&config.database)
+            .connect_timeout(CONNECT_TIMEOUT);
+        pg_config
+    }
+
+    async fn open_client(config: &DatabaseConfig) -> Result<Client> {
+        let (client, connection) = Self::pg_config(config)
+            .connect(NoTls)
             .await
             .context("Error al conectar con la base de datos")?;
 
         tokio::spawn(async move {
             if let Err(e) = connection.await {
-                eprintln!("Error en la conexión: {}", e);
+                log::error!("Error en la conexión: {}", e);
             }
         });
 
-        self.client = Some(client);
-        
-        // Aplicar fix temporal del constraint de email automáticamente
-        if let Err(e) = self.fix_email_constraint_temp().await {
-            println!("ADVERTENCIA: No se pudo aplicar el fix del constraint de email: {}", e);
+        Ok(client)
+    }
+
+    // Conecta y devuelve las tablas del esquema que faltan. Los ajustes de sesión solo se
+    // aplican con el esquema completo; si falta alguna tabla se puede usar `create_schema`.
+    pub async fn connect(&mut self, config: &DatabaseConfig) -> Result<Vec<String>> {
+        self.client = Some(Self::open_client(config).await?);
+        self.config = Some(config.clone());
+
+        let missing = self.verify_schema().await?;
+        if missing.is_empty() {
+            self.prepare_session().await;
+        }
+        Ok(missing)
+    }
+
+    // Tablas requeridas que no existen en el esquema actual de la conexión
+    pub async fn verify_schema(&self) -> Result<Vec<String>> {
+        if let Some(client) = &self.client {
+            let required: Vec<&str> = REQUIRED_TABLES.to_vec();
+            let rows = client
+                .query_traced(
+                    "SELECT table_name::text AS table_name FROM information_schema.tables 
+                     WHERE table_schema = current_schema() AND table_name = ANY($1)",
+                    &[&required],
+                )
+                .await?;
+            let existing: Vec<String> = rows.iter().map(|row| row.get("table_name")).collect();
+            Ok(REQUIRED_TABLES.iter()
+                .filter(|table| !existing.iter().any(|e| e == *table))
+                .map(|table| table.to_string())
+                .collect())
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
         }
-        
+    }
+
+    // Crea el esquema comunitario aplicando todas las migraciones y prepara la sesión.
+    // Si la base tiene solo parte de las tablas, la migración inicial falla sin cambios.
+    pub async fn create_schema(&mut self) -> Result<()> {
+        self.run_migrations().await?;
+        self.prepare_session().await;
         Ok(())
     }
 
+    // Ajustes de esquema y extensiones que la aplicación necesita en cada conexión
+    async fn prepare_session(&mut self) {
+        // Migraciones pendientes (p. ej. columnas de auditoría en bases existentes)
+        if let Err(e) = self.run_migrations().await {
+            log::warn!("No se pudieron aplicar las migraciones: {}", e);
+        }
+
+        // Sin privilegios para crear la extensión se busca con ILIKE simple
+        self.unaccent = match self.ensure_unaccent().await {
+            Ok(()) => true,
+            Err(e) => {
+                log::warn!("Extensión unaccent no disponible, las búsquedas distinguirán acentos: {}", e);
+                false
+            }
+        };
+    }
+
     pub async fn disconnect(&mut self) {
         self.client = None;
+        self.config = None;
+        self.unaccent = false;
+    }
+
+    // Prueba las credenciales con una conexión temporal, sin tocar la conexión activa
+    // ni aplicar cambios de esquema
+    pub async fn probe_connection(config: &DatabaseConfig) -> Result<bool> {
+        let manager = Self { client: Some(Self::open_client(config).await?), config: None, unaccent: false };
+        manager.test_connection().await
+    }
+
+    // Reabre la conexión con la configuración del último connect, tras perderla
+    pub async fn reconnect(&mut self) -> Result<()> {
+        let config = self.config.clone()
+            .ok_or_else(|| anyhow::anyhow!("No hay una conexión previa que restablecer"))?;
+        self.client = Some(Self::open_client(&config).await?);
+        self.prepare_session().await;
+        Ok(())
+    }
+
+    // Ejecuta SELECT 1 cada `interval` para que el servidor o un firewall no cierren la
+    // conexión inactiva. Si el ping falla o no responde en KEEPALIVE_TIMEOUT se descarta el
+    // cliente (la configuración se conserva para `reconnect`) y se avisa por `lost`; así el
+    // mutex no queda tomado por un ping colgado. Termina sola al desconectar.
+    pub fn spawn_keepalive(
+        db_manager: Arc<tokio::sync::Mutex<Self>>,
+        interval: Duration,
+        lost: tokio::sync::mpsc::UnboundedSender<String>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                tokio::time::sleep(interval).await;
+                let mut db = db_manager.lock().await;
+                let Some(client) = &db.client else {
+                    return;
+                };
+                let error = match tokio::time::timeout(KEEPALIVE_TIMEOUT, client.simple_query("SELECT 1")).await {
+                    Ok(Ok(_)) => None,
+                    Ok(Err(e)) => Some(e.to_string()),
+                    Err(_) => Some(format!("el servidor no respondió en {} s", KEEPALIVE_TIMEOUT.as_secs())),
+                };
+                if let Some(error) = error {
+                    log::warn!("Falló el keep-alive, la conexión se da por perdida: {}", error);
+                    db.client = None;
+                    let _ = lost.send(error);
+                    return;
+                }
+                log::trace!("keep-alive ok");
+            }
+        })
+    }
+
+    // Hay un cliente abierto; no consulta al servidor (para eso está test_connection)
+    pub fn is_connected(&self) -> bool {
+        self.client.as_ref().is_some_and(|client| !client.is_closed())
     }
 
     pub async fn test_connection(&self) -> Result<bool> {
@@ -54,39 +498,140 @@ impl DatabaseManager {
 
     pub async fn get_dashboard_stats(&self) -> Result<DashboardStats> {
         if let Some(client) = &self.client {
-            let personas_row = client.query_one("SELECT COUNT(*) as count FROM per_personasmayores", &[]).await?;
+            let personas_row = client.query_one_traced("SELECT COUNT(*) as count FROM per_personasmayores", &[]).await?;
             let personas_count: i64 = personas_row.get("count");
 
-            let organizaciones_row = client.query_one("SELECT COUNT(*) as count FROM org_orgcomunitarias", &[]).await?;
+            let organizaciones_row = client.query_one_traced("SELECT COUNT(*) as count FROM org_orgcomunitarias", &[]).await?;
             let organizaciones_count: i64 = organizaciones_row.get("count");
 
-            let actividades_row = client.query_one("SELECT COUNT(*) as count FROM act_actividades", &[]).await?;
+            let actividades_row = client.query_one_traced("SELECT COUNT(*) as count FROM act_actividades", &[]).await?;
             let actividades_count: i64 = actividades_row.get("count");
 
-            let viajes_row = client.query_one("SELECT COUNT(*) as count FROM via_viajes", &[]).await?;
+            let viajes_row = client.query_one_traced("SELECT COUNT(*) as count FROM via_viajes", &[]).await?;
             let viajes_count: i64 = viajes_row.get("count");
 
+            // La tabla de centros es opcional en algunas instalaciones
+            let centros_row = client.query_one_traced("SELECT to_regclass('cen_cencomunitarios') IS NOT NULL AS existe", &[]).await?;
+            let centros_count: Option<i64> = if centros_row.get::<_, bool>("existe") {
+                let row = client.query_one_traced("SELECT COUNT(*) as count FROM cen_cencomunitarios", &[]).await?;
+                Some(row.get("count"))
+            } else {
+                None
+            };
+
+            // Distribución de edades calculada en SQL; los rangos sin personas aparecen con 0
+            let edades_rows = client.query_traced(
+                "SELECT r.rango, COUNT(p.per_id) AS count 
+                 FROM (VALUES ('60-69', 60, 69), ('70-79', 70, 79), ('80-89', 80, 89), ('90+', 90, 200)) AS r(rango, desde, hasta) 
+                 LEFT JOIN per_personasmayores p 
+                   ON date_part('year', age(p.per_fechadenac)) BETWEEN r.desde AND r.hasta 
+                 GROUP BY r.rango, r.desde 
+                 ORDER BY r.desde",
+                &[]
+            ).await?;
+            let edades_por_rango = edades_rows.iter()
+                .map(|row| (row.get("rango"), row.get("count")))
+                .collect();
+
+            // Personas por macrosector; los macrosectores sin personas aparecen con 0
+            let macro_rows = client.query_traced(
+                "SELECT mac.mac_id, mac.mac_nombre, COUNT(per.per_id) AS count 
+                 FROM mac_macrosectores mac 
+                 LEFT JOIN uv_unidadesvecinales uv ON uv.uv_macid = mac.mac_id 
+                 LEFT JOIN per_personasmayores per ON per.per_uvid = uv.uv_id 
+                 GROUP BY mac.mac_id, mac.mac_nombre 
+                 ORDER BY mac.mac_nombre",
+                &[]
+            ).await?;
+            let personas_por_macro = macro_rows.iter()
+                .map(|row| (row.get("mac_id"), row.get("mac_nombre"), row.get("count")))
+                .collect();
+
+            // Las actividades sin asistencias cuentan como 0 participantes
+            let participantes_row = client.query_one_traced(
+                "SELECT COALESCE(AVG(t.count), 0)::float8 AS promedio 
+                 FROM (SELECT COUNT(asis.asisact_id) AS count 
+                       FROM act_actividades act 
+                       LEFT JOIN asis_actividades asis ON asis.asis_actid = act.act_id 
+                       GROUP BY act.act_id) t",
+                &[]
+            ).await?;
+            let promedio_participantes: f64 = participantes_row.get("promedio");
+
+            // Actividades que comienzan este mes y personas registradas este mes
+            let mes_row = client.query_one_traced(
+                "SELECT 
+                    (SELECT COUNT(*) FROM act_actividades 
+                     WHERE date_trunc('month', act_fecha_ini) = date_trunc('month', now())) AS actividades, 
+                    (SELECT COUNT(*) FROM per_personasmayores 
+                     WHERE date_trunc('month', per_fecha_registro) = date_trunc('month', now())) AS personas",
+                &[]
+            ).await?;
+            let actividades_mes_actual: i64 = mes_row.get("actividades");
+            let nuevas_personas_mes: i64 = mes_row.get("personas");
+
+            let ultimas_actividades = self.recent_actividades(RECENT_ACTIVIDADES_LIMIT).await?;
+
             Ok(DashboardStats {
                 total_personas: personas_count,
                 total_organizaciones: organizaciones_count,
                 total_actividades: actividades_count,
                 total_viajes: viajes_count,
-                personas_por_macro: Vec::new(),
-                actividades_mes_actual: 0,
-                nuevas_personas_mes: 0,
+                total_centros: centros_count,
+                personas_por_macro,
+                edades_por_rango,
+                actividades_mes_actual,
+                nuevas_personas_mes,
+                promedio_participantes,
+                ultimas_actividades,
             })
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
         }
     }
 
+    // Personas por unidad vecinal dentro de un macrosector, como (uv_id, nombre, personas);
+    // las UV sin personas aparecen con 0
+    pub async fn personas_por_uv_in_macro(&self, mac_id: i32) -> Result<Vec<(i32, String, i64)>> {
+        if let Some(client) = &self.client {
+            let params: [&(dyn ToSql + Sync); 1] = [&mac_id];
+            let rows = with_retry(
+                READ_RETRY_ATTEMPTS,
+                "SELECT uv.uv_id, uv.uv_nombre, COUNT(per.per_id) AS count 
+                 FROM uv_unidadesvecinales uv 
+                 LEFT JOIN per_personasmayores per ON per.per_uvid = uv.uv_id 
+                 WHERE uv.uv_macid = $1 
+                 GROUP BY uv.uv_id, uv.uv_nombre 
+                 ORDER BY count DESC, uv.uv_nombre",
+                |sql| client.query(sql, &params),
+            ).await?;
+            Ok(rows.iter()
+                .map(|row| (row.get("uv_id"), row.get("uv_nombre"), row.get("count")))
+                .collect())
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Actividades más recientes según su fecha de inicio
+    pub async fn recent_actividades(&self, limit: i64) -> Result<Vec<Actividad>> {
+        if let Some(client) = &self.client {
+            let query = format!("{} ORDER BY act.act_fecha_ini DESC, act.act_id DESC LIMIT $1", ACTIVIDAD_SELECT);
+            let params: [&(dyn ToSql + Sync); 1] = [&limit];
+            let rows = with_retry(READ_RETRY_ATTEMPTS, query.as_str(), |sql| client.query(sql, &params)).await?;
+            Ok(rows.iter().map(actividad_from_row).collect::<Result<_, _>>()?)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
     pub async fn get_generos(&self) -> Result<Vec<Genero>> {
         if let Some(client) = &self.client {
-            let rows = client.query("SELECT gen_id, gen_genero FROM gen_generos ORDER BY gen_genero", &[]).await?;
-            let generos = rows.iter().map(|row| Genero {
-                gen_id: row.get("gen_id"),
-                gen_genero: row.get("gen_genero"),
-            }).collect();
+            let rows = with_retry(READ_RETRY_ATTEMPTS, "SELECT gen_id, gen_genero FROM gen_generos ORDER BY gen_genero", |sql| client.query(sql, &[])).await?;
+            let generos = rows.iter().map(|row| Ok(Genero {
+                gen_id: row.try_get("gen_id")?,
+                gen_genero: row.try_get("gen_genero")?,
+            })).collect::<Result<_, tokio_postgres::Error>>()?;
             Ok(generos)
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
@@ -95,11 +640,11 @@ impl DatabaseManager {
 
     pub async fn get_nacionalidades(&self) -> Result<Vec<Nacionalidad>> {
         if let Some(client) = &self.client {
-            let rows = client.query("SELECT nac_id, nac_nacionalidad FROM nac_nacionalidades ORDER BY nac_nacionalidad", &[]).await?;
-            let nacionalidades = rows.iter().map(|row| Nacionalidad {
-                nac_id: row.get("nac_id"),
-                nac_nacionalidad: row.get("nac_nacionalidad"),
-            }).collect();
+            let rows = with_retry(READ_RETRY_ATTEMPTS, "SELECT nac_id, nac_nacionalidad FROM nac_nacionalidades ORDER BY nac_nacionalidad", |sql| client.query(sql, &[])).await?;
+            let nacionalidades = rows.iter().map(|row| Ok(Nacionalidad {
+                nac_id: row.try_get("nac_id")?,
+                nac_nacionalidad: row.try_get("nac_nacionalidad")?,
+            })).collect::<Result<_, tokio_postgres::Error>>()?;
             Ok(nacionalidades)
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
@@ -108,19 +653,20 @@ impl DatabaseManager {
 
     pub async fn get_unidades_vecinales(&self) -> Result<Vec<UnidadVecinal>> {
         if let Some(client) = &self.client {
-            let rows = client.query(
+            let rows = with_retry(
+                READ_RETRY_ATTEMPTS,
                 "SELECT uv.uv_id, uv.uv_nombre, uv.uv_macid, mac.mac_nombre 
                  FROM uv_unidadesvecinales uv 
                  LEFT JOIN mac_macrosectores mac ON uv.uv_macid = mac.mac_id 
-                 ORDER BY uv.uv_nombre", 
-                &[]
+                 ORDER BY uv.uv_nombre",
+                |sql| client.query(sql, &[]),
             ).await?;
-            let unidades = rows.iter().map(|row| UnidadVecinal {
-                uv_id: row.get("uv_id"),
-                uv_nombre: row.get("uv_nombre"),
-                uv_macid: row.get("uv_macid"),
-                mac_nombre: row.get("mac_nombre"),
-            }).collect();
+            let unidades = rows.iter().map(|row| Ok(UnidadVecinal {
+                uv_id: row.try_get("uv_id")?,
+                uv_nombre: row.try_get("uv_nombre")?,
+                uv_macid: row.try_get("uv_macid")?,
+                mac_nombre: row.try_get("mac_nombre")?,
+            })).collect::<Result<_, tokio_postgres::Error>>()?;
             Ok(unidades)
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
@@ -129,115 +675,470 @@ impl DatabaseManager {
 
     pub async fn get_macro_sectores(&self) -> Result<Vec<MacroSector>> {
         if let Some(client) = &self.client {
-            let rows = client.query("SELECT mac_id, mac_nombre FROM mac_macrosectores ORDER BY mac_nombre", &[]).await?;
-            let macro_sectores = rows.iter().map(|row| MacroSector {
-                mac_id: row.get("mac_id"),
-                mac_nombre: row.get("mac_nombre"),
-            }).collect();
+            let rows = with_retry(READ_RETRY_ATTEMPTS, "SELECT mac_id, mac_nombre FROM mac_macrosectores ORDER BY mac_nombre", |sql| client.query(sql, &[])).await?;
+            let macro_sectores = rows.iter().map(|row| Ok(MacroSector {
+                mac_id: row.try_get("mac_id")?,
+                mac_nombre: row.try_get("mac_nombre")?,
+            })).collect::<Result<_, tokio_postgres::Error>>()?;
             Ok(macro_sectores)
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
         }
     }
 
-    pub async fn get_personas_mayores(&self, _filter: &PersonaFilter) -> Result<Vec<PersonaMayor>> {
+    // Ejecuta una consulta SELECT ad-hoc dentro de una transacción de solo lectura.
+    // Devuelve los nombres de columna y hasta `max_rows` filas con cada valor como texto;
+    // la consulta se aborta si supera SQL_CONSOLE_TIMEOUT_SECS.
+    pub async fn run_readonly_query(&self, sql: &str, max_rows: usize) -> Result<ReadonlyQueryResult> {
         if let Some(client) = &self.client {
-            let rows = client.query("SELECT per_id, per_rut, per_prinombre, per_segnombre, per_priapellido, per_segapellido, per_genid, per_nacid, per_fechadenac, per_direccion, per_email, per_uvid FROM per_personasmayores ORDER BY per_priapellido, per_prinombre", &[]).await?;
-            let mut personas = Vec::new();
+            let sql = sql.trim().trim_end_matches(';').trim();
+            let first_word = sql.split_whitespace().next().unwrap_or("");
+            if !first_word.eq_ignore_ascii_case("select") {
+                return Err(anyhow::anyhow!("Solo se permiten consultas SELECT"));
+            }
+            if sql.contains(';') {
+                return Err(anyhow::anyhow!("Solo se permite una sentencia por ejecución"));
+            }
+
+            client.batch_execute("BEGIN READ ONLY").await?;
+            let result = async {
+                client.batch_execute(&format!("SET LOCAL statement_timeout = '{}s'", SQL_CONSOLE_TIMEOUT_SECS)).await?;
+                // La sentencia preparada entrega los nombres de columna aunque no haya filas
+                let statement = client.prepare(sql).await?;
+                let columns: Vec<String> = statement.columns().iter().map(|c| c.name().to_string()).collect();
 
+                // Un cursor evita traer más filas de las que se muestran; se pide una de
+                // más para saber si el resultado quedó truncado
+                client.batch_execute(&format!("DECLARE consola NO SCROLL CURSOR FOR {}", sql)).await?;
+                let mut rows = Vec::new();
+                for message in client.simple_query(&format!("FETCH {} FROM consola", max_rows + 1)).await? {
+                    if let tokio_postgres::SimpleQueryMessage::Row(row) = message {
+                        let values = (0..row.len())
+                            .map(|i| row.get(i).unwrap_or("NULL").to_string())
+                            .collect();
+                        rows.push(values);
+                    }
+                }
+                let truncated = rows.len() > max_rows;
+                rows.truncate(max_rows);
+                Ok::<_, tokio_postgres::Error>(ReadonlyQueryResult { columns, rows, truncated })
+            }.await;
+            client.batch_execute("ROLLBACK").await?;
+
+            Ok(result?)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Reescribe todos los RUT existentes a su forma canónica en una sola transacción.
+    // Devuelve la cantidad de filas modificadas; si algo falla no se modifica ninguna.
+    pub async fn normalize_all_ruts(&mut self) -> Result<u64> {
+        self.with_transaction(|transaction| Box::pin(async move {
+            let rows = transaction.query("SELECT per_id, per_rut FROM per_personasmayores", &[]).await?;
+            let mut updated = 0;
             for row in rows {
-                personas.push(PersonaMayor {
-                    per_id: row.get("per_id"),
-                    per_rut: row.get("per_rut"),
-                    per_prinombre: row.get("per_prinombre"),
-                    per_segnombre: row.get("per_segnombre"),
-                    per_priapellido: row.get("per_priapellido"),
-                    per_segapellido: row.get("per_segapellido"),
-                    per_genid: row.get("per_genid"),
-                    per_nacid: row.get("per_nacid"),
-                    per_fechadenac: row.get("per_fechadenac"),
-                    per_direccion: row.get("per_direccion"),
-                    per_email: row.get("per_email"),
-                    per_uvid: row.get("per_uvid"),
-                    gen_genero: None,
-                    nac_nacionalidad: None,
-                    uv_nombre: None,
-                });
+                let per_id: i32 = row.get("per_id");
+                let rut: String = row.get("per_rut");
+                let normalized = utils::normalize_rut(&rut);
+                if normalized != rut {
+                    updated += transaction
+                        .execute("UPDATE per_personasmayores SET per_rut = $1, per_fecha_modificacion = now() WHERE per_id = $2", &[&normalized, &per_id])
+                        .await?;
+                }
             }
+            Ok(updated)
+        })).await
+    }
+
+    // Revisa problemas de calidad de datos que la validación anterior dejó pasar.
+    // El dígito verificador y el email se validan en Rust con las mismas reglas que los formularios.
+    pub async fn integrity_report(&self) -> Result<Vec<IntegrityFinding>> {
+        if let Some(client) = &self.client {
+            let personas = with_retry(
+                READ_RETRY_ATTEMPTS,
+                "SELECT per_id, per_rut FROM per_personasmayores ORDER BY per_id",
+                |sql| client.query(sql, &[]),
+            ).await?;
+            let ruts_invalidos = personas.iter()
+                .filter(|row| !utils::validate_rut_check_digit(&utils::normalize_rut(row.get("per_rut"))))
+                .map(|row| format!("id {}: RUT {}", row.get::<_, i32>("per_id"), row.get::<_, String>("per_rut")))
+                .collect();
+
+            let huerfanas = with_retry(
+                READ_RETRY_ATTEMPTS,
+                "SELECT per.per_id, per.per_rut, 
+                        uv.uv_id IS NULL AS sin_uv, gen.gen_id IS NULL AS sin_genero, nac.nac_id IS NULL AS sin_nacionalidad 
+                 FROM per_personasmayores per 
+                 LEFT JOIN uv_unidadesvecinales uv ON per.per_uvid = uv.uv_id 
+                 LEFT JOIN gen_generos gen ON per.per_genid = gen.gen_id 
+                 LEFT JOIN nac_nacionalidades nac ON per.per_nacid = nac.nac_id 
+                 WHERE uv.uv_id IS NULL OR gen.gen_id IS NULL OR nac.nac_id IS NULL 
+                 ORDER BY per.per_id",
+                |sql| client.query(sql, &[]),
+            ).await?;
+            let referencias_rotas = huerfanas.iter().map(|row| {
+                let faltantes: Vec<&str> = [("sin_uv", "UV"), ("sin_genero", "género"), ("sin_nacionalidad", "nacionalidad")]
+                    .iter()
+                    .filter(|(column, _)| row.get::<_, bool>(*column))
+                    .map(|(_, label)| *label)
+                    .collect();
+                format!("id {}: RUT {} ({} inexistente)", row.get::<_, i32>("per_id"), row.get::<_, String>("per_rut"), faltantes.join(", "))
+            }).collect();
+
+            let organizaciones = with_retry(
+                READ_RETRY_ATTEMPTS,
+                "SELECT org_id, org_nombre, org_email FROM org_orgcomunitarias WHERE org_email IS NOT NULL ORDER BY org_id",
+                |sql| client.query(sql, &[]),
+            ).await?;
+            let emails_invalidos = organizaciones.iter()
+                .filter(|row| !utils::validate_email(row.get("org_email")))
+                .map(|row| format!("id {}: {} <{}>", row.get::<_, i32>("org_id"), row.get::<_, String>("org_nombre"), row.get::<_, String>("org_email")))
+                .collect();
+
+            let actividades = with_retry(
+                READ_RETRY_ATTEMPTS,
+                "SELECT act_id, act_nombre, act_fecha_ini, act_fecha_fin FROM act_actividades 
+                 WHERE act_fecha_fin < act_fecha_ini ORDER BY act_id",
+                |sql| client.query(sql, &[]),
+            ).await?;
+            let fechas_invertidas = actividades.iter().map(|row| format!(
+                "id {}: {} ({} - {})",
+                row.get::<_, i32>("act_id"),
+                row.get::<_, String>("act_nombre"),
+                utils::format_date(&row.get("act_fecha_ini")),
+                utils::format_date(&row.get("act_fecha_fin")),
+            )).collect();
 
-            Ok(personas)
+            Ok(vec![
+                IntegrityFinding { descripcion: "Personas con dígito verificador de RUT inválido".to_string(), detalles: ruts_invalidos },
+                IntegrityFinding { descripcion: "Personas con UV, género o nacionalidad inexistente".to_string(), detalles: referencias_rotas },
+                IntegrityFinding { descripcion: "Organizaciones con email mal formado".to_string(), detalles: emails_invalidos },
+                IntegrityFinding { descripcion: "Actividades que terminan antes de comenzar".to_string(), detalles: fechas_invertidas },
+            ])
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
         }
     }
 
-    pub async fn get_organizaciones(&self, _filter: &OrganizacionFilter) -> Result<Vec<OrganizacionComunitaria>> {
+    pub async fn get_talleres(&self, page: &PageRequest) -> Result<PagedResult<Taller>> {
         if let Some(client) = &self.client {
-            let rows = client.query("SELECT org_id, org_nombre, org_direccion, org_uvid, org_fechaconst, org_perjuridica, org_email FROM org_orgcomunitarias ORDER BY org_nombre", &[]).await?;
-            let mut organizaciones = Vec::new();
+            let (rows, total) = query_page(client, "SELECT tal_id, tal_nombre FROM tal_talleres", "tal_nombre, tal_id", &[], page).await?;
+            let talleres = rows.iter().map(|row| Ok(Taller {
+                tal_id: row.try_get("tal_id")?,
+                tal_nombre: row.try_get("tal_nombre")?,
+            })).collect::<Result<_, tokio_postgres::Error>>()?;
+            Ok(PagedResult { rows: talleres, total })
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
 
-            for row in rows {
-                organizaciones.push(OrganizacionComunitaria {
-                    org_id: row.get("org_id"),
-                    org_nombre: row.get("org_nombre"),
-                    org_direccion: row.get("org_direccion"),
-                    org_uvid: row.get("org_uvid"),
-                    org_fechaconst: row.get("org_fechaconst"),
-                    org_perjuridica: row.get("org_perjuridica"),
-                    org_email: row.get("org_email"),
-                    uv_nombre: None,
-                });
+    pub async fn get_beneficios(&self, page: &PageRequest) -> Result<PagedResult<Beneficio>> {
+        if let Some(client) = &self.client {
+            let (rows, total) = query_page(client, "SELECT ben_id, ben_codigo, ben_descripcion FROM ben_beneficios", "ben_codigo, ben_id", &[], page).await?;
+            let beneficios = rows.iter().map(|row| Ok(Beneficio {
+                ben_id: row.try_get("ben_id")?,
+                ben_codigo: row.try_get("ben_codigo")?,
+                ben_descripcion: row.try_get("ben_descripcion")?,
+            })).collect::<Result<_, tokio_postgres::Error>>()?;
+            Ok(PagedResult { rows: beneficios, total })
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn get_personas_mayores(&self, filter: &PersonaFilter, page: &PageRequest) -> Result<PagedResult<PersonaMayor>> {
+        if let Some(client) = &self.client {
+            let mut query = format!("{} WHERE 1=1", PERSONA_SELECT);
+            let mut params: Vec<Box<dyn ToSql + Sync + Send>> = Vec::new();
+
+            if !filter.nombre.trim().is_empty() {
+                params.push(Box::new(utils::contains_pattern(filter.nombre.trim())));
+                query.push_str(&format!(
+                    " AND ({} OR {})",
+                    self.text_match("per.per_prinombre", params.len()),
+                    self.text_match("per.per_segnombre", params.len())
+                ));
+            }
+            if !filter.apellido.trim().is_empty() {
+                params.push(Box::new(utils::contains_pattern(filter.apellido.trim())));
+                query.push_str(&format!(
+                    " AND ({} OR {})",
+                    self.text_match("per.per_priapellido", params.len()),
+                    self.text_match("per.per_segapellido", params.len())
+                ));
+            }
+            // El RUT guardado se compara con la misma forma que el texto buscado
+            let rut = utils::rut_search_key(&filter.rut);
+            if !rut.is_empty() {
+                params.push(Box::new(utils::contains_pattern(&rut)));
+                query.push_str(&format!(
+                    " AND replace(replace(replace(per.per_rut, '.', ''), ' ', ''), '-', '') ILIKE ${}",
+                    params.len()
+                ));
             }
+            if let Some(genero_id) = filter.genero_id {
+                params.push(Box::new(genero_id));
+                query.push_str(&format!(" AND per.per_genid = ${}", params.len()));
+            }
+            if let Some(nacionalidad_id) = filter.nacionalidad_id {
+                params.push(Box::new(nacionalidad_id));
+                query.push_str(&format!(" AND per.per_nacid = ${}", params.len()));
+            }
+            if let Some(uv_id) = filter.unidad_vecinal_id {
+                params.push(Box::new(uv_id));
+                query.push_str(&format!(" AND per.per_uvid = ${}", params.len()));
+            }
+            if let Some(mac_id) = filter.macro_sector_id {
+                params.push(Box::new(mac_id));
+                query.push_str(&format!(" AND uv.uv_macid = ${}", params.len()));
+            }
+            let param_refs: Vec<&(dyn ToSql + Sync)> = params.iter().map(|p| p.as_ref() as &(dyn ToSql + Sync)).collect();
+            let (rows, total) = query_page(client, &query, &filter.orden.order_by(), &param_refs, page).await?;
+            Ok(PagedResult { rows: rows.iter().map(persona_from_row).collect::<Result<_, _>>()?, total })
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
 
-            Ok(organizaciones)
+    pub async fn get_organizaciones(&self, filter: &OrganizacionFilter, page: &PageRequest) -> Result<PagedResult<OrganizacionComunitaria>> {
+        if let Some(client) = &self.client {
+            let mut query = format!("{} WHERE 1=1", ORGANIZACION_SELECT);
+            let mut params: Vec<Box<dyn ToSql + Sync + Send>> = Vec::new();
+
+            if !filter.nombre.trim().is_empty() {
+                params.push(Box::new(utils::contains_pattern(filter.nombre.trim())));
+                query.push_str(&format!(" AND {}", self.text_match("org.org_nombre", params.len())));
+            }
+            if let Some(uv_id) = filter.unidad_vecinal_id {
+                params.push(Box::new(uv_id));
+                query.push_str(&format!(" AND org.org_uvid = ${}", params.len()));
+            }
+            if let Some(mac_id) = filter.macro_sector_id {
+                params.push(Box::new(mac_id));
+                query.push_str(&format!(" AND uv.uv_macid = ${}", params.len()));
+            }
+            if let Some(desde) = filter.fecha_const_desde {
+                params.push(Box::new(desde));
+                query.push_str(&format!(" AND org.org_fechaconst >= ${}", params.len()));
+            }
+            if let Some(hasta) = filter.fecha_const_hasta {
+                params.push(Box::new(hasta));
+                query.push_str(&format!(" AND org.org_fechaconst <= ${}", params.len()));
+            }
+            let param_refs: Vec<&(dyn ToSql + Sync)> = params.iter().map(|p| p.as_ref() as &(dyn ToSql + Sync)).collect();
+            let (rows, total) = query_page(client, &query, "org.org_nombre, org.org_id", &param_refs, page).await?;
+            Ok(PagedResult { rows: rows.iter().map(organizacion_from_row).collect::<Result<_, _>>()?, total })
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
         }
     }
 
-    pub async fn get_actividades(&self, _filter: &ActividadFilter) -> Result<Vec<Actividad>> {
+    pub async fn get_actividades(&self, filter: &ActividadFilter, page: &PageRequest) -> Result<PagedResult<Actividad>> {
         if let Some(client) = &self.client {
-            let rows = client.query("SELECT act_id, act_nombre, act_uvid, act_fecha_ini, act_fecha_fin, act_descripcion FROM actividades ORDER BY act_fecha_ini DESC", &[]).await?;
-            let mut actividades = Vec::new();
+            let mut query = format!("{} WHERE 1=1", ACTIVIDAD_SELECT);
+            let mut params: Vec<Box<dyn ToSql + Sync + Send>> = Vec::new();
 
-            for row in rows {
-                actividades.push(Actividad {
-                    act_id: row.get("act_id"),
-                    act_nombre: row.get("act_nombre"),
-                    act_uvid: row.get("act_uvid"),
-                    act_fecha_ini: row.get("act_fecha_ini"),
-                    act_fecha_fin: row.get("act_fecha_fin"),
-                    act_descripcion: row.get("act_descripcion"),
-                    uv_nombre: None,
-                });
+            if !filter.nombre.trim().is_empty() {
+                params.push(Box::new(utils::contains_pattern(filter.nombre.trim())));
+                query.push_str(&format!(" AND {}", self.text_match("act.act_nombre", params.len())));
             }
+            if let Some(uv_id) = filter.unidad_vecinal_id {
+                params.push(Box::new(uv_id));
+                query.push_str(&format!(" AND act.act_uvid = ${}", params.len()));
+            }
+            if let Some(mac_id) = filter.macro_sector_id {
+                params.push(Box::new(mac_id));
+                query.push_str(&format!(" AND uv.uv_macid = ${}", params.len()));
+            }
+            match (filter.fecha_desde, filter.fecha_hasta) {
+                (Some(desde), Some(hasta)) => {
+                    params.push(Box::new(desde));
+                    params.push(Box::new(hasta));
+                    query.push_str(&format!(" AND act.act_fecha_ini BETWEEN ${} AND ${}", params.len() - 1, params.len()));
+                }
+                (Some(desde), None) => {
+                    params.push(Box::new(desde));
+                    query.push_str(&format!(" AND act.act_fecha_ini >= ${}", params.len()));
+                }
+                (None, Some(hasta)) => {
+                    params.push(Box::new(hasta));
+                    query.push_str(&format!(" AND act.act_fecha_ini <= ${}", params.len()));
+                }
+                (None, None) => {}
+            }
+            // Se compara con la fecha local del cliente, la misma que usa la columna de estado
+            if let Some(estado) = filter.estado {
+                params.push(Box::new(chrono::Local::now().date_naive()));
+                query.push_str(&format!(" AND {}", estado.sql_condition(params.len())));
+            }
+            if let Some(tal_id) = filter.taller_id {
+                params.push(Box::new(tal_id));
+                query.push_str(&format!(" AND act.act_talid = ${}", params.len()));
+            }
+            let param_refs: Vec<&(dyn ToSql + Sync)> = params.iter().map(|p| p.as_ref() as &(dyn ToSql + Sync)).collect();
+            let (rows, total) = query_page(client, &query, "act.act_fecha_ini DESC, act.act_id DESC", &param_refs, page).await?;
+            Ok(PagedResult { rows: rows.iter().map(actividad_from_row).collect::<Result<_, _>>()?, total })
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Busca un término en personas (nombre, apellido, RUT), organizaciones y actividades
+    // (nombre), con un máximo de `GLOBAL_SEARCH_LIMIT` resultados por categoría
+    pub async fn global_search(&self, term: &str) -> Result<GlobalSearchResults> {
+        if let Some(client) = &self.client {
+            let pattern = utils::contains_pattern(term.trim());
+            let limit = GLOBAL_SEARCH_LIMIT;
+            let params: [&(dyn ToSql + Sync); 2] = [&pattern, &limit];
+
+            let personas_query = format!(
+                "{} WHERE {} OR {} OR {} OR {} OR per.per_rut ILIKE $1 
+                 ORDER BY per.per_priapellido, per.per_prinombre, per.per_id LIMIT $2",
+                PERSONA_SELECT,
+                self.text_match("per.per_prinombre", 1),
+                self.text_match("per.per_segnombre", 1),
+                self.text_match("per.per_priapellido", 1),
+                self.text_match("per.per_segapellido", 1)
+            );
+            let personas = with_retry(READ_RETRY_ATTEMPTS, personas_query.as_str(), |sql| client.query(sql, &params)).await?;
+
+            let organizaciones_query = format!("{} WHERE {} ORDER BY org.org_nombre, org.org_id LIMIT $2", ORGANIZACION_SELECT, self.text_match("org.org_nombre", 1));
+            let organizaciones = with_retry(READ_RETRY_ATTEMPTS, organizaciones_query.as_str(), |sql| client.query(sql, &params)).await?;
+
+            let actividades_query = format!("{} WHERE {} ORDER BY act.act_fecha_ini DESC, act.act_id DESC LIMIT $2", ACTIVIDAD_SELECT, self.text_match("act.act_nombre", 1));
+            let actividades = with_retry(READ_RETRY_ATTEMPTS, actividades_query.as_str(), |sql| client.query(sql, &params)).await?;
+
+            Ok(GlobalSearchResults {
+                personas: personas.iter().map(persona_from_row).collect::<Result<_, _>>()?,
+                organizaciones: organizaciones.iter().map(organizacion_from_row).collect::<Result<_, _>>()?,
+                actividades: actividades.iter().map(actividad_from_row).collect::<Result<_, _>>()?,
+            })
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn get_viajes(&self, filter: &ViajeFilter, page: &PageRequest) -> Result<PagedResult<Viaje>> {
+        if let Some(client) = &self.client {
+            let mut query = String::from(
+                "SELECT via.via_id, via.via_nombre, via.via_destino, via.via_fecha_salida, via.via_fecha_regreso, via.via_uvid, uv.uv_nombre 
+                 FROM via_viajes via 
+                 LEFT JOIN uv_unidadesvecinales uv ON via.via_uvid = uv.uv_id 
+                 WHERE 1=1"
+            );
+            let mut params: Vec<Box<dyn ToSql + Sync + Send>> = Vec::new();
+
+            if !filter.nombre.trim().is_empty() {
+                params.push(Box::new(utils::contains_pattern(filter.nombre.trim())));
+                query.push_str(&format!(" AND via.via_nombre ILIKE ${}", params.len()));
+            }
+            if !filter.destino.trim().is_empty() {
+                params.push(Box::new(utils::contains_pattern(filter.destino.trim())));
+                query.push_str(&format!(" AND via.via_destino ILIKE ${}", params.len()));
+            }
+            if let Some(uv_id) = filter.unidad_vecinal_id {
+                params.push(Box::new(uv_id));
+                query.push_str(&format!(" AND via.via_uvid = ${}", params.len()));
+            }
+            if let Some(desde) = filter.fecha_salida_desde {
+                params.push(Box::new(desde));
+                query.push_str(&format!(" AND via.via_fecha_salida >= ${}", params.len()));
+            }
+            if let Some(hasta) = filter.fecha_salida_hasta {
+                params.push(Box::new(hasta));
+                query.push_str(&format!(" AND via.via_fecha_salida <= ${}", params.len()));
+            }
+            let param_refs: Vec<&(dyn ToSql + Sync)> = params.iter().map(|p| p.as_ref() as &(dyn ToSql + Sync)).collect();
+            let (rows, total) = query_page(client, &query, "via.via_fecha_salida DESC, via.via_id DESC", &param_refs, page).await?;
+            let viajes = rows.iter().map(|row| Ok(Viaje {
+                via_id: row.try_get("via_id")?,
+                via_nombre: row.try_get("via_nombre")?,
+                via_destino: text_or_empty(row, "via_destino")?,
+                via_fecha_salida: row.try_get("via_fecha_salida")?,
+                via_fecha_regreso: row.try_get("via_fecha_regreso")?,
+                via_uvid: row.try_get("via_uvid")?,
+                uv_nombre: row.try_get("uv_nombre")?,
+            })).collect::<Result<_, tokio_postgres::Error>>()?;
+            Ok(PagedResult { rows: viajes, total })
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
 
-            Ok(actividades)
+    pub async fn get_centros(&self, filter: &CentroFilter, page: &PageRequest) -> Result<PagedResult<CentroComunitario>> {
+        if let Some(client) = &self.client {
+            let mut query = String::from(
+                "SELECT cen.cen_id, cen.cen_nombre, cen.cen_direccion, cen.cen_uvid, uv.uv_nombre 
+                 FROM cen_cencomunitarios cen 
+                 LEFT JOIN uv_unidadesvecinales uv ON cen.cen_uvid = uv.uv_id 
+                 WHERE 1=1"
+            );
+            let mut params: Vec<Box<dyn ToSql + Sync + Send>> = Vec::new();
+
+            if !filter.nombre.trim().is_empty() {
+                params.push(Box::new(utils::contains_pattern(filter.nombre.trim())));
+                query.push_str(&format!(" AND cen.cen_nombre ILIKE ${}", params.len()));
+            }
+            if let Some(uv_id) = filter.unidad_vecinal_id {
+                params.push(Box::new(uv_id));
+                query.push_str(&format!(" AND cen.cen_uvid = ${}", params.len()));
+            }
+            let param_refs: Vec<&(dyn ToSql + Sync)> = params.iter().map(|p| p.as_ref() as &(dyn ToSql + Sync)).collect();
+            let (rows, total) = query_page(client, &query, "cen.cen_nombre, cen.cen_id", &param_refs, page).await?;
+            let centros = rows.iter().map(|row| Ok(CentroComunitario {
+                cen_id: row.try_get("cen_id")?,
+                cen_nombre: row.try_get("cen_nombre")?,
+                cen_direccion: text_or_empty(row, "cen_direccion")?,
+                cen_uvid: row.try_get("cen_uvid")?,
+                uv_nombre: row.try_get("uv_nombre")?,
+            })).collect::<Result<_, tokio_postgres::Error>>()?;
+            Ok(PagedResult { rows: centros, total })
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn uv_exists(&self, uv_id: i32) -> Result<bool> {
+        if let Some(client) = &self.client {
+            let params: [&(dyn ToSql + Sync); 1] = [&uv_id];
+            let row = with_retry(
+                READ_RETRY_ATTEMPTS,
+                "SELECT EXISTS (SELECT 1 FROM uv_unidadesvecinales WHERE uv_id = $1) AS existe",
+                |sql| client.query_one(sql, &params),
+            ).await?;
+            Ok(row.get("existe"))
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
         }
     }
 
+    // Verificación previa a las inserciones: la UV elegida pudo eliminarse en otra sesión
+    // después de cargar los catálogos
+    async fn ensure_uv_exists(&self, uv_id: i32) -> Result<()> {
+        if self.uv_exists(uv_id).await? {
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("La unidad vecinal seleccionada ya no existe, recargue los catálogos"))
+        }
+    }
+
     pub async fn insert_persona(&self, persona: &PersonaMayor) -> Result<i32> {
         if let Some(client) = &self.client {
-            // Log para debug completo
-            println!("DEBUG: Insertando persona:");
-            println!("  RUT: '{}'", persona.per_rut);
-            println!("  Nombres: '{}' '{:?}'", persona.per_prinombre, persona.per_segnombre);
-            println!("  Apellidos: '{}' '{:?}'", persona.per_priapellido, persona.per_segapellido);
-            if let Some(ref email) = persona.per_email {
-                println!("  Email: '{}' (length: {})", email, email.len());
-                println!("  Email bytes: {:?}", email.as_bytes());
-            } else {
-                println!("  Email: NULL");
+            log::debug!("Insertando persona con RUT {}", persona.per_rut);
+            // Se valida aquí con la misma regla que el constraint de la base
+            if persona.per_email.as_deref().is_some_and(|email| !utils::validate_email(email)) {
+                return Err(anyhow::anyhow!("El email no tiene un formato válido"));
             }
-            
+            self.ensure_uv_exists(persona.per_uvid).await?;
+            let rut = utils::normalize_rut(&persona.per_rut);
             let row = client
-                .query_one(
-                    "INSERT INTO per_personasmayores (per_rut, per_prinombre, per_segnombre, per_priapellido, per_segapellido, per_genid, per_nacid, per_fechadenac, per_direccion, per_email, per_uvid) 
-                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) RETURNING per_id",
+                .query_one_traced(
+                    "INSERT INTO per_personasmayores (per_rut, per_prinombre, per_segnombre, per_priapellido, per_segapellido, per_genid, per_nacid, per_fechadenac, per_direccion, per_email, per_uvid, per_fecha_registro) 
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, now()) RETURNING per_id",
                     &[
-                        &persona.per_rut,
+                        &rut,
                         &persona.per_prinombre,
                         &persona.per_segnombre,
                         &persona.per_priapellido,
@@ -250,7 +1151,8 @@ impl DatabaseManager {
                         &persona.per_uvid,
                     ],
                 )
-                .await?;
+                .await
+                .map_err(friendly_db_error)?;
             Ok(row.get("per_id"))
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
@@ -259,10 +1161,11 @@ impl DatabaseManager {
 
     pub async fn insert_organizacion(&self, organizacion: &OrganizacionComunitaria) -> Result<i32> {
         if let Some(client) = &self.client {
+            self.ensure_uv_exists(organizacion.org_uvid).await?;
             let row = client
-                .query_one(
-                    "INSERT INTO org_orgcomunitarias (org_nombre, org_direccion, org_uvid, org_fechaconst, org_perjuridica, org_email) 
-                     VALUES ($1, $2, $3, $4, $5, $6) RETURNING org_id",
+                .query_one_traced(
+                    "INSERT INTO org_orgcomunitarias (org_nombre, org_direccion, org_uvid, org_fechaconst, org_perjuridica, org_email, org_fecha_registro) 
+                     VALUES ($1, $2, $3, $4, $5, $6, now()) RETURNING org_id",
                     &[
                         &organizacion.org_nombre,
                         &organizacion.org_direccion,
@@ -272,7 +1175,8 @@ impl DatabaseManager {
                         &organizacion.org_email,
                     ],
                 )
-                .await?;
+                .await
+                .map_err(friendly_db_error)?;
             Ok(row.get("org_id"))
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
@@ -281,48 +1185,570 @@ impl DatabaseManager {
 
     pub async fn insert_actividad(&self, actividad: &Actividad) -> Result<i32> {
         if let Some(client) = &self.client {
+            self.ensure_uv_exists(actividad.act_uvid).await?;
             let row = client
-                .query_one(
-                    "INSERT INTO act_actividades (act_nombre, act_uvid, act_fecha_ini, act_fecha_fin, act_descripcion) 
-                     VALUES ($1, $2, $3, $4, $5) RETURNING act_id",
+                .query_one_traced(
+                    "INSERT INTO act_actividades (act_nombre, act_uvid, act_fecha_ini, act_fecha_fin, act_descripcion, act_talid, act_fecha_registro) 
+                     VALUES ($1, $2, $3, $4, $5, $6, now()) RETURNING act_id",
                     &[
                         &actividad.act_nombre,
                         &actividad.act_uvid,
                         &actividad.act_fecha_ini,
                         &actividad.act_fecha_fin,
                         &actividad.act_descripcion,
+                        &actividad.act_talid,
                     ],
                 )
-                .await?;
+                .await
+                .map_err(friendly_db_error)?;
             Ok(row.get("act_id"))
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
         }
     }
 
-    // Métodos adicionales de inserción
-    pub async fn insert_macro_sector(&self, nombre: &str) -> Result<i32> {
+    pub async fn insert_centro(&self, centro: &CentroComunitario) -> Result<i32> {
         if let Some(client) = &self.client {
+            self.ensure_uv_exists(centro.cen_uvid).await?;
             let row = client
-                .query_one(
-                    "INSERT INTO mac_macrosectores (mac_nombre) VALUES ($1) RETURNING mac_id",
-                    &[&nombre],
+                .query_one_traced(
+                    "INSERT INTO cen_cencomunitarios (cen_nombre, cen_direccion, cen_uvid) 
+                     VALUES ($1, $2, $3) RETURNING cen_id",
+                    &[
+                        &centro.cen_nombre,
+                        &centro.cen_direccion,
+                        &centro.cen_uvid,
+                    ],
                 )
-                .await?;
-            Ok(row.get("mac_id"))
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(row.get("cen_id"))
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
         }
     }
 
-    pub async fn insert_unidad_vecinal(&self, nombre: &str, macro_sector_id: i32) -> Result<i32> {
+    // Métodos de actualización. RUT, género, nacionalidad, unidad vecinal y taller no se
+    // editan desde el detalle; el cambio de UV se hace con la reasignación masiva
+    pub async fn update_persona(&self, persona: &PersonaMayor) -> Result<u64> {
         if let Some(client) = &self.client {
-            let row = client
-                .query_one(
-                    "INSERT INTO uv_unidadesvecinales (uv_nombre, uv_macid) VALUES ($1, $2) RETURNING uv_id",
-                    &[&nombre, &macro_sector_id],
+            let affected = client
+                .execute_traced(
+                    "UPDATE per_personasmayores 
+                     SET per_prinombre = $1, per_segnombre = $2, per_priapellido = $3, per_segapellido = $4, 
+                         per_fechadenac = $5, per_direccion = $6, per_email = $7, per_fecha_modificacion = now() 
+                     WHERE per_id = $8",
+                    &[
+                        &persona.per_prinombre,
+                        &persona.per_segnombre,
+                        &persona.per_priapellido,
+                        &persona.per_segapellido,
+                        &persona.per_fechadenac,
+                        &persona.per_direccion,
+                        &persona.per_email,
+                        &persona.per_id,
+                    ],
                 )
-                .await?;
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn update_organizacion(&self, organizacion: &OrganizacionComunitaria) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced(
+                    "UPDATE org_orgcomunitarias 
+                     SET org_nombre = $1, org_direccion = $2, org_fechaconst = $3, org_perjuridica = $4, 
+                         org_email = $5, org_fecha_modificacion = now() 
+                     WHERE org_id = $6",
+                    &[
+                        &organizacion.org_nombre,
+                        &organizacion.org_direccion,
+                        &organizacion.org_fechaconst,
+                        &organizacion.org_perjuridica,
+                        &organizacion.org_email,
+                        &organizacion.org_id,
+                    ],
+                )
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn update_actividad(&self, actividad: &Actividad) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced(
+                    "UPDATE act_actividades 
+                     SET act_nombre = $1, act_fecha_ini = $2, act_fecha_fin = $3, act_descripcion = $4, 
+                         act_fecha_modificacion = now() 
+                     WHERE act_id = $5",
+                    &[
+                        &actividad.act_nombre,
+                        &actividad.act_fecha_ini,
+                        &actividad.act_fecha_fin,
+                        &actividad.act_descripcion,
+                        &actividad.act_id,
+                    ],
+                )
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Cambia la UV de varias personas en una sola sentencia; devuelve las filas modificadas
+    pub async fn reassign_personas_uv(&self, ids: &[i32], new_uv: i32) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced(
+                    "UPDATE per_personasmayores SET per_uvid = $1, per_fecha_modificacion = now() WHERE per_id = ANY($2)",
+                    &[&new_uv, &ids],
+                )
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Métodos de eliminación
+    pub async fn delete_persona(&self, per_id: i32) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced("DELETE FROM per_personasmayores WHERE per_id = $1", &[&per_id])
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn delete_organizacion(&self, org_id: i32) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced("DELETE FROM org_orgcomunitarias WHERE org_id = $1", &[&org_id])
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn delete_actividad(&self, act_id: i32) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced("DELETE FROM act_actividades WHERE act_id = $1", &[&act_id])
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn delete_macro_sector(&self, mac_id: i32) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced("DELETE FROM mac_macrosectores WHERE mac_id = $1", &[&mac_id])
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn delete_unidad_vecinal(&self, uv_id: i32) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced("DELETE FROM uv_unidadesvecinales WHERE uv_id = $1", &[&uv_id])
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn delete_taller(&self, tal_id: i32) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced("DELETE FROM tal_talleres WHERE tal_id = $1", &[&tal_id])
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn delete_beneficio(&self, ben_id: i32) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced("DELETE FROM ben_beneficios WHERE ben_id = $1", &[&ben_id])
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn delete_centro(&self, cen_id: i32) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced("DELETE FROM cen_cencomunitarios WHERE cen_id = $1", &[&cen_id])
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Tabla y columnas de teléfonos según la entidad: (tabla, id, fk, tipo, numero)
+    fn telefono_columns(entity_type: TelefonoEntidad) -> (&'static str, &'static str, &'static str, &'static str, &'static str) {
+        match entity_type {
+            TelefonoEntidad::Persona => ("per_telefonos", "pt_id", "pt_perid", "pt_tipo", "pt_numero"),
+            TelefonoEntidad::Organizacion => ("org_telefonos", "ot_id", "ot_orgid", "ot_tipo", "ot_numero"),
+        }
+    }
+
+    pub async fn get_telefonos_for(&self, entity_type: TelefonoEntidad, entity_id: i32) -> Result<Vec<Telefono>> {
+        if let Some(client) = &self.client {
+            let (table, id_col, fk_col, tipo_col, numero_col) = Self::telefono_columns(entity_type);
+            let query = format!(
+                "SELECT {id} AS id, {fk} AS entity_id, {tipo} AS tipo, {numero} AS numero FROM {table} WHERE {fk} = $1 ORDER BY {tipo}",
+                id = id_col, fk = fk_col, tipo = tipo_col, numero = numero_col, table = table
+            );
+            let params: [&(dyn ToSql + Sync); 1] = [&entity_id];
+            let rows = with_retry(READ_RETRY_ATTEMPTS, query.as_str(), |sql| client.query(sql, &params)).await?;
+            let telefonos = rows.iter().map(|row| Ok(Telefono {
+                id: row.try_get("id")?,
+                entity_id: row.try_get("entity_id")?,
+                tipo: text_or_empty(row, "tipo")?,
+                numero: row.try_get("numero")?,
+            })).collect::<Result<_, tokio_postgres::Error>>()?;
+            Ok(telefonos)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn insert_telefono(&self, entity_type: TelefonoEntidad, telefono: &Telefono) -> Result<i32> {
+        if let Some(client) = &self.client {
+            let (table, id_col, fk_col, tipo_col, numero_col) = Self::telefono_columns(entity_type);
+
+            // Evitar duplicados y tipos repetidos para la misma entidad. Se compara la forma
+            // normalizada, de modo que "+56 9 1234 5678" y "+56912345678" sean el mismo número
+            let numero = utils::normalize_telefono(&telefono.numero);
+            let existing = client
+                .query_traced(
+                    format!("SELECT {tipo} AS tipo, {numero} AS numero FROM {table} WHERE {fk} = $1",
+                        tipo = tipo_col, numero = numero_col, table = table, fk = fk_col).as_str(),
+                    &[&telefono.entity_id],
+                )
+                .await
+                .map_err(friendly_db_error)?;
+            for row in &existing {
+                let existing_numero: String = row.try_get("numero")?;
+                let tipo: Option<String> = row.try_get("tipo")?;
+                if utils::normalize_telefono(&existing_numero) == numero {
+                    return Err(anyhow::anyhow!("El número {} ya está registrado", existing_numero));
+                }
+                if tipo.as_deref() == Some(telefono.tipo.as_str()) {
+                    return Err(anyhow::anyhow!("Ya existe un teléfono de tipo '{}' registrado", telefono.tipo));
+                }
+            }
+
+            let row = client
+                .query_one_traced(
+                    format!("INSERT INTO {table} ({fk}, {tipo}, {numero}) VALUES ($1, $2, $3) RETURNING {id} AS id",
+                        table = table, fk = fk_col, tipo = tipo_col, numero = numero_col, id = id_col).as_str(),
+                    &[&telefono.entity_id, &telefono.tipo, &numero],
+                )
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(row.get("id"))
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn delete_telefono(&self, entity_type: TelefonoEntidad, telefono_id: i32) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let (table, id_col, _, _, _) = Self::telefono_columns(entity_type);
+            let affected = client
+                .execute_traced(format!("DELETE FROM {} WHERE {} = $1", table, id_col).as_str(), &[&telefono_id])
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Personas que pertenecen a una organización (tabla per_org)
+    pub async fn get_miembros(&self, org_id: i32) -> Result<Vec<PersonaMayor>> {
+        if let Some(client) = &self.client {
+            let query = format!(
+                "{} JOIN per_org po ON po.po_perid = per.per_id WHERE po.po_orgid = $1 ORDER BY per.per_priapellido, per.per_prinombre",
+                PERSONA_SELECT
+            );
+            let params: [&(dyn ToSql + Sync); 1] = [&org_id];
+            let rows = with_retry(READ_RETRY_ATTEMPTS, query.as_str(), |sql| client.query(sql, &params)).await?;
+            Ok(rows.iter().map(persona_from_row).collect::<Result<_, _>>()?)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Busca una persona por RUT exacto (se normaliza antes de comparar; sin guion se toma
+    // el último carácter como dígito verificador)
+    pub async fn get_persona_by_rut(&self, rut: &str) -> Result<Option<PersonaMayor>> {
+        if let Some(client) = &self.client {
+            let mut rut = utils::normalize_rut(rut);
+            if !rut.contains('-') {
+                if let Some((dv_index, _)) = rut.char_indices().last().filter(|(index, _)| *index > 0) {
+                    rut.insert(dv_index, '-');
+                }
+            }
+            let query = format!("{} WHERE per.per_rut = $1", PERSONA_SELECT);
+            let params: [&(dyn ToSql + Sync); 1] = [&rut];
+            let row = with_retry(READ_RETRY_ATTEMPTS, query.as_str(), |sql| client.query_opt(sql, &params)).await?;
+            Ok(row.as_ref().map(persona_from_row).transpose()?)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Últimos registros creados de personas, organizaciones y actividades, del más nuevo
+    // al más antiguo. Se ordena por fecha de registro y, a igual fecha, por id
+    pub async fn recent_records(&self, limit: i64) -> Result<Vec<RecentRecord>> {
+        if let Some(client) = &self.client {
+            let query = "
+                (SELECT 'persona' AS tipo, per_id AS id, 
+                        per_prinombre || ' ' || per_priapellido || ' (' || per_rut || ')' AS nombre, 
+                        per_fecha_registro AS creado 
+                 FROM per_personasmayores ORDER BY per_fecha_registro DESC NULLS LAST, per_id DESC LIMIT $1) 
+                UNION ALL 
+                (SELECT 'organizacion', org_id, org_nombre, org_fecha_registro 
+                 FROM org_orgcomunitarias ORDER BY org_fecha_registro DESC NULLS LAST, org_id DESC LIMIT $1) 
+                UNION ALL 
+                (SELECT 'actividad', act_id, act_nombre, act_fecha_registro 
+                 FROM act_actividades ORDER BY act_fecha_registro DESC NULLS LAST, act_id DESC LIMIT $1) 
+                ORDER BY creado DESC NULLS LAST, id DESC 
+                LIMIT $1";
+            let params: [&(dyn ToSql + Sync); 1] = [&limit];
+            let rows = with_retry(READ_RETRY_ATTEMPTS, query, |sql| client.query(sql, &params)).await?;
+            let records = rows.iter()
+                .filter_map(|row| {
+                    Some(RecentRecord {
+                        kind: RecentRecordKind::from_tag(row.get("tipo"))?,
+                        id: row.get("id"),
+                        nombre: row.get("nombre"),
+                        creado: row.get("creado"),
+                    })
+                })
+                .collect();
+            Ok(records)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Reúne lo que se imprime en la ficha de una persona: macrosector, teléfonos,
+    // organizaciones a las que pertenece y actividades a las que asistió
+    pub async fn get_persona_ficha(&self, persona: PersonaMayor) -> Result<PersonaFicha> {
+        if let Some(client) = &self.client {
+            let per_id = persona.per_id;
+            let macro_sector = client
+                .query_opt_traced(
+                    "SELECT mac.mac_nombre FROM uv_unidadesvecinales uv 
+                     JOIN mac_macrosectores mac ON uv.uv_macid = mac.mac_id 
+                     WHERE uv.uv_id = $1",
+                    &[&persona.per_uvid],
+                )
+                .await?
+                .map(|row| row.get::<_, String>("mac_nombre"));
+            let telefonos = self.get_telefonos_for(TelefonoEntidad::Persona, per_id).await?;
+            let organizaciones = client
+                .query_traced(
+                    "SELECT org.org_nombre FROM per_org po 
+                     JOIN org_orgcomunitarias org ON org.org_id = po.po_orgid 
+                     WHERE po.po_perid = $1 ORDER BY org.org_nombre",
+                    &[&per_id],
+                )
+                .await?
+                .iter()
+                .map(|row| row.get("org_nombre"))
+                .collect();
+            let actividades = client
+                .query_traced(
+                    "SELECT act.act_nombre, act.act_fecha_ini FROM asis_actividades asis 
+                     JOIN act_actividades act ON act.act_id = asis.asis_actid 
+                     WHERE asis.asis_perid = $1 ORDER BY act.act_fecha_ini DESC, act.act_nombre",
+                    &[&per_id],
+                )
+                .await?
+                .iter()
+                .map(|row| (row.get("act_nombre"), row.get("act_fecha_ini")))
+                .collect();
+            Ok(PersonaFicha { persona, macro_sector, telefonos, organizaciones, actividades })
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Agrega una persona a la organización; si ya es miembro se informa sin error de la base
+    pub async fn add_miembro(&self, org_id: i32, per_id: i32) -> Result<()> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced(
+                    "INSERT INTO per_org (po_perid, po_orgid) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+                    &[&per_id, &org_id],
+                )
+                .await
+                .map_err(friendly_db_error)?;
+            if affected == 0 {
+                return Err(anyhow::anyhow!("La persona ya es miembro de la organización"));
+            }
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn remove_miembro(&self, org_id: i32, per_id: i32) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced("DELETE FROM per_org WHERE po_perid = $1 AND po_orgid = $2", &[&per_id, &org_id])
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Personas que asistieron a una actividad (tabla asis_actividades)
+    pub async fn get_participantes(&self, act_id: i32) -> Result<Vec<PersonaMayor>> {
+        if let Some(client) = &self.client {
+            let query = format!(
+                "{} JOIN asis_actividades asis ON asis.asis_perid = per.per_id WHERE asis.asis_actid = $1 ORDER BY per.per_priapellido, per.per_prinombre",
+                PERSONA_SELECT
+            );
+            let params: [&(dyn ToSql + Sync); 1] = [&act_id];
+            let rows = with_retry(READ_RETRY_ATTEMPTS, query.as_str(), |sql| client.query(sql, &params)).await?;
+            Ok(rows.iter().map(persona_from_row).collect::<Result<_, _>>()?)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Registra la asistencia; uq_asisact impide repetir la misma persona en una actividad
+    pub async fn add_participante(&self, act_id: i32, per_id: i32) -> Result<()> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced(
+                    "INSERT INTO asis_actividades (asis_perid, asis_actid) VALUES ($1, $2) ON CONFLICT DO NOTHING",
+                    &[&per_id, &act_id],
+                )
+                .await
+                .map_err(friendly_db_error)?;
+            if affected == 0 {
+                return Err(anyhow::anyhow!("La persona ya participa en esta actividad"));
+            }
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn remove_participante(&self, act_id: i32, per_id: i32) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let affected = client
+                .execute_traced("DELETE FROM asis_actividades WHERE asis_perid = $1 AND asis_actid = $2", &[&per_id, &act_id])
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Comprobaciones previas a insertar: comparan sin distinguir mayúsculas ni espacios
+    // extremos, para avisar de casi-duplicados que los constraints UNIQUE no detectan
+    pub async fn macro_sector_name_match(&self, nombre: &str) -> Result<NameMatch> {
+        self.name_match("mac_macrosectores", "mac_nombre", nombre).await
+    }
+
+    // Las organizaciones se comparan en todas las unidades vecinales: los listados y
+    // selectores las muestran solo por nombre
+    pub async fn org_name_match(&self, nombre: &str) -> Result<NameMatch> {
+        self.name_match("org_orgcomunitarias", "org_nombre", nombre).await
+    }
+
+    // Busca `nombre` en `table.column`, distinguiendo un nombre idéntico de uno que solo
+    // difiere en mayúsculas o espacios
+    async fn name_match(&self, table: &str, column: &str, nombre: &str) -> Result<NameMatch> {
+        if let Some(client) = &self.client {
+            let params: [&(dyn ToSql + Sync); 1] = [&nombre];
+            let query = format!(
+                "SELECT COALESCE(bool_or({column} = $1), false) AS exacto, COUNT(*) > 0 AS parecido 
+                 FROM {table} WHERE lower(trim({column})) = lower(trim($1))"
+            );
+            let row = with_retry(READ_RETRY_ATTEMPTS, query.as_str(), |sql| client.query_one(sql, &params)).await?;
+            Ok(if row.get("exacto") {
+                NameMatch::Exact
+            } else if row.get("parecido") {
+                NameMatch::Variant
+            } else {
+                NameMatch::None
+            })
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Métodos adicionales de inserción
+    pub async fn insert_macro_sector(&self, nombre: &str) -> Result<i32> {
+        if let Some(client) = &self.client {
+            let row = client
+                .query_one_traced(
+                    "INSERT INTO mac_macrosectores (mac_nombre) VALUES ($1) RETURNING mac_id",
+                    &[&nombre],
+                )
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(row.get("mac_id"))
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn insert_unidad_vecinal(&self, nombre: &str, macro_sector_id: i32) -> Result<i32> {
+        if let Some(client) = &self.client {
+            let row = client
+                .query_one_traced(
+                    "INSERT INTO uv_unidadesvecinales (uv_nombre, uv_macid) VALUES ($1, $2) RETURNING uv_id",
+                    &[&nombre, &macro_sector_id],
+                )
+                .await
+                .map_err(friendly_db_error)?;
             Ok(row.get("uv_id"))
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
@@ -332,53 +1758,128 @@ impl DatabaseManager {
     pub async fn insert_taller(&self, nombre: &str) -> Result<i32> {
         if let Some(client) = &self.client {
             let row = client
-                .query_one(
+                .query_one_traced(
                     "INSERT INTO tal_talleres (tal_nombre) VALUES ($1) RETURNING tal_id",
                     &[&nombre],
                 )
-                .await?;
+                .await
+                .map_err(friendly_db_error)?;
             Ok(row.get("tal_id"))
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
         }
     }
 
-    #[allow(dead_code)]
+    pub async fn insert_beneficio(&self, codigo: &str, descripcion: &str) -> Result<i32> {
+        if let Some(client) = &self.client {
+            // Verificar que el código no exista antes de insertar
+            let existing = client
+                .query_one_traced(
+                    "SELECT EXISTS(SELECT 1 FROM ben_beneficios WHERE ben_codigo = $1) AS existe",
+                    &[&codigo],
+                )
+                .await
+                .map_err(friendly_db_error)?;
+            if existing.get::<_, bool>("existe") {
+                return Err(anyhow::anyhow!("Ya existe un beneficio con el código '{}'", codigo));
+            }
+
+            let row = client
+                .query_one_traced(
+                    "INSERT INTO ben_beneficios (ben_codigo, ben_descripcion) VALUES ($1, $2) RETURNING ben_id",
+                    &[&codigo, &descripcion],
+                )
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(row.get("ben_id"))
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
     pub async fn insert_genero(&self, nombre: &str) -> Result<i32> {
         if let Some(client) = &self.client {
             let row = client
-                .query_one(
+                .query_one_traced(
                     "INSERT INTO gen_generos (gen_genero) VALUES ($1) RETURNING gen_id",
                     &[&nombre],
                 )
-                .await?;
+                .await
+                .map_err(friendly_db_error)?;
             Ok(row.get("gen_id"))
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
         }
     }
 
-    #[allow(dead_code)]
     pub async fn insert_nacionalidad(&self, nombre: &str) -> Result<i32> {
         if let Some(client) = &self.client {
             let row = client
-                .query_one(
+                .query_one_traced(
                     "INSERT INTO nac_nacionalidades (nac_nacionalidad) VALUES ($1) RETURNING nac_id",
                     &[&nombre],
                 )
-                .await?;
+                .await
+                .map_err(friendly_db_error)?;
             Ok(row.get("nac_id"))
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
         }
     }
 
+    // Renombra un registro de catálogo. Las tablas y columnas son constantes del llamador;
+    // se rechaza el nombre si otro registro del mismo catálogo ya lo usa.
+    async fn rename_catalog_entry(&self, table: &str, id_column: &str, name_column: &str, id: i32, nombre: &str) -> Result<u64> {
+        if let Some(client) = &self.client {
+            let existing = client
+                .query_one_traced(
+                    &format!(
+                        "SELECT EXISTS(SELECT 1 FROM {} WHERE lower({}) = lower($1) AND {} <> $2) AS existe",
+                        table, name_column, id_column
+                    ),
+                    &[&nombre, &id],
+                )
+                .await
+                .map_err(friendly_db_error)?;
+            if existing.get::<_, bool>("existe") {
+                return Err(anyhow::anyhow!("Ya existe un registro con el nombre '{}'", nombre));
+            }
+
+            let affected = client
+                .execute_traced(
+                    &format!("UPDATE {} SET {} = $1 WHERE {} = $2", table, name_column, id_column),
+                    &[&nombre, &id],
+                )
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(affected)
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    pub async fn update_genero(&self, gen_id: i32, nombre: &str) -> Result<u64> {
+        self.rename_catalog_entry("gen_generos", "gen_id", "gen_genero", gen_id, nombre).await
+    }
+
+    pub async fn update_nacionalidad(&self, nac_id: i32, nombre: &str) -> Result<u64> {
+        self.rename_catalog_entry("nac_nacionalidades", "nac_id", "nac_nacionalidad", nac_id, nombre).await
+    }
+
+    pub async fn update_macro_sector(&self, mac_id: i32, nombre: &str) -> Result<u64> {
+        self.rename_catalog_entry("mac_macrosectores", "mac_id", "mac_nombre", mac_id, nombre).await
+    }
+
+    pub async fn update_unidad_vecinal(&self, uv_id: i32, nombre: &str) -> Result<u64> {
+        self.rename_catalog_entry("uv_unidadesvecinales", "uv_id", "uv_nombre", uv_id, nombre).await
+    }
+
     // Función helper para probar constraints de email
     #[allow(dead_code)]
     pub async fn test_email_constraint(&self, email: &str) -> Result<bool> {
         if let Some(client) = &self.client {
             let result = client
-                .query_one(
+                .query_one_traced(
                     "SELECT $1 ~* '^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\\.[A-Za-z]{2,}$' AS is_valid",
                     &[&email],
                 )
@@ -389,29 +1890,210 @@ impl DatabaseManager {
         }
     }
 
-    // Función para aplicar fix temporal del constraint
-    pub async fn fix_email_constraint_temp(&self) -> Result<()> {
+    // Ejecuta `f` dentro de una transacción: se confirma si devuelve Ok y se revierte
+    // completa si devuelve Err, para no dejar cambios aplicados a medias
+    pub async fn with_transaction<T, F>(&mut self, f: F) -> Result<T>
+    where
+        F: for<'t> FnOnce(&'t Transaction<'t>) -> TransactionFuture<'t, T>,
+    {
+        if let Some(client) = &mut self.client {
+            let started = Instant::now();
+            let transaction = client.transaction().await?;
+            match f(&transaction).await {
+                Ok(value) => {
+                    transaction.commit().await?;
+                    log::trace!("transaction committed in {:?}", started.elapsed());
+                    Ok(value)
+                }
+                Err(e) => {
+                    transaction.rollback().await?;
+                    log::trace!("transaction rolled back after {:?}: {}", started.elapsed(), e);
+                    Err(e)
+                }
+            }
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Habilita la extensión unaccent; falla si el usuario no tiene privilegios para crearla
+    pub async fn ensure_unaccent(&self) -> Result<()> {
+        if let Some(client) = &self.client {
+            client.batch_execute("CREATE EXTENSION IF NOT EXISTS unaccent").await?;
+            Ok(())
+        } else {
+            Err(anyhow::anyhow!("No hay conexión a la base de datos"))
+        }
+    }
+
+    // Aplica las migraciones pendientes, cada una en su propia transacción; devuelve
+    // cuántas se aplicaron. El bloqueo de schema_migrations evita que dos sesiones
+    // apliquen la misma migración.
+    pub async fn run_migrations(&mut self) -> Result<usize> {
         if let Some(client) = &self.client {
-            // Eliminar constraint existente
-            let _ = client
-                .execute(
-                    "ALTER TABLE per_personasmayores DROP CONSTRAINT IF EXISTS chk_per_email_formato",
-                    &[],
-                )
-                .await;
-            
-            // Agregar constraint temporal más permisivo
             client
-                .execute(
-                    "ALTER TABLE per_personasmayores ADD CONSTRAINT chk_per_email_formato_temp CHECK (per_email IS NULL OR (per_email LIKE '%@%.%' AND length(per_email) > 5))",
-                    &[],
+                .batch_execute(
+                    "CREATE TABLE IF NOT EXISTS schema_migrations (
+                        version INT PRIMARY KEY,
+                        nombre VARCHAR(255) NOT NULL,
+                        aplicada TIMESTAMP NOT NULL DEFAULT now()
+                    )",
                 )
                 .await?;
-            
-            println!("DEBUG: Constraint de email actualizado temporalmente");
-            Ok(())
+        } else {
+            return Err(anyhow::anyhow!("No hay conexión a la base de datos"));
+        }
+
+        // Bases creadas con el script original antes de existir las migraciones:
+        // el esquema inicial se registra como aplicado sin ejecutarlo
+        let baseline = self.verify_schema().await?.is_empty();
+
+        let mut applied = 0;
+        for migration in &MIGRATIONS {
+            let done = self.with_transaction(|transaction| Box::pin(async move {
+                transaction.batch_execute("LOCK TABLE schema_migrations IN EXCLUSIVE MODE").await?;
+                let exists = transaction
+                    .query_opt("SELECT 1 FROM schema_migrations WHERE version = $1", &[&migration.version])
+                    .await?
+                    .is_some();
+                if exists {
+                    return Ok(false);
+                }
+                let first_run = transaction
+                    .query_one("SELECT COUNT(*) AS count FROM schema_migrations", &[])
+                    .await?
+                    .get::<_, i64>("count") == 0;
+                if !(migration.version == 1 && baseline && first_run) {
+                    transaction
+                        .batch_execute(migration.sql)
+                        .await
+                        .with_context(|| format!("Error en la migración {} ({})", migration.version, migration.nombre))?;
+                }
+                transaction
+                    .execute(
+                        "INSERT INTO schema_migrations (version, nombre) VALUES ($1, $2)",
+                        &[&migration.version, &migration.nombre],
+                    )
+                    .await?;
+                Ok(true)
+            })).await?;
+            if done {
+                applied += 1;
+            }
+        }
+        Ok(applied)
+    }
+
+    // Última migración registrada; None si la base aún no usa migraciones
+    pub async fn schema_version(&self) -> Result<Option<i32>> {
+        if let Some(client) = &self.client {
+            let row = client.query_one_traced("SELECT to_regclass('schema_migrations') IS NOT NULL AS existe", &[]).await?;
+            if !row.get::<_, bool>("existe") {
+                return Ok(None);
+            }
+            let row = with_retry(READ_RETRY_ATTEMPTS, "SELECT MAX(version) AS version FROM schema_migrations", |sql| client.query_one(sql, &[])).await?;
+            Ok(row.get("version"))
         } else {
             Err(anyhow::anyhow!("No hay conexión a la base de datos"))
         }
     }
+
+    // Acción de administración: reemplaza los constraints de email de personas y
+    // organizaciones por la misma regla de `utils::validate_email`. Las bases creadas con el
+    // esquema original tenían la expresión con el punto mal escapado (exigía una barra
+    // invertida), y versiones anteriores cambiaban el de personas al conectar por un constraint
+    // temporal más permisivo (chk_per_email_formato_temp), que también se elimina.
+    // Todo va en una transacción: si hay emails existentes que no cumplen la regla, el ADD
+    // falla y las tablas quedan con sus constraints anteriores.
+    pub async fn repair_email_constraints(&mut self) -> Result<()> {
+        self.with_transaction(|transaction| Box::pin(async move {
+            transaction
+                .batch_execute(
+                    "ALTER TABLE per_personasmayores DROP CONSTRAINT IF EXISTS chk_per_email_formato;
+                     ALTER TABLE per_personasmayores DROP CONSTRAINT IF EXISTS chk_per_email_formato_temp;
+                     ALTER TABLE per_personasmayores ADD CONSTRAINT chk_per_email_formato
+                         CHECK (per_email IS NULL OR per_email ~* '^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\\.[A-Za-z]{2,}$');
+                     ALTER TABLE org_orgcomunitarias DROP CONSTRAINT IF EXISTS chk_org_email_formato;
+                     ALTER TABLE org_orgcomunitarias ADD CONSTRAINT chk_org_email_formato
+                         CHECK (org_email IS NULL OR org_email ~* '^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\\.[A-Za-z]{2,}$')",
+                )
+                .await
+                .map_err(friendly_db_error)?;
+            Ok(())
+        })).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    #[derive(Debug)]
+    struct FakeError {
+        transient: bool,
+    }
+
+    impl std::fmt::Display for FakeError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "falla simulada (transitoria: {})", self.transient)
+        }
+    }
+
+    impl TransientError for FakeError {
+        fn is_transient(&self) -> bool {
+            self.transient
+        }
+    }
+
+    #[test]
+    fn empty_if_null_muestra_null_como_vacio() {
+        assert_eq!(empty_if_null(None), "");
+        assert_eq!(empty_if_null(Some(String::new())), "");
+        assert_eq!(empty_if_null(Some("Av. Central 123".to_string())), "Av. Central 123");
+    }
+
+    #[test]
+    fn pg_config_no_requiere_escapar_credenciales() {
+        let config = DatabaseConfig {
+            host: "db.example.cl".to_string(),
+            port: 5433,
+            username: "adulto mayor".to_string(),
+            password: "a b'c".to_string(),
+            database: "base\\prueba".to_string(),
+        };
+        let pg_config = DatabaseManager::pg_config(&config);
+        assert_eq!(pg_config.get_password(), Some(b"a b'c".as_ref()));
+        assert_eq!(pg_config.get_user(), Some("adulto mayor"));
+        assert_eq!(pg_config.get_dbname(), Some("base\\prueba"));
+        assert_eq!(pg_config.get_ports(), &[5433]);
+        assert!(matches!(
+            pg_config.get_hosts(),
+            [tokio_postgres::config::Host::Tcp(host)] if host == "db.example.cl"
+        ));
+    }
+
+    #[tokio::test]
+    async fn with_retry_reintenta_errores_transitorios() {
+        let calls = AtomicU32::new(0);
+        let result = with_retry(READ_RETRY_ATTEMPTS, "SELECT 1", |_| {
+            let call = calls.fetch_add(1, Ordering::SeqCst) + 1;
+            async move {
+                if call < 3 { Err(FakeError { transient: true }) } else { Ok(call) }
+            }
+        }).await;
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn with_retry_no_reintenta_errores_permanentes() {
+        let calls = AtomicU32::new(0);
+        let result: Result<(), FakeError> = with_retry(READ_RETRY_ATTEMPTS, "SELECT 1", |_| {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async { Err(FakeError { transient: false }) }
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
 }
@@ -0,0 +1,324 @@
+// Exportación de resultados de consultas a CSV y Excel (.xlsx)
+use anyhow::Result;
+use chrono::{Datelike, NaiveDate};
+use rust_xlsxwriter::{ExcelDateTime, Format, Workbook};
+use std::path::Path;
+use crate::models::{Actividad, OrganizacionComunitaria, PersonaMayor};
+use crate::utils;
+
+// Valor de una celda exportada
+#[derive(Debug, Clone)]
+pub enum ExportCell {
+    Text(String),
+    Number(i64),
+    Date(NaiveDate),
+    Empty,
+}
+
+impl ExportCell {
+    fn text(value: &str) -> Self {
+        ExportCell::Text(value.to_string())
+    }
+
+    fn optional_text(value: &Option<String>) -> Self {
+        value.as_deref().map(Self::text).unwrap_or(ExportCell::Empty)
+    }
+
+    fn optional_date(value: &Option<NaiveDate>) -> Self {
+        value.map(ExportCell::Date).unwrap_or(ExportCell::Empty)
+    }
+
+    fn to_plain_text(&self) -> String {
+        match self {
+            ExportCell::Text(text) => text.clone(),
+            ExportCell::Number(number) => number.to_string(),
+            ExportCell::Date(date) => utils::format_date(date),
+            ExportCell::Empty => String::new(),
+        }
+    }
+
+    fn to_csv_field(&self) -> String {
+        let raw = self.to_plain_text();
+        if raw.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", raw.replace('"', "\"\""))
+        } else {
+            raw
+        }
+    }
+
+    // En TSV no hay comillas: tabulaciones y saltos de línea se reemplazan por espacios
+    fn to_tsv_field(&self) -> String {
+        self.to_plain_text().replace(['\t', '\n', '\r'], " ")
+    }
+}
+
+// Una fila como texto separado por tabulaciones, para pegar en planillas
+pub fn tsv_row<T: ExportableRows>(item: &T) -> String {
+    item.export_row().iter().map(ExportCell::to_tsv_field).collect::<Vec<_>>().join("\t")
+}
+
+// Entidad que puede exportarse como filas de una tabla.
+// Para agregar una entidad basta con definir sus encabezados y `export_row`.
+pub trait ExportableRows {
+    const HEADERS: &'static [&'static str];
+
+    fn export_row(&self) -> Vec<ExportCell>;
+}
+
+impl ExportableRows for PersonaMayor {
+    const HEADERS: &'static [&'static str] = &[
+        "RUT", "Primer nombre", "Segundo nombre", "Primer apellido", "Segundo apellido",
+        "Fecha de nacimiento", "Edad", "Género", "Nacionalidad", "Dirección", "Email", "Unidad Vecinal",
+    ];
+
+    fn export_row(&self) -> Vec<ExportCell> {
+        vec![
+            ExportCell::text(&self.per_rut),
+            ExportCell::text(&self.per_prinombre),
+            ExportCell::optional_text(&self.per_segnombre),
+            ExportCell::text(&self.per_priapellido),
+            ExportCell::optional_text(&self.per_segapellido),
+            ExportCell::Date(self.per_fechadenac),
+            ExportCell::Number(utils::calculate_age(&self.per_fechadenac) as i64),
+            ExportCell::optional_text(&self.gen_genero),
+            ExportCell::optional_text(&self.nac_nacionalidad),
+            ExportCell::text(&self.per_direccion),
+            ExportCell::optional_text(&self.per_email),
+            ExportCell::optional_text(&self.uv_nombre),
+        ]
+    }
+}
+
+impl ExportableRows for OrganizacionComunitaria {
+    const HEADERS: &'static [&'static str] = &[
+        "Nombre", "Dirección", "Fecha constitución", "Personalidad jurídica", "Email", "Unidad Vecinal",
+    ];
+
+    fn export_row(&self) -> Vec<ExportCell> {
+        vec![
+            ExportCell::text(&self.org_nombre),
+            ExportCell::text(&self.org_direccion),
+            ExportCell::Date(self.org_fechaconst),
+            ExportCell::text(&self.org_perjuridica),
+            ExportCell::optional_text(&self.org_email),
+            ExportCell::optional_text(&self.uv_nombre),
+        ]
+    }
+}
+
+impl ExportableRows for Actividad {
+    const HEADERS: &'static [&'static str] = &[
+        "Nombre", "Fecha inicio", "Fecha fin", "Descripción", "Unidad Vecinal", "Taller",
+    ];
+
+    fn export_row(&self) -> Vec<ExportCell> {
+        vec![
+            ExportCell::text(&self.act_nombre),
+            ExportCell::Date(self.act_fecha_ini),
+            ExportCell::optional_date(&self.act_fecha_fin),
+            ExportCell::optional_text(&self.act_descripcion),
+            ExportCell::optional_text(&self.uv_nombre),
+            ExportCell::optional_text(&self.tal_nombre),
+        ]
+    }
+}
+
+// Filas exportadas, opcionalmente bajo un título de sección. Una exportación sin agrupar
+// es una sola sección sin título.
+pub struct ExportSection<T> {
+    pub title: Option<String>,
+    pub rows: Vec<T>,
+}
+
+impl<T> ExportSection<T> {
+    pub fn ungrouped(rows: Vec<T>) -> Vec<Self> {
+        vec![ExportSection { title: None, rows }]
+    }
+}
+
+// Clave de orden alfabético que ignora mayúsculas y tildes; la ñ queda entre la n y la o
+fn name_sort_key(text: &str) -> String {
+    text.trim().to_lowercase().chars().fold(String::with_capacity(text.len()), |mut key, c| {
+        match c {
+            'á' | 'à' | 'ä' => key.push('a'),
+            'é' | 'è' | 'ë' => key.push('e'),
+            'í' | 'ì' | 'ï' => key.push('i'),
+            'ó' | 'ò' | 'ö' => key.push('o'),
+            'ú' | 'ù' | 'ü' => key.push('u'),
+            'ñ' => key.push_str("n~"),
+            c => key.push(c),
+        }
+        key
+    })
+}
+
+// Las UV se ordenan por su número cuando el nombre lo tiene ("UV 2" antes que "UV 10");
+// las que no tienen número van después, por nombre, y las personas sin UV al final
+fn uv_sort_key(uv_nombre: Option<&str>) -> (bool, Option<u64>, String) {
+    let Some(nombre) = uv_nombre else {
+        return (true, None, String::new());
+    };
+    let number = nombre.split(|c: char| !c.is_ascii_digit())
+        .find(|part| !part.is_empty())
+        .and_then(|digits| digits.parse().ok());
+    (false, number.or(Some(u64::MAX)), name_sort_key(nombre))
+}
+
+// Personas agrupadas por unidad vecinal y ordenadas por UV y luego por apellidos y
+// nombre, para planificar visitas puerta a puerta. Las personas sin UV van al final.
+pub fn group_personas_by_uv(mut personas: Vec<PersonaMayor>) -> Vec<ExportSection<PersonaMayor>> {
+    personas.sort_by_cached_key(|p| {
+        (
+            uv_sort_key(p.uv_nombre.as_deref()),
+            p.per_uvid,
+            name_sort_key(&p.per_priapellido),
+            name_sort_key(p.per_segapellido.as_deref().unwrap_or("")),
+            name_sort_key(&p.per_prinombre),
+        )
+    });
+
+    // Se agrupa por id: dos UV con el mismo nombre no se mezclan en una sección
+    let mut sections: Vec<ExportSection<PersonaMayor>> = Vec::new();
+    let mut current_uv = None;
+    for persona in personas {
+        let uv_id = persona.uv_nombre.is_some().then_some(persona.per_uvid);
+        if !sections.is_empty() && current_uv == Some(uv_id) {
+            if let Some(section) = sections.last_mut() {
+                section.rows.push(persona);
+            }
+            continue;
+        }
+        current_uv = Some(uv_id);
+        let uv = persona.uv_nombre.clone().unwrap_or_else(|| "Sin unidad vecinal".to_string());
+        sections.push(ExportSection { title: Some(uv), rows: vec![persona] });
+    }
+    for section in &mut sections {
+        let count = section.rows.len();
+        let noun = if count == 1 { "persona" } else { "personas" };
+        section.title = section.title.take()
+            .map(|uv| format!("Unidad Vecinal: {} ({} {})", uv, utils::format_number(count as i64), noun));
+    }
+    sections
+}
+
+// Los títulos de sección van en una línea propia, precedida de una línea en blanco
+pub fn write_csv<T: ExportableRows>(sections: &[ExportSection<T>], path: &Path) -> Result<()> {
+    let mut content = T::HEADERS.join(",");
+    content.push('\n');
+    for (index, section) in sections.iter().enumerate() {
+        if let Some(title) = &section.title {
+            if index > 0 {
+                content.push('\n');
+            }
+            content.push_str(&ExportCell::text(title).to_csv_field());
+            content.push('\n');
+        }
+        for row in &section.rows {
+            let fields: Vec<String> = row.export_row().iter().map(ExportCell::to_csv_field).collect();
+            content.push_str(&fields.join(","));
+            content.push('\n');
+        }
+    }
+    std::fs::write(path, content)?;
+    Ok(())
+}
+
+pub fn write_xlsx<T: ExportableRows>(sections: &[ExportSection<T>], sheet_name: &str, path: &Path) -> Result<()> {
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name(sheet_name)?;
+
+    let header_format = Format::new().set_bold();
+    let section_format = Format::new().set_bold().set_background_color(0xDDEBF7);
+    let date_format = Format::new().set_num_format("dd/mm/yyyy");
+
+    for (col, header) in T::HEADERS.iter().enumerate() {
+        worksheet.write_string_with_format(0, col as u16, *header, &header_format)?;
+    }
+    worksheet.set_freeze_panes(1, 0)?;
+
+    let mut row_num = 0;
+    for section in sections {
+        if let Some(title) = &section.title {
+            row_num += 1;
+            worksheet.merge_range(row_num, 0, row_num, T::HEADERS.len() as u16 - 1, title, &section_format)?;
+        }
+        for row in &section.rows {
+            row_num += 1;
+            for (col, cell) in row.export_row().into_iter().enumerate() {
+                let col = col as u16;
+                match cell {
+                    ExportCell::Text(text) => {
+                        worksheet.write_string(row_num, col, text)?;
+                    }
+                    ExportCell::Number(number) => {
+                        worksheet.write_number(row_num, col, number as f64)?;
+                    }
+                    ExportCell::Date(date) => {
+                        let excel_date = ExcelDateTime::from_ymd(date.year() as u16, date.month() as u8, date.day() as u8)?;
+                        worksheet.write_datetime_with_format(row_num, col, &excel_date, &date_format)?;
+                    }
+                    ExportCell::Empty => {}
+                }
+            }
+        }
+    }
+
+    worksheet.autofit();
+    workbook.save(path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn persona(uv_id: i32, uv: Option<&str>, apellido: &str) -> PersonaMayor {
+        PersonaMayor {
+            per_id: 0,
+            per_rut: String::new(),
+            per_prinombre: "Ana".to_string(),
+            per_segnombre: None,
+            per_priapellido: apellido.to_string(),
+            per_segapellido: None,
+            per_genid: 1,
+            per_nacid: 1,
+            per_fechadenac: NaiveDate::from_ymd_opt(1950, 1, 1).unwrap(),
+            per_direccion: String::new(),
+            per_email: None,
+            per_uvid: uv_id,
+            per_fecha_registro: None,
+            per_fecha_modificacion: None,
+            gen_genero: None,
+            nac_nacionalidad: None,
+            uv_nombre: uv.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn name_sort_key_ignora_mayusculas_y_tildes() {
+        assert_eq!(name_sort_key("Álvarez"), name_sort_key("alvarez"));
+        assert!(name_sort_key("Muñoz") > name_sort_key("Munoz"));
+        assert!(name_sort_key("Muñoz") < name_sort_key("Muoz"));
+    }
+
+    #[test]
+    fn group_personas_by_uv_ordena_por_numero_y_agrupa_por_id() {
+        let sections = group_personas_by_uv(vec![
+            persona(3, Some("UV 10"), "pérez"),
+            persona(1, Some("UV 2"), "Soto"),
+            persona(9, None, "Rojas"),
+            persona(2, Some("UV 2"), "Araya"),
+            persona(3, Some("UV 10"), "Álvarez"),
+        ]);
+        let titles: Vec<_> = sections.iter().map(|s| s.title.clone().unwrap()).collect();
+        assert_eq!(titles, [
+            "Unidad Vecinal: UV 2 (1 persona)",
+            "Unidad Vecinal: UV 2 (1 persona)",
+            "Unidad Vecinal: UV 10 (2 personas)",
+            "Unidad Vecinal: Sin unidad vecinal (1 persona)",
+        ]);
+        let apellidos: Vec<_> = sections[2].rows.iter().map(|p| p.per_priapellido.as_str()).collect();
+        assert_eq!(apellidos, ["Álvarez", "pérez"]);
+    }
+}
@@ -0,0 +1,247 @@
+// Importación de personas desde archivos CSV con columnas en cualquier orden
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use crate::models::{Genero, Nacionalidad, PersonaMayor, UnidadVecinal};
+use crate::utils;
+
+// Campo de persona al que puede asignarse una columna del archivo
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PersonaField {
+    Rut,
+    PrimerNombre,
+    SegundoNombre,
+    PrimerApellido,
+    SegundoApellido,
+    FechaNacimiento,
+    Genero,
+    Nacionalidad,
+    Direccion,
+    Email,
+    UnidadVecinal,
+}
+
+impl PersonaField {
+    pub const ALL: [PersonaField; 11] = [
+        PersonaField::Rut,
+        PersonaField::PrimerNombre,
+        PersonaField::SegundoNombre,
+        PersonaField::PrimerApellido,
+        PersonaField::SegundoApellido,
+        PersonaField::FechaNacimiento,
+        PersonaField::Genero,
+        PersonaField::Nacionalidad,
+        PersonaField::Direccion,
+        PersonaField::Email,
+        PersonaField::UnidadVecinal,
+    ];
+
+    // Mismos nombres que los encabezados de la exportación, para reconocer esos archivos
+    pub fn label(&self) -> &'static str {
+        match self {
+            PersonaField::Rut => "RUT",
+            PersonaField::PrimerNombre => "Primer nombre",
+            PersonaField::SegundoNombre => "Segundo nombre",
+            PersonaField::PrimerApellido => "Primer apellido",
+            PersonaField::SegundoApellido => "Segundo apellido",
+            PersonaField::FechaNacimiento => "Fecha de nacimiento",
+            PersonaField::Genero => "Género",
+            PersonaField::Nacionalidad => "Nacionalidad",
+            PersonaField::Direccion => "Dirección",
+            PersonaField::Email => "Email",
+            PersonaField::UnidadVecinal => "Unidad Vecinal",
+        }
+    }
+
+    pub fn required(&self) -> bool {
+        !matches!(self, PersonaField::SegundoNombre | PersonaField::SegundoApellido | PersonaField::Email)
+    }
+}
+
+// Columna del archivo -> campo de persona (None = columna omitida)
+pub type ColumnMapping = Vec<Option<PersonaField>>;
+
+#[derive(Debug, Clone, Default)]
+pub struct CsvData {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    // Número de fila de cada elemento de `rows` en el archivo (el encabezado es la fila 1),
+    // que no coincide con la posición cuando se omitieron filas en blanco
+    pub row_numbers: Vec<usize>,
+}
+
+// Lee un CSV separado por comas o punto y coma (se detecta en el encabezado).
+// Acepta campos entre comillas con comillas dobles escapadas, como los genera la exportación.
+pub fn read_csv(path: &Path) -> Result<CsvData> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("No se pudo leer {}", path.display()))?;
+    let content = content.trim_start_matches('\u{feff}');
+
+    let first_line = content.lines().next().unwrap_or_default();
+    let delimiter = if first_line.matches(';').count() > first_line.matches(',').count() { ';' } else { ',' };
+
+    let mut records = parse_records(content, delimiter).into_iter();
+    let headers: Vec<String> = records.next()
+        .context("El archivo está vacío")?
+        .into_iter()
+        .map(|header| header.trim().to_string())
+        .collect();
+    let (row_numbers, rows) = records
+        .enumerate()
+        .filter(|(_, record)| record.iter().any(|value| !value.trim().is_empty()))
+        .map(|(index, record)| (index + 2, record))
+        .unzip();
+    Ok(CsvData { headers, rows, row_numbers })
+}
+
+fn parse_records(content: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    field.push('"');
+                    chars.next();
+                }
+                '"' => in_quotes = false,
+                _ => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' => in_quotes = true,
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            c if c == delimiter => record.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+    records
+}
+
+// Identifica un formato de archivo por sus encabezados, para recordar su mapeo
+pub fn header_signature(headers: &[String]) -> String {
+    headers.iter()
+        .map(|header| header.trim().to_lowercase())
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+// Mapeo inicial: cada encabezado que coincide con el nombre de un campo se asigna a él
+pub fn guess_mapping(headers: &[String]) -> ColumnMapping {
+    let mut used = Vec::new();
+    headers.iter()
+        .map(|header| {
+            let header = header.trim().to_lowercase();
+            let field = PersonaField::ALL.iter()
+                .find(|field| !used.contains(*field) && field.label().to_lowercase() == header)
+                .copied();
+            used.extend(field);
+            field
+        })
+        .collect()
+}
+
+// Campos obligatorios que ninguna columna tiene asignados
+pub fn missing_required(mapping: &[Option<PersonaField>]) -> Vec<&'static str> {
+    PersonaField::ALL.iter()
+        .filter(|field| field.required() && !mapping.contains(&Some(**field)))
+        .map(|field| field.label())
+        .collect()
+}
+
+// Catálogos para traducir los nombres del archivo a ids
+pub struct ImportCatalogs<'a> {
+    pub generos: &'a [Genero],
+    pub nacionalidades: &'a [Nacionalidad],
+    pub unidades_vecinales: &'a [UnidadVecinal],
+}
+
+// Convierte una fila en persona; el error describe el primer campo inválido.
+// `min_age` es la edad mínima para el registro (AppConfig::min_age).
+pub fn build_persona(row: &[String], mapping: &[Option<PersonaField>], catalogs: &ImportCatalogs, min_age: i32) -> Result<PersonaMayor, String> {
+    let value = |field: PersonaField| -> &str {
+        mapping.iter()
+            .position(|mapped| *mapped == Some(field))
+            .and_then(|column| row.get(column))
+            .map(|value| value.trim())
+            .unwrap_or_default()
+    };
+    let required = |field: PersonaField| -> Result<String, String> {
+        match value(field) {
+            "" => Err(format!("{} vacío", field.label())),
+            value => Ok(value.to_string()),
+        }
+    };
+    let optional = |field: PersonaField| Some(value(field).to_string()).filter(|value| !value.is_empty());
+    let matches = |a: &str, b: &str| a.trim().to_lowercase() == b.trim().to_lowercase();
+
+    let rut = utils::normalize_rut(&required(PersonaField::Rut)?);
+    if !utils::validate_rut_check_digit(&rut) {
+        return Err(format!("RUT inválido: {}", rut));
+    }
+
+    let fecha = required(PersonaField::FechaNacimiento)?;
+    let fecha_nacimiento = utils::parse_date(&fecha)
+        .ok_or_else(|| format!("Fecha de nacimiento inválida: {}", fecha))?;
+    if let Some(error) = utils::birthdate_error(&fecha_nacimiento, min_age) {
+        return Err(format!("{}: {}", error, fecha));
+    }
+
+    let email = optional(PersonaField::Email);
+    if let Some(email) = &email {
+        if !utils::validate_email(email) {
+            return Err(format!("Email inválido: {}", email));
+        }
+    }
+
+    let genero = required(PersonaField::Genero)?;
+    let genero_id = catalogs.generos.iter()
+        .find(|g| matches(&g.gen_genero, &genero))
+        .map(|g| g.gen_id)
+        .ok_or_else(|| format!("Género desconocido: {}", genero))?;
+
+    let nacionalidad = required(PersonaField::Nacionalidad)?;
+    let nacionalidad_id = catalogs.nacionalidades.iter()
+        .find(|n| matches(&n.nac_nacionalidad, &nacionalidad))
+        .map(|n| n.nac_id)
+        .ok_or_else(|| format!("Nacionalidad desconocida: {}", nacionalidad))?;
+
+    let uv = required(PersonaField::UnidadVecinal)?;
+    let uv_id = catalogs.unidades_vecinales.iter()
+        .find(|u| matches(&u.uv_nombre, &uv))
+        .map(|u| u.uv_id)
+        .ok_or_else(|| format!("Unidad vecinal desconocida: {}", uv))?;
+
+    Ok(PersonaMayor {
+        per_id: 0,
+        per_rut: rut,
+        per_prinombre: required(PersonaField::PrimerNombre)?,
+        per_segnombre: optional(PersonaField::SegundoNombre),
+        per_priapellido: required(PersonaField::PrimerApellido)?,
+        per_segapellido: optional(PersonaField::SegundoApellido),
+        per_genid: genero_id,
+        per_nacid: nacionalidad_id,
+        per_fechadenac: fecha_nacimiento,
+        per_direccion: required(PersonaField::Direccion)?,
+        per_email: email,
+        per_uvid: uv_id,
+        per_fecha_registro: None,
+        per_fecha_modificacion: None,
+        gen_genero: None,
+        nac_nacionalidad: None,
+        uv_nombre: None,
+    })
+}
@@ -1,7 +1,12 @@
 use eframe::egui;
 
+mod config;
 mod database;
+mod export;
+mod import;
+mod migrations;
 mod models;
+mod report;
 mod ui;
 mod utils;
 
@@ -0,0 +1,37 @@
+// Migraciones del esquema, embebidas en el ejecutable y aplicadas en orden de versión.
+// Cada migración se registra en schema_migrations al aplicarse; nunca se modifica una
+// migración ya publicada, los cambios van en un archivo nuevo.
+
+pub struct Migration {
+    pub version: i32,
+    pub nombre: &'static str,
+    pub sql: &'static str,
+}
+
+pub const MIGRATIONS: [Migration; 4] = [
+    Migration {
+        version: 1,
+        nombre: "esquema inicial",
+        sql: include_str!("../migrations/0001_esquema_inicial.sql"),
+    },
+    Migration {
+        version: 2,
+        nombre: "columnas de auditoría",
+        sql: include_str!("../migrations/0002_columnas_auditoria.sql"),
+    },
+    Migration {
+        version: 3,
+        nombre: "catálogos base",
+        sql: include_str!("../migrations/0003_catalogos_base.sql"),
+    },
+    Migration {
+        version: 4,
+        nombre: "taller de las actividades",
+        sql: include_str!("../migrations/0004_actividad_taller.sql"),
+    },
+];
+
+// Versión que alcanza una base de datos con todas las migraciones aplicadas
+pub fn latest_version() -> i32 {
+    MIGRATIONS.iter().map(|m| m.version).max().unwrap_or(0)
+}
@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use chrono::NaiveDate;
+use chrono::{NaiveDate, NaiveDateTime};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseConfig {
@@ -22,6 +22,33 @@ impl Default for DatabaseConfig {
     }
 }
 
+impl DatabaseConfig {
+    // Cadena de conexión para compartir en soportes: la contraseña siempre va enmascarada
+    pub fn sanitized_dsn(&self) -> String {
+        format!("postgresql://{}:****@{}:{}/{}", self.username, self.host, self.port, self.database)
+    }
+
+    // El puerto 0 no es válido para conectarse
+    pub fn port_error(&self) -> Option<&'static str> {
+        (self.port == 0).then_some("Puerto inválido")
+    }
+
+    // Los puertos bajo 1024 están reservados para servicios del sistema: se permite conectar, pero se avisa
+    pub fn port_warning(&self) -> Option<&'static str> {
+        (self.port > 0 && self.port < 1024)
+            .then_some("Puerto reservado del sistema; PostgreSQL usa normalmente 5432")
+    }
+
+    // Motivo por el que no se puede intentar la conexión, si lo hay
+    pub fn validation_error(&self) -> Option<&'static str> {
+        if self.host.is_empty() || self.username.is_empty() || self.database.is_empty() {
+            Some("Por favor complete todos los campos requeridos")
+        } else {
+            self.port_error()
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MacroSector {
     pub mac_id: i32,
@@ -57,6 +84,8 @@ pub struct OrganizacionComunitaria {
     pub org_fechaconst: NaiveDate,
     pub org_perjuridica: String,
     pub org_email: Option<String>,
+    pub org_fecha_registro: Option<NaiveDateTime>,
+    pub org_fecha_modificacion: Option<NaiveDateTime>,
     pub uv_nombre: Option<String>, // Para joins
 }
 
@@ -74,11 +103,31 @@ pub struct PersonaMayor {
     pub per_direccion: String,
     pub per_email: Option<String>,
     pub per_uvid: i32,
+    pub per_fecha_registro: Option<NaiveDateTime>,
+    pub per_fecha_modificacion: Option<NaiveDateTime>,
     pub gen_genero: Option<String>, // Para joins
     pub nac_nacionalidad: Option<String>, // Para joins
     pub uv_nombre: Option<String>, // Para joins
 }
 
+impl PersonaMayor {
+    // Nombres y apellidos separados por un espacio, omitiendo los opcionales vacíos
+    pub fn nombre_completo(&self) -> String {
+        [
+            Some(self.per_prinombre.as_str()),
+            self.per_segnombre.as_deref(),
+            Some(self.per_priapellido.as_str()),
+            self.per_segapellido.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(str::trim)
+        .filter(|parte| !parte.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Taller {
     pub tal_id: i32,
@@ -93,7 +142,60 @@ pub struct Actividad {
     pub act_fecha_ini: NaiveDate,
     pub act_fecha_fin: Option<NaiveDate>,
     pub act_descripcion: Option<String>,
+    pub act_talid: Option<i32>,
+    pub act_fecha_registro: Option<NaiveDateTime>,
+    pub act_fecha_modificacion: Option<NaiveDateTime>,
     pub uv_nombre: Option<String>, // Para joins
+    pub tal_nombre: Option<String>, // Para joins
+}
+
+// Estado de una actividad según sus fechas y la fecha de hoy
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ActividadEstado {
+    Proxima,
+    EnCurso,
+    Finalizada,
+}
+
+impl ActividadEstado {
+    pub const ALL: [ActividadEstado; 3] = [
+        ActividadEstado::Proxima,
+        ActividadEstado::EnCurso,
+        ActividadEstado::Finalizada,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ActividadEstado::Proxima => "Próxima",
+            ActividadEstado::EnCurso => "En curso",
+            ActividadEstado::Finalizada => "Finalizada",
+        }
+    }
+
+    // Condición SQL equivalente a `Actividad::estado`; `$index` es la fecha de hoy
+    pub fn sql_condition(&self, index: usize) -> String {
+        match self {
+            ActividadEstado::Proxima => format!("act.act_fecha_ini > ${index}"),
+            ActividadEstado::EnCurso => format!(
+                "act.act_fecha_ini <= ${index} AND (act.act_fecha_fin IS NULL OR act.act_fecha_fin >= ${index})"
+            ),
+            ActividadEstado::Finalizada => format!("act.act_fecha_ini <= ${index} AND act.act_fecha_fin < ${index}"),
+        }
+    }
+}
+
+impl Actividad {
+    // Próxima si aún no empieza, finalizada si su fecha de fin ya pasó y en curso en otro
+    // caso (también cuando empezó y no tiene fecha de fin)
+    pub fn estado(&self, hoy: NaiveDate) -> ActividadEstado {
+        if self.act_fecha_ini > hoy {
+            ActividadEstado::Proxima
+        } else if self.act_fecha_fin.is_some_and(|fin| fin < hoy) {
+            ActividadEstado::Finalizada
+        } else {
+            ActividadEstado::EnCurso
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -131,14 +233,137 @@ pub struct Telefono {
     pub numero: String,
 }
 
-// Estructuras para filtros de consultas
-#[derive(Debug, Clone, Default)]
+// Entidades que pueden tener teléfonos asociados
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TelefonoEntidad {
+    Persona,
+    Organizacion,
+}
+
+// Tipos de teléfono admitidos en los formularios
+pub const TIPOS_TELEFONO: [&str; 3] = ["móvil", "fijo", "emergencia"];
+
+// Resultado de buscar un nombre ya registrado antes de insertar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NameMatch {
+    None,
+    // Coincide salvo mayúsculas o espacios al inicio o al final
+    Variant,
+    Exact,
+}
+
+// Tipo de un registro recién ingresado; `tag` es la etiqueta que devuelve la consulta
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RecentRecordKind {
+    Persona,
+    Organizacion,
+    Actividad,
+}
+
+impl RecentRecordKind {
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        match tag {
+            "persona" => Some(RecentRecordKind::Persona),
+            "organizacion" => Some(RecentRecordKind::Organizacion),
+            "actividad" => Some(RecentRecordKind::Actividad),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RecentRecordKind::Persona => "Persona",
+            RecentRecordKind::Organizacion => "Organización",
+            RecentRecordKind::Actividad => "Actividad",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RecentRecord {
+    pub kind: RecentRecordKind,
+    pub id: i32,
+    pub nombre: String,
+    pub creado: Option<NaiveDateTime>,
+}
+
+// Datos de una persona y sus vínculos para imprimir su ficha
+#[derive(Debug, Clone)]
+pub struct PersonaFicha {
+    pub persona: PersonaMayor,
+    pub macro_sector: Option<String>,
+    pub telefonos: Vec<Telefono>,
+    pub organizaciones: Vec<String>,
+    // Actividades a las que asistió (nombre, fecha de inicio), la más reciente primero
+    pub actividades: Vec<(String, NaiveDate)>,
+}
+
+// Campo por el que se ordena el listado de personas (ORDER BY en el servidor)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum PersonaSortField {
+    #[default]
+    Apellido,
+    Nombre,
+    Rut,
+    Edad,
+    UnidadVecinal,
+}
+
+impl PersonaSortField {
+    pub const ALL: [PersonaSortField; 5] = [
+        PersonaSortField::Apellido,
+        PersonaSortField::Nombre,
+        PersonaSortField::Rut,
+        PersonaSortField::Edad,
+        PersonaSortField::UnidadVecinal,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PersonaSortField::Apellido => "Apellido",
+            PersonaSortField::Nombre => "Nombre",
+            PersonaSortField::Rut => "RUT",
+            PersonaSortField::Edad => "Edad",
+            PersonaSortField::UnidadVecinal => "UV",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PersonaSort {
+    pub field: PersonaSortField,
+    pub descending: bool,
+}
+
+impl PersonaSort {
+    // ORDER BY para el listado; per_id al final mantiene estable la paginación
+    pub fn order_by(&self) -> String {
+        let (dir, inverse) = if self.descending { ("DESC", "ASC") } else { ("ASC", "DESC") };
+        let columns = match self.field {
+            PersonaSortField::Apellido => format!("per.per_priapellido {dir}, per.per_prinombre {dir}"),
+            PersonaSortField::Nombre => format!("per.per_prinombre {dir}, per.per_priapellido {dir}"),
+            // Por el número del RUT, sin puntos ni dígito verificador (también si es K), de modo
+            // que 9999999-9 quede antes que 10000000-K. Los RUT sin ese formato van al final.
+            PersonaSortField::Rut => format!(
+                "substring(regexp_replace(per.per_rut, '[^0-9Kk]', '', 'g') from '^([0-9]+)[0-9Kk]$')::bigint {dir} NULLS LAST, per.per_rut {dir}"
+            ),
+            // Mayor edad = fecha de nacimiento más antigua
+            PersonaSortField::Edad => format!("per.per_fechadenac {inverse}, per.per_priapellido, per.per_prinombre"),
+            PersonaSortField::UnidadVecinal => format!("uv.uv_nombre {dir}, per.per_priapellido, per.per_prinombre"),
+        };
+        format!("{}, per.per_id", columns)
+    }
+}
+
+// Estructuras para filtros de consultas. Se serializan para guardar y volver a cargar
+// los criterios de una búsqueda (ver config::FilterCriteria).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PersonaFilter {
     pub nombre: String,
     pub apellido: String,
     pub rut: String,
     pub genero_id: Option<i32>,
-    #[allow(dead_code)]
     pub nacionalidad_id: Option<i32>,
     pub unidad_vecinal_id: Option<i32>,
     pub macro_sector_id: Option<i32>,
@@ -146,40 +371,111 @@ pub struct PersonaFilter {
     pub edad_min: Option<i32>,
     #[allow(dead_code)]
     pub edad_max: Option<i32>,
+    pub orden: PersonaSort,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct OrganizacionFilter {
     pub nombre: String,
     pub unidad_vecinal_id: Option<i32>,
-    #[allow(dead_code)]
     pub macro_sector_id: Option<i32>,
-    #[allow(dead_code)]
     pub fecha_const_desde: Option<NaiveDate>,
-    #[allow(dead_code)]
     pub fecha_const_hasta: Option<NaiveDate>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ActividadFilter {
     pub nombre: String,
     pub unidad_vecinal_id: Option<i32>,
-    #[allow(dead_code)]
     pub macro_sector_id: Option<i32>,
-    #[allow(dead_code)]
     pub fecha_desde: Option<NaiveDate>,
-    #[allow(dead_code)]
     pub fecha_hasta: Option<NaiveDate>,
+    pub estado: Option<ActividadEstado>,
+    pub taller_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ViajeFilter {
+    pub nombre: String,
+    pub destino: String,
+    pub unidad_vecinal_id: Option<i32>,
+    pub fecha_salida_desde: Option<NaiveDate>,
+    pub fecha_salida_hasta: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CentroFilter {
+    pub nombre: String,
+    pub unidad_vecinal_id: Option<i32>,
+}
+
+// Paginación de los listados de consultas
+pub const PAGE_SIZE_OPTIONS: [i64; 4] = [25, 50, 100, 200];
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+
+// Página solicitada de un listado; `limit` None trae todas las filas
+#[derive(Debug, Clone, Copy)]
+pub struct PageRequest {
+    pub limit: Option<i64>,
+    pub offset: i64,
+}
+
+impl PageRequest {
+    pub fn all() -> Self {
+        Self { limit: None, offset: 0 }
+    }
+}
+
+// Filas de una página junto al total de filas que cumplen el filtro
+#[derive(Debug, Clone)]
+pub struct PagedResult<T> {
+    pub rows: Vec<T>,
+    pub total: i64,
+}
+
+// Resultado de la consola SQL: columnas y filas como texto. `truncated` indica que la
+// consulta tenía más filas que las devueltas.
+#[derive(Debug, Clone, Default)]
+pub struct ReadonlyQueryResult {
+    pub columns: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    pub truncated: bool,
 }
 
 // Tipos para estadísticas del dashboard
+// Máximo de resultados por categoría en la búsqueda global
+pub const GLOBAL_SEARCH_LIMIT: i64 = 20;
+
+#[derive(Debug, Clone, Default)]
+pub struct GlobalSearchResults {
+    pub personas: Vec<PersonaMayor>,
+    pub organizaciones: Vec<OrganizacionComunitaria>,
+    pub actividades: Vec<Actividad>,
+}
+
+// Hallazgo del reporte de integridad: qué se revisó y las filas que no cumplen
+#[derive(Debug, Clone)]
+pub struct IntegrityFinding {
+    pub descripcion: String,
+    pub detalles: Vec<String>,
+}
+
+// Actividades recientes que muestra el dashboard
+pub const RECENT_ACTIVIDADES_LIMIT: i64 = 5;
+
 #[derive(Debug, Clone, Default)]
 pub struct DashboardStats {
     pub total_personas: i64,
     pub total_organizaciones: i64,
     pub total_actividades: i64,
     pub total_viajes: i64,
-    pub personas_por_macro: Vec<(String, i64)>,
+    pub total_centros: Option<i64>, // None si la tabla de centros no existe
+    pub personas_por_macro: Vec<(i32, String, i64)>, // (mac_id, nombre, personas)
+    pub edades_por_rango: Vec<(String, i64)>,
     pub actividades_mes_actual: i64,
     pub nuevas_personas_mes: i64,
+    pub promedio_participantes: f64,
+    pub ultimas_actividades: Vec<Actividad>,
 }
@@ -0,0 +1,208 @@
+// Generación de PDFs: reporte de estadísticas del dashboard y ficha de una persona
+use anyhow::{Context, Result};
+use chrono::Local;
+use printpdf::{BuiltinFont, IndirectFontRef, Line, Mm, PdfDocument, PdfLayerReference, Point};
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use crate::models::{DashboardStats, PersonaFicha};
+use crate::utils;
+
+// Tamaño carta en milímetros
+const PAGE_WIDTH: f32 = 215.9;
+const PAGE_HEIGHT: f32 = 279.4;
+const MARGIN: f32 = 20.0;
+
+// Elementos por lista en la ficha de persona para que quepa en una página
+const FICHA_MAX_ITEMS: usize = 6;
+
+// Cursor vertical para escribir líneas de arriba hacia abajo
+struct PdfWriter<'a> {
+    layer: &'a PdfLayerReference,
+    regular: &'a IndirectFontRef,
+    bold: &'a IndirectFontRef,
+    y: f32,
+}
+
+impl PdfWriter<'_> {
+    fn text(&mut self, text: &str, size: f32, bold: bool, x: f32) {
+        let font = if bold { self.bold } else { self.regular };
+        self.layer.use_text(text, size, Mm(x), Mm(self.y), font);
+    }
+
+    fn line(&mut self, text: &str, size: f32, bold: bool) {
+        self.text(text, size, bold, MARGIN);
+        self.y -= size * 0.5;
+    }
+
+    // Fila de dos columnas: etiqueta a la izquierda y valor alineado en la segunda columna
+    fn row(&mut self, label: &str, value: &str) {
+        self.text(label, 11.0, false, MARGIN + 5.0);
+        self.text(value, 11.0, true, MARGIN + 110.0);
+        self.y -= 7.0;
+    }
+
+    // Campo de una ficha: etiqueta corta y valor largo (dirección, teléfonos) más a la izquierda
+    fn field(&mut self, label: &str, value: &str) {
+        self.text(label, 11.0, false, MARGIN + 5.0);
+        self.text(value, 11.0, true, MARGIN + 50.0);
+        self.y -= 7.0;
+    }
+
+    fn separator(&mut self) {
+        self.y -= 2.0;
+        self.layer.set_outline_thickness(0.5);
+        self.layer.add_line(Line {
+            points: vec![
+                (Point::new(Mm(MARGIN), Mm(self.y)), false),
+                (Point::new(Mm(PAGE_WIDTH - MARGIN), Mm(self.y)), false),
+            ],
+            is_closed: false,
+        });
+        self.y -= 8.0;
+    }
+}
+
+fn new_document(title: &str) -> Result<(printpdf::PdfDocumentReference, PdfLayerReference, IndirectFontRef, IndirectFontRef)> {
+    let (doc, page, layer) = PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Contenido");
+    let regular = doc.add_builtin_font(BuiltinFont::Helvetica)
+        .context("No se pudo cargar la fuente del reporte")?;
+    let bold = doc.add_builtin_font(BuiltinFont::HelveticaBold)
+        .context("No se pudo cargar la fuente del reporte")?;
+    let layer = doc.get_page(page).get_layer(layer);
+    Ok((doc, layer, regular, bold))
+}
+
+fn save_document(doc: printpdf::PdfDocumentReference, path: &Path) -> Result<()> {
+    let file = File::create(path)
+        .with_context(|| format!("No se pudo crear el archivo {}", path.display()))?;
+    doc.save(&mut BufWriter::new(file))
+        .context("Error al escribir el PDF")?;
+    Ok(())
+}
+
+pub fn write_dashboard_pdf(stats: &DashboardStats, database: &str, path: &Path) -> Result<()> {
+    let (doc, layer, regular, bold) = new_document("Reporte de Estadísticas")?;
+
+    let mut writer = PdfWriter {
+        layer: &layer,
+        regular: &regular,
+        bold: &bold,
+        y: PAGE_HEIGHT - MARGIN,
+    };
+
+    // Encabezado
+    writer.line("Reporte de Estadísticas Comunitarias", 18.0, true);
+    writer.y -= 2.0;
+    writer.line(&format!("Base de datos: {}", database), 10.0, false);
+    writer.line(&format!("Generado: {}", Local::now().format("%d/%m/%Y %H:%M")), 10.0, false);
+    writer.separator();
+
+    // Totales
+    writer.line("Resumen General", 14.0, true);
+    writer.row("Personas mayores", &stats.total_personas.to_string());
+    writer.row("Organizaciones", &stats.total_organizaciones.to_string());
+    writer.row("Actividades", &stats.total_actividades.to_string());
+    writer.row("Viajes", &stats.total_viajes.to_string());
+    if let Some(total_centros) = stats.total_centros {
+        writer.row("Centros comunitarios", &total_centros.to_string());
+    }
+    writer.separator();
+
+    // Distribución por macrosector
+    writer.line("Personas por Macrosector", 14.0, true);
+    if stats.personas_por_macro.is_empty() {
+        writer.row("Sin datos disponibles", "");
+    } else {
+        for (_, macro_name, count) in &stats.personas_por_macro {
+            writer.row(macro_name, &count.to_string());
+        }
+    }
+    writer.separator();
+
+    // Distribución por rango de edad
+    writer.line("Personas por Rango de Edad", 14.0, true);
+    for (rango, count) in &stats.edades_por_rango {
+        writer.row(&format!("{} años", rango), &count.to_string());
+    }
+    writer.separator();
+
+    // Actividad del mes
+    writer.line("Actividad del Mes Actual", 14.0, true);
+    writer.row("Actividades este mes", &stats.actividades_mes_actual.to_string());
+    writer.row("Nuevas personas", &stats.nuevas_personas_mes.to_string());
+    writer.row("Promedio de participantes por actividad", &format!("{:.1}", stats.promedio_participantes));
+
+    save_document(doc, path)
+}
+
+// Lista de la ficha recortada a FICHA_MAX_ITEMS, con el total de los omitidos
+fn ficha_list(writer: &mut PdfWriter, title: &str, items: &[String]) {
+    writer.line(&format!("{} ({})", title, items.len()), 14.0, true);
+    let mut lines: Vec<String> = items.iter().take(FICHA_MAX_ITEMS).cloned().collect();
+    if items.is_empty() {
+        lines.push("Sin registros".to_string());
+    } else if items.len() > FICHA_MAX_ITEMS {
+        lines.push(format!("... y {} más", items.len() - FICHA_MAX_ITEMS));
+    }
+    for line in lines {
+        writer.text(&line, 10.0, false, MARGIN + 5.0);
+        writer.y -= 5.0;
+    }
+    writer.separator();
+}
+
+// Ficha imprimible de una persona en una sola página
+pub fn write_persona_pdf(ficha: &PersonaFicha, path: &Path) -> Result<()> {
+    let (doc, layer, regular, bold) = new_document("Ficha de Persona Mayor")?;
+    let mut writer = PdfWriter {
+        layer: &layer,
+        regular: &regular,
+        bold: &bold,
+        y: PAGE_HEIGHT - MARGIN,
+    };
+    let persona = &ficha.persona;
+    let nombre = [
+        Some(persona.per_prinombre.as_str()),
+        persona.per_segnombre.as_deref(),
+        Some(persona.per_priapellido.as_str()),
+        persona.per_segapellido.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join(" ");
+
+    // Encabezado
+    writer.line("Gestor Base de Datos Comunitaria", 18.0, true);
+    writer.y -= 2.0;
+    writer.line("Ficha de Persona Mayor", 12.0, false);
+    writer.line(&format!("Generado: {}", Local::now().format("%d/%m/%Y %H:%M")), 10.0, false);
+    writer.separator();
+
+    // Datos personales
+    writer.line(&nombre, 14.0, true);
+    writer.field("RUT", &persona.per_rut);
+    writer.field("Fecha de nacimiento", &utils::format_date(&persona.per_fechadenac));
+    writer.field("Edad", &format!("{} años", utils::calculate_age(&persona.per_fechadenac)));
+    writer.field("Género", persona.gen_genero.as_deref().unwrap_or("N/A"));
+    writer.field("Nacionalidad", persona.nac_nacionalidad.as_deref().unwrap_or("N/A"));
+    writer.field("Dirección", &persona.per_direccion);
+    writer.field("Email", persona.per_email.as_deref().unwrap_or("-"));
+    writer.field("Unidad vecinal", persona.uv_nombre.as_deref().unwrap_or("N/A"));
+    writer.field("Macrosector", ficha.macro_sector.as_deref().unwrap_or("N/A"));
+    let telefonos: Vec<String> = ficha.telefonos.iter()
+        .map(|telefono| format!("{} ({})", telefono.numero, telefono.tipo))
+        .collect();
+    writer.field("Teléfonos", if telefonos.is_empty() { "-".to_string() } else { telefonos.join(", ") }.as_str());
+    writer.field("Registrado", &utils::format_optional_date(&persona.per_fecha_registro.map(|fecha| fecha.date())));
+    writer.separator();
+
+    ficha_list(&mut writer, "Organizaciones", &ficha.organizaciones);
+    let actividades: Vec<String> = ficha.actividades.iter()
+        .map(|(nombre, fecha)| format!("{} - {}", utils::format_date(fecha), nombre))
+        .collect();
+    ficha_list(&mut writer, "Actividades", &actividades);
+
+    save_document(doc, path)
+}
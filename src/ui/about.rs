@@ -1,17 +1,334 @@
 use eframe::egui;
+use std::sync::Arc;
+use tokio::sync::{Mutex, mpsc};
+use crate::database::DatabaseManager;
+use crate::migrations;
+use crate::models::{DatabaseConfig, IntegrityFinding};
+use crate::ui::components::ConfirmDialog;
+
+// Datos de la conexión activa que se muestran en la sección Conexión
+struct ConnectionInfo {
+    // Configuración sin contraseña
+    config: DatabaseConfig,
+    server_version: String,
+    schema_version: Option<i32>,
+}
 
 pub struct AboutView {
+    db_manager: Arc<Mutex<DatabaseManager>>,
     show_tech_details: bool,
+
+    // Mantenimiento de RUT
+    normalizing: bool,
+    normalize_message: Option<(bool, String)>,
+    normalize_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+
+    // Reparación de los constraints de email (acción explícita, con confirmación)
+    confirm_email_repair: ConfirmDialog,
+    repairing_email: bool,
+    email_repair_message: Option<(bool, String)>,
+    email_repair_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+
+    // Reporte de integridad de datos
+    checking_integrity: bool,
+    integrity_findings: Vec<IntegrityFinding>,
+    integrity_error: Option<String>,
+    expanded_finding: Option<usize>,
+    integrity_receiver: Option<mpsc::UnboundedReceiver<Result<Vec<IntegrityFinding>, String>>>,
+
+    // Datos de la conexión activa
+    loading_connection: bool,
+    connection_info: Option<ConnectionInfo>,
+    connection_error: Option<String>,
+    dsn_copied: bool,
+    connection_receiver: Option<mpsc::UnboundedReceiver<Result<ConnectionInfo, String>>>,
 }
 
 impl AboutView {
-    pub fn new() -> Self {
+    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>) -> Self {
         Self {
+            db_manager,
             show_tech_details: false,
+            normalizing: false,
+            normalize_message: None,
+            normalize_receiver: None,
+            confirm_email_repair: ConfirmDialog::new(),
+            repairing_email: false,
+            email_repair_message: None,
+            email_repair_receiver: None,
+            checking_integrity: false,
+            integrity_findings: Vec::new(),
+            integrity_error: None,
+            expanded_finding: None,
+            integrity_receiver: None,
+            loading_connection: false,
+            connection_info: None,
+            connection_error: None,
+            dsn_copied: false,
+            connection_receiver: None,
+        }
+    }
+
+    fn check_normalize_result(&mut self) {
+        if let Some(receiver) = &mut self.normalize_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.normalizing = false;
+                self.normalize_receiver = None;
+                self.normalize_message = Some(match result {
+                    Ok(message) => (true, message),
+                    Err(message) => (false, message),
+                });
+            }
+        }
+    }
+
+    fn normalize_ruts(&mut self) {
+        self.normalizing = true;
+        self.normalize_message = None;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.normalize_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let mut db = db_manager.lock().await;
+            let result = db.normalize_all_ruts().await
+                .map(|updated| format!("{} RUT normalizados", updated))
+                .map_err(|e| format!("Error al normalizar RUT: {}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    fn check_email_repair_result(&mut self) {
+        if let Some(receiver) = &mut self.email_repair_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.repairing_email = false;
+                self.email_repair_receiver = None;
+                self.email_repair_message = Some(match result {
+                    Ok(message) => (true, message),
+                    Err(message) => (false, message),
+                });
+            }
+        }
+    }
+
+    fn repair_email_constraints(&mut self) {
+        self.repairing_email = true;
+        self.email_repair_message = None;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.email_repair_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let mut db = db_manager.lock().await;
+            let result = db.repair_email_constraints().await
+                .map(|_| "Constraints de email restaurados".to_string())
+                .map_err(|e| format!("Error al restaurar constraints de email: {}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    fn check_integrity_result(&mut self) {
+        if let Some(receiver) = &mut self.integrity_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.checking_integrity = false;
+                self.integrity_receiver = None;
+                match result {
+                    Ok(findings) => self.integrity_findings = findings,
+                    Err(error_msg) => self.integrity_error = Some(error_msg),
+                }
+            }
+        }
+    }
+
+    fn run_integrity_report(&mut self) {
+        self.checking_integrity = true;
+        self.integrity_error = None;
+        self.expanded_finding = None;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.integrity_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = db.integrity_report().await
+                .map_err(|e| format!("Error al revisar integridad: {}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    fn check_connection_result(&mut self) {
+        if let Some(receiver) = &mut self.connection_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.loading_connection = false;
+                self.connection_receiver = None;
+                match result {
+                    Ok(info) => self.connection_info = Some(info),
+                    Err(error_msg) => self.connection_error = Some(error_msg),
+                }
+            }
+        }
+    }
+
+    fn load_connection_info(&mut self) {
+        self.loading_connection = true;
+        self.connection_error = None;
+        self.dsn_copied = false;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.connection_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = match db.connection_config() {
+                Some(config) => async {
+                    let server_version = db.server_version().await?;
+                    let schema_version = db.schema_version().await?;
+                    Ok::<_, anyhow::Error>(ConnectionInfo { config, server_version, schema_version })
+                }.await.map_err(|e| format!("Error al consultar los datos de la conexión: {}", e)),
+                None => Err("No hay conexión a la base de datos".to_string()),
+            };
+            let _ = tx.send(result);
+        });
+    }
+
+    fn show_connection_details(&mut self, ui: &mut egui::Ui) {
+        // Se consulta al abrir la sección por primera vez
+        if self.connection_info.is_none() && self.connection_error.is_none() && !self.loading_connection {
+            self.load_connection_info();
+        }
+
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!self.loading_connection, egui::Button::new("🔄 Actualizar")).clicked() {
+                self.load_connection_info();
+            }
+            if self.loading_connection {
+                ui.add(egui::widgets::Spinner::new().size(16.0));
+            }
+        });
+        if let Some(error) = &self.connection_error {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+
+        let Some(info) = &self.connection_info else {
+            return;
+        };
+        let config = &info.config;
+        ui.add_space(5.0);
+        egui::Grid::new("connection_grid")
+            .num_columns(2)
+            .spacing([15.0, 8.0])
+            .show(ui, |ui| {
+                ui.strong("Servidor:");
+                ui.label(&config.host);
+                ui.end_row();
+
+                ui.strong("Puerto:");
+                ui.label(config.port.to_string());
+                ui.end_row();
+
+                ui.strong("Base de datos:");
+                ui.label(&config.database);
+                ui.end_row();
+
+                ui.strong("Usuario:");
+                ui.label(&config.username);
+                ui.end_row();
+
+                ui.strong("Versión del servidor:");
+                ui.label(&info.server_version);
+                ui.end_row();
+
+                ui.strong("Versión del esquema:");
+                let latest = migrations::latest_version();
+                match info.schema_version {
+                    Some(version) if version >= latest => ui.label(version.to_string()),
+                    Some(version) => ui.colored_label(
+                        egui::Color32::from_rgb(255, 165, 0),
+                        format!("{} (pendiente hasta {})", version, latest),
+                    ),
+                    None => ui.label("sin migraciones"),
+                };
+                ui.end_row();
+            });
+
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            if ui.button("📋 Copiar cadena de conexión")
+                .on_hover_text(config.sanitized_dsn())
+                .clicked()
+            {
+                ui.output_mut(|o| o.copied_text = config.sanitized_dsn());
+                self.dsn_copied = true;
+            }
+            if self.dsn_copied {
+                ui.colored_label(egui::Color32::GREEN, "Copiada (contraseña enmascarada)");
+            }
+        });
+    }
+
+    fn show_integrity_report(&mut self, ui: &mut egui::Ui) {
+        ui.label("Cuenta los registros que no cumplen las reglas de validación actuales.");
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!self.checking_integrity, egui::Button::new("🩺 Revisar integridad")).clicked() {
+                self.run_integrity_report();
+            }
+            if self.checking_integrity {
+                ui.add(egui::widgets::Spinner::new().size(16.0));
+            }
+        });
+        if let Some(error) = &self.integrity_error {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+
+        if self.integrity_findings.is_empty() {
+            return;
+        }
+        ui.add_space(5.0);
+        egui::Grid::new("integrity_grid")
+            .num_columns(3)
+            .spacing([15.0, 8.0])
+            .show(ui, |ui| {
+                for (index, finding) in self.integrity_findings.iter().enumerate() {
+                    ui.label(&finding.descripcion);
+                    let count = finding.detalles.len();
+                    let color = if count == 0 { egui::Color32::GREEN } else { egui::Color32::from_rgb(255, 165, 0) };
+                    ui.colored_label(color, count.to_string());
+                    if count > 0 {
+                        let expanded = self.expanded_finding == Some(index);
+                        let label = if expanded { "ocultar detalles" } else { "ver detalles" };
+                        if ui.small_button(label).clicked() {
+                            self.expanded_finding = if expanded { None } else { Some(index) };
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+
+        if let Some(finding) = self.expanded_finding.and_then(|index| self.integrity_findings.get(index)) {
+            ui.add_space(5.0);
+            ui.strong(&finding.descripcion);
+            egui::ScrollArea::vertical()
+                .id_source("integrity_details")
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    for detalle in &finding.detalles {
+                        ui.label(detalle);
+                    }
+                });
         }
     }
 
     pub fn show(&mut self, ui: &mut egui::Ui) {
+        self.check_normalize_result();
+        self.check_email_repair_result();
+        self.check_integrity_result();
+        self.check_connection_result();
+
         ui.heading("ⓘ Acerca de la Aplicación");
         ui.add_space(20.0);
 
@@ -53,6 +370,9 @@ impl AboutView {
                         " Registro de personas mayores y organizaciones",
                         " Seguimiento de actividades y eventos",
                         " Administración de viajes comunitarios",
+                        " Búsqueda global en personas, organizaciones y actividades",
+                        " Consola SQL de solo lectura para consultas ad-hoc",
+                        " Atajos: Ctrl+1..4 cambian de vista, Ctrl+S guarda el formulario, Ctrl+F busca, Ctrl+G va a una persona por RUT",
                         " Interfaz moderna y responsiva",
                         " Rendimiento optimizado para grandes volúmenes de datos",
                     ];
@@ -132,6 +452,69 @@ impl AboutView {
 
             ui.add_space(20.0);
 
+            // Conexión activa, para adjuntar en solicitudes de soporte
+            egui::CollapsingHeader::new("Conexión")
+                .default_open(false)
+                .show(ui, |ui| {
+                    self.show_connection_details(ui);
+                });
+
+            ui.add_space(20.0);
+
+            // Tareas de mantenimiento de datos
+            egui::CollapsingHeader::new("Mantenimiento")
+                .default_open(false)
+                .show(ui, |ui| {
+                    ui.label("Reescribe los RUT existentes al formato 12345678-9 (sin puntos ni espacios, verificador en mayúscula).");
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(!self.normalizing, egui::Button::new("🧹 Normalizar RUTs")).clicked() {
+                            self.normalize_ruts();
+                        }
+                        if self.normalizing {
+                            ui.add(egui::widgets::Spinner::new().size(16.0));
+                        }
+                    });
+                    if let Some((success, message)) = &self.normalize_message {
+                        let color = if *success { egui::Color32::GREEN } else { egui::Color32::RED };
+                        ui.colored_label(color, message);
+                    }
+
+                    ui.add_space(10.0);
+                    ui.label("Restablece los constraints de formato de email de personas y organizaciones con la misma regla que validan los formularios.");
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(!self.repairing_email, egui::Button::new("🛠 Restaurar constraints de email")).clicked() {
+                            self.confirm_email_repair.open(
+                                "Restaurar constraints de email",
+                                "Se modificará el esquema de per_personasmayores y org_orgcomunitarias. Si hay emails registrados con formato inválido la operación fallará sin cambios. ¿Continuar?",
+                            );
+                        }
+                        if self.repairing_email {
+                            ui.add(egui::widgets::Spinner::new().size(16.0));
+                        }
+                    });
+                    if let Some((success, message)) = &self.email_repair_message {
+                        let color = if *success { egui::Color32::GREEN } else { egui::Color32::RED };
+                        ui.colored_label(color, message);
+                    }
+                });
+
+            if self.confirm_email_repair.show(ui) == Some(true) {
+                self.repair_email_constraints();
+            }
+
+            ui.add_space(20.0);
+
+            // Reporte de calidad de datos
+            egui::CollapsingHeader::new("Integridad de datos")
+                .default_open(false)
+                .show(ui, |ui| {
+                    self.show_integrity_report(ui);
+                });
+
+            ui.add_space(20.0);
+
             // Información de contacto o desarrollo
             egui::Frame::none()
                 .fill(egui::Color32::from_gray(20))
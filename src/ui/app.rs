@@ -1,10 +1,21 @@
 use eframe::egui;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::{Mutex, mpsc};
 use crate::database::DatabaseManager;
 use crate::models::DatabaseConfig;
-use crate::ui::theme::AppleMusicStyle;
-use super::{login::LoginView, dashboard::DashboardView, sidebar::Sidebar, queries::QueriesView, insertions::InsertionsView, about::AboutView};
+use crate::ui::catalogs::{Catalogs, SharedCatalogs};
+use crate::ui::components::{self, ConfirmDialog};
+use crate::ui::detail::SelectedRecord;
+use crate::ui::rut_jump::RutJumpDialog;
+use crate::ui::toasts::{SharedToasts, Toasts};
+use crate::config::AppConfig;
+use crate::ui::theme::{AppleMusicStyle, UiDensity};
+use super::setup_wizard::{SetupWizard, WizardOutcome};
+use super::{login::LoginView, dashboard::DashboardView, sidebar::Sidebar, status_bar::StatusBar, queries::QueriesView, insertions::InsertionsView, about::AboutView, sql_console::SqlConsoleView, search::SearchView, catalog_admin::CatalogAdminView};
+
+// Tiempo máximo que se espera al cerrar la ventana a que terminen las operaciones en curso
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum AppState {
@@ -12,136 +23,382 @@ pub enum AppState {
     Dashboard,
     Queries,
     Insertions,
+    CatalogAdmin,
+    Search,
+    SqlConsole,
     About,
 }
 
+// Resultado de un intento de conexión exitoso a nivel de red
+enum ConnectionOutcome {
+    Connected(String),
+    // La base de datos no tiene el esquema comunitario (tablas faltantes)
+    MissingTables(Vec<String>),
+}
+
 pub struct App {
     pub state: AppState,
     pub db_manager: Arc<Mutex<DatabaseManager>>,
+    // Runtime donde corren las tareas de la app; se usa para esperar el cierre en on_exit
+    runtime: tokio::runtime::Handle,
+    catalogs: SharedCatalogs,
     
     // Views
     login_view: LoginView,
+    // Asistente de primera ejecución; reemplaza al login mientras está abierto
+    setup_wizard: Option<SetupWizard>,
     dashboard_view: DashboardView,
     sidebar: Sidebar,
+    status_bar: StatusBar,
     queries_view: QueriesView,
     insertions_view: InsertionsView,
+    catalog_admin_view: CatalogAdminView,
+    search_view: SearchView,
+    sql_console_view: SqlConsoleView,
     about_view: AboutView,
+    rut_jump: RutJumpDialog,
     
     // App state
+    density: UiDensity,
     is_connected: bool,
-    error_message: Option<String>,
-    success_message: Option<String>,
+    // Avisos de resultado (conexión, inserciones, exportaciones), compartidos con las vistas
+    toasts: SharedToasts,
     
     // Async connection handling
-    connection_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+    connection_receiver: Option<mpsc::UnboundedReceiver<Result<ConnectionOutcome, String>>>,
+    test_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+
+    // Tablas faltantes detectadas al conectar y creación del esquema
+    missing_tables: Vec<String>,
+    creating_schema: bool,
+    schema_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+
+    // Keep-alive de la conexión y reconexión tras perderla
+    keepalive: Option<tokio::task::JoinHandle<()>>,
+    keepalive_receiver: Option<mpsc::UnboundedReceiver<String>>,
+    connection_lost: bool,
+    reconnect_receiver: Option<mpsc::UnboundedReceiver<Result<(), String>>>,
+
+    // Desconexión pedida desde la barra lateral
+    confirm_disconnect: ConfirmDialog,
+    disconnecting: bool,
+    disconnect_receiver: Option<mpsc::UnboundedReceiver<()>>,
 }
 
 impl App {
     pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
         let db_manager = Arc::new(Mutex::new(DatabaseManager::new()));
+        let catalogs = Catalogs::new_shared();
+        let toasts = Toasts::new_shared();
         
         Self {
             state: AppState::Login,
             db_manager: db_manager.clone(),
+            runtime: tokio::runtime::Handle::current(),
+            catalogs: catalogs.clone(),
             login_view: LoginView::new(),
+            setup_wizard: (!AppConfig::exists()).then(|| SetupWizard::new(db_manager.clone())),
             dashboard_view: DashboardView::new(db_manager.clone()),
             sidebar: Sidebar::new(),
-            queries_view: QueriesView::new(db_manager.clone()),
-            insertions_view: InsertionsView::new(db_manager.clone()),
-            about_view: AboutView::new(),
+            status_bar: StatusBar::new(db_manager.clone()),
+            queries_view: QueriesView::new(db_manager.clone(), catalogs.clone(), toasts.clone()),
+            insertions_view: InsertionsView::new(db_manager.clone(), catalogs.clone()),
+            catalog_admin_view: CatalogAdminView::new(db_manager.clone(), catalogs),
+            search_view: SearchView::new(db_manager.clone()),
+            sql_console_view: SqlConsoleView::new(db_manager.clone()),
+            about_view: AboutView::new(db_manager.clone()),
+            rut_jump: RutJumpDialog::new(db_manager.clone()),
+            density: AppConfig::load().density,
             is_connected: false,
-            error_message: None,
-            success_message: None,
+            toasts,
             connection_receiver: None,
+            test_receiver: None,
+            missing_tables: Vec::new(),
+            creating_schema: false,
+            schema_receiver: None,
+            keepalive: None,
+            keepalive_receiver: None,
+            connection_lost: false,
+            reconnect_receiver: None,
+            confirm_disconnect: ConfirmDialog::new(),
+            disconnecting: false,
+            disconnect_receiver: None,
         }
     }
 
     pub fn set_state(&mut self, state: AppState) {
         self.state = state;
-        self.clear_messages();
     }
 
     pub fn set_connected(&mut self, connected: bool) {
         self.is_connected = connected;
+        self.connection_lost = false;
+        if connected {
+            // Los errores de intentos de conexión anteriores ya no aplican
+            self.toasts.lock().unwrap().dismiss_errors();
+            self.start_keepalive();
+        } else {
+            self.stop_keepalive();
+        }
         if connected && self.state == AppState::Login {
             self.set_state(AppState::Dashboard);
-            // Inicializar datos en queries_view una vez conectado
+            // Cargar catálogos compartidos e inicializar datos en queries_view una vez conectado
+            Catalogs::refresh(&self.catalogs, &self.db_manager);
+            self.dashboard_view.initialize_data();
             self.queries_view.initialize_data();
+            self.status_bar.refresh();
         } else if !connected {
             self.set_state(AppState::Login);
+            self.status_bar.clear();
         }
     }
 
-    pub fn set_error(&mut self, message: String) {
-        self.error_message = Some(message);
-        self.success_message = None;
+    fn start_keepalive(&mut self) {
+        self.stop_keepalive();
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.keepalive_receiver = Some(rx);
+        let interval = AppConfig::load().keepalive_interval();
+        self.keepalive = Some(DatabaseManager::spawn_keepalive(self.db_manager.clone(), interval, tx));
     }
 
-    pub fn set_success(&mut self, message: String) {
-        self.success_message = Some(message);
-        self.error_message = None;
+    fn stop_keepalive(&mut self) {
+        if let Some(handle) = self.keepalive.take() {
+            handle.abort();
+        }
+        self.keepalive_receiver = None;
     }
 
-    pub fn clear_messages(&mut self) {
-        self.error_message = None;
-        self.success_message = None;
+    // El keep-alive detectó la conexión caída: se muestra desconectado y la próxima
+    // acción del usuario intenta reconectar
+    fn check_keepalive_result(&mut self) -> bool {
+        let Some(receiver) = &mut self.keepalive_receiver else {
+            return false;
+        };
+        let Ok(error) = receiver.try_recv() else {
+            return false;
+        };
+        self.stop_keepalive();
+        self.is_connected = false;
+        self.connection_lost = true;
+        self.set_error(format!("Se perdió la conexión con la base de datos ({}). Se reconectará con la próxima acción.", error));
+        true
+    }
+
+    fn start_reconnect(&mut self) {
+        log::info!("Reconectando a la base de datos");
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.reconnect_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let mut db = db_manager.lock().await;
+            let result = db.reconnect().await
+                .map_err(|e| format!("No se pudo reconectar: {:#}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    fn check_reconnect_result(&mut self) -> bool {
+        let Some(receiver) = &mut self.reconnect_receiver else {
+            return false;
+        };
+        let Ok(result) = receiver.try_recv() else {
+            return false;
+        };
+        self.reconnect_receiver = None;
+        match result {
+            Ok(()) => {
+                self.set_connected(true);
+                self.status_bar.refresh();
+                // Lo cargado mientras la conexión estaba caída quedó vacío o incompleto
+                self.dashboard_view.initialize_data();
+                Catalogs::refresh(&self.catalogs, &self.db_manager);
+                self.set_success("Conexión restablecida".to_string());
+            }
+            // Sigue perdida: la siguiente acción vuelve a intentarlo
+            Err(error_msg) => self.set_error(error_msg),
+        }
+        true
+    }
+
+    // Desconexión desde la barra lateral; si hay formularios con datos pide confirmación
+    fn request_disconnect(&mut self) {
+        if self.disconnecting {
+            return;
+        }
+        if self.insertions_view.has_unsaved_data() {
+            self.confirm_disconnect.open(
+                "Desconectar",
+                "Hay formularios con datos sin guardar que se perderán. ¿Desconectar de todos modos?",
+            );
+        } else {
+            self.start_disconnect();
+        }
+    }
+
+    // Cierra el cliente en segundo plano; al terminar se vuelve al login (check_disconnect_result)
+    fn start_disconnect(&mut self) {
+        self.stop_keepalive();
+        self.disconnecting = true;
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.disconnect_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let mut db = db_manager.lock().await;
+            db.disconnect().await;
+            let _ = tx.send(());
+        });
+    }
+
+    fn check_disconnect_result(&mut self) -> bool {
+        let Some(receiver) = &mut self.disconnect_receiver else {
+            return false;
+        };
+        if receiver.try_recv().is_err() {
+            return false;
+        }
+        self.disconnecting = false;
+        self.disconnect_receiver = None;
+        self.reset_views();
+        self.set_connected(false);
+        log::info!("Desconectado de la base de datos");
+        true
+    }
+
+    // Descarta resultados, formularios y catálogos de la conexión anterior recreando las
+    // vistas; los canales de tareas aún en curso se cierran junto con ellas
+    fn reset_views(&mut self) {
+        Catalogs::clear(&self.catalogs);
+        let db_manager = &self.db_manager;
+        self.dashboard_view = DashboardView::new(db_manager.clone());
+        self.queries_view = QueriesView::new(db_manager.clone(), self.catalogs.clone(), self.toasts.clone());
+        self.insertions_view = InsertionsView::new(db_manager.clone(), self.catalogs.clone());
+        self.catalog_admin_view = CatalogAdminView::new(db_manager.clone(), self.catalogs.clone());
+        self.search_view = SearchView::new(db_manager.clone());
+        self.sql_console_view = SqlConsoleView::new(db_manager.clone());
+        self.about_view = AboutView::new(db_manager.clone());
+        self.rut_jump = RutJumpDialog::new(db_manager.clone());
+    }
+
+    fn finish_setup_wizard(&mut self, outcome: WizardOutcome) {
+        self.setup_wizard = None;
+        match outcome {
+            // Guardar la configuración por defecto para no volver a mostrar el asistente
+            WizardOutcome::Skipped => AppConfig::update(|_| {}),
+            WizardOutcome::Finished(profile_name, config) => {
+                self.login_view.config = config;
+                self.login_view.save_profile_as(&profile_name);
+                self.login_view.on_connected();
+                self.set_connected(true);
+                self.set_success("Configuración inicial completada".to_string());
+            }
+        }
+    }
+
+    pub fn set_error(&mut self, message: String) {
+        self.toasts.lock().unwrap().error(message);
+    }
+
+    pub fn set_success(&mut self, message: String) {
+        self.toasts.lock().unwrap().success(message);
     }
 
     pub fn start_connection(&mut self, config: DatabaseConfig) {
-        println!("DEBUG: Iniciando conexión con config: host={}, port={}, database={}, username={}", 
-                 config.host, config.port, config.database, config.username);
-        
+        log::info!("Conectando a {}", config.sanitized_dsn());
+
         let (tx, rx) = mpsc::unbounded_channel();
         self.connection_receiver = Some(rx);
         
         let db_manager = self.db_manager.clone();
         tokio::spawn(async move {
-            println!("DEBUG: Intentando conectar a la base de datos...");
             let mut manager = db_manager.lock().await;
             let result = manager.connect(&config).await;
             
             match result {
+                Ok(missing) if !missing.is_empty() => {
+                    let _ = tx.send(Ok(ConnectionOutcome::MissingTables(missing)));
+                }
                 Ok(_) => {
-                    println!("DEBUG: Conexión exitosa, probando conexión...");
                     // Test the connection
                     match manager.test_connection().await {
                         Ok(true) => {
-                            println!("DEBUG: Test de conexión exitoso");
-                            let _ = tx.send(Ok("Conexión establecida exitosamente".to_string()));
+                            let _ = tx.send(Ok(ConnectionOutcome::Connected("Conexión establecida exitosamente".to_string())));
                         }
                         Ok(false) => {
-                            println!("DEBUG: Test de conexión falló");
+                            log::warn!("La consulta de prueba no devolvió resultados");
                             let _ = tx.send(Err("Error al probar la conexión".to_string()));
                         }
                         Err(e) => {
-                            println!("DEBUG: Error en test de conexión: {}", e);
+                            log::warn!("Error en test de conexión: {}", e);
                             let _ = tx.send(Err(format!("Error en test de conexión: {}", e)));
                         }
                     }
                 }
                 Err(e) => {
-                    println!("DEBUG: Error de conexión: {}", e);
+                    log::warn!("Error de conexión: {}", e);
                     let _ = tx.send(Err(format!("Error de conexión: {}", e)));
                 }
             }
         });
     }
 
+    // Prueba las credenciales del formulario sin cambiar de vista
+    pub fn start_connection_test(&mut self, config: DatabaseConfig) {
+        self.login_view.testing = true;
+        self.login_view.test_message = None;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.test_receiver = Some(rx);
+
+        tokio::spawn(async move {
+            let result = match DatabaseManager::probe_connection(&config).await {
+                Ok(true) => Ok("Conexión exitosa".to_string()),
+                Ok(false) => Err("Error al probar la conexión".to_string()),
+                Err(e) => Err(format!("Error de conexión: {:#}", e)),
+            };
+            let _ = tx.send(result);
+        });
+    }
+
+    pub fn check_connection_test_result(&mut self) -> bool {
+        if let Some(receiver) = &mut self.test_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.login_view.testing = false;
+                self.login_view.test_message = Some(match result {
+                    Ok(message) => (true, message),
+                    Err(message) => (false, message),
+                });
+                self.test_receiver = None;
+                return true;
+            }
+        }
+        false
+    }
+
     pub fn check_connection_result(&mut self) -> bool {
         if let Some(receiver) = &mut self.connection_receiver {
             if let Ok(result) = receiver.try_recv() {
-                println!("DEBUG: Recibido resultado de conexión");
                 self.login_view.connecting = false;
+                self.missing_tables.clear();
                 match result {
-                    Ok(success_msg) => {
-                        println!("DEBUG: Conexión exitosa: {}", success_msg);
+                    Ok(ConnectionOutcome::Connected(success_msg)) => {
+                        log::info!("{}", success_msg);
+                        self.login_view.on_connected();
                         self.set_connected(true);
                         self.set_success(success_msg);
                         self.connection_receiver = None;
                         return true;
                     }
+                    Ok(ConnectionOutcome::MissingTables(missing)) => {
+                        self.set_error(format!(
+                            "La base de datos no tiene el esquema de la aplicación. Faltan las tablas: {}",
+                            missing.join(", ")
+                        ));
+                        self.missing_tables = missing;
+                        self.connection_receiver = None;
+                    }
                     Err(error_msg) => {
-                        println!("DEBUG: Error de conexión: {}", error_msg);
                         self.set_error(error_msg);
                         self.connection_receiver = None;
                     }
@@ -151,28 +408,175 @@ impl App {
         false
     }
 
-    fn show_messages(&mut self, ui: &mut egui::Ui) {
-        if let Some(ref error) = self.error_message.clone() {
-            ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+    // Crea el esquema en la base de datos recién conectada que no lo tenía
+    fn start_schema_creation(&mut self) {
+        self.creating_schema = true;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.schema_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let mut db = db_manager.lock().await;
+            let result = db.create_schema().await
+                .map(|_| "Esquema creado, conexión establecida".to_string())
+                .map_err(|e| format!("Error al crear el esquema: {:#}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    fn check_schema_result(&mut self) -> bool {
+        if let Some(receiver) = &mut self.schema_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.creating_schema = false;
+                self.schema_receiver = None;
+                match result {
+                    Ok(success_msg) => {
+                        self.missing_tables.clear();
+                        self.login_view.on_connected();
+                        self.set_connected(true);
+                        self.set_success(success_msg);
+                    }
+                    Err(error_msg) => self.set_error(error_msg),
+                }
+                return true;
+            }
         }
-        
-        if let Some(ref success) = self.success_message.clone() {
-            ui.colored_label(egui::Color32::GREEN, format!("✅ {}", success));
+        false
+    }
+
+    // Selector de perfiles de conexión guardados, con "Guardar como..." y "Eliminar perfil"
+    fn show_profile_selector(&mut self, ui: &mut egui::Ui) {
+        let login = &mut self.login_view;
+        ui.horizontal(|ui| {
+            ui.add(egui::Label::new(AppleMusicStyle::secondary_text("Perfil:")));
+            let mut selected = login.selected_profile.clone();
+            egui::ComboBox::from_id_source("login_profile")
+                .selected_text(selected.clone().unwrap_or_else(|| "(sin perfil)".to_string()))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut selected, None, "(sin perfil)");
+                    for (name, _) in &login.profiles {
+                        ui.selectable_value(&mut selected, Some(name.clone()), name);
+                    }
+                });
+            if selected != login.selected_profile {
+                login.select_profile(selected);
+            }
+
+            if ui.small_button("Guardar como...").clicked() {
+                login.new_profile_name = Some(login.selected_profile.clone().unwrap_or_default());
+            }
+            if ui.add_enabled(login.selected_profile.is_some(), egui::Button::new("Eliminar perfil").small()).clicked() {
+                login.delete_selected_profile();
+            }
+        });
+
+        if let Some(mut name) = login.new_profile_name.take() {
+            let mut keep_open = true;
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut name).hint_text("Nombre del perfil").desired_width(180.0));
+                if ui.add_enabled(!name.trim().is_empty(), egui::Button::new("Guardar").small()).clicked() {
+                    login.save_profile_as(&name);
+                    keep_open = false;
+                }
+                if ui.small_button("Cancelar").clicked() {
+                    keep_open = false;
+                }
+            });
+            if keep_open {
+                login.new_profile_name = Some(name);
+            }
+        }
+    }
+
+    // Atajos globales: Ctrl+1..4 cambian de vista, Ctrl+S guarda el formulario activo
+    // y Ctrl+F enfoca el primer filtro de Consultas
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        // No interceptar mientras el usuario escribe caracteres en un campo de texto
+        let typing = ctx.wants_keyboard_input()
+            && ctx.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Text(_))));
+        if typing {
+            return;
+        }
+
+        let views = [
+            (egui::Key::Num1, AppState::Dashboard),
+            (egui::Key::Num2, AppState::Queries),
+            (egui::Key::Num3, AppState::Insertions),
+            (egui::Key::Num4, AppState::About),
+        ];
+        for (key, state) in views {
+            if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, key)) {
+                self.set_state(state);
+            }
+        }
+
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::S))
+            && self.state == AppState::Insertions
+        {
+            self.insertions_view.save_current_form();
+        }
+
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::F))
+            && self.state == AppState::Queries
+        {
+            self.queries_view.focus_first_filter();
+        }
+
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::COMMAND, egui::Key::G)) && self.is_connected {
+            self.rut_jump.open();
         }
     }
 }
 
 impl eframe::App for App {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Un perfil importado puede traer otra densidad de la interfaz
+        let profile_changed = self.login_view.check_profile_results();
+        if profile_changed {
+            self.density = AppConfig::load().density;
+        }
+
         // Check for async connection results
-        if self.check_connection_result() {
+        if self.check_connection_result()
+            || self.check_schema_result()
+            || self.check_connection_test_result()
+            || profile_changed
+            || self.check_disconnect_result()
+            || self.check_keepalive_result()
+            || self.check_reconnect_result()
+            // Nada se anima mientras se desconecta o reconecta; seguir redibujando hasta
+            // recibir el resultado
+            || self.disconnecting
+            || self.reconnect_receiver.is_some()
+        {
             ctx.request_repaint();
         }
         
         // Aplicar el tema Apple Music con colores azules
-        AppleMusicStyle::apply_style(ctx);
+        AppleMusicStyle::apply_style(ctx, self.density);
+
+        if self.state != AppState::Login {
+            // Con la conexión perdida, cualquier clic o tecla intenta reconectar
+            let user_action = ctx.input(|i| i.pointer.any_pressed() || i.events.iter().any(|e| matches!(e, egui::Event::Key { pressed: true, .. })));
+            if self.connection_lost && self.reconnect_receiver.is_none() && user_action {
+                self.start_reconnect();
+            }
+            self.handle_shortcuts(ctx);
+        }
 
         match self.state {
+            AppState::Login if self.setup_wizard.is_some() => {
+                let mut outcome = None;
+                egui::CentralPanel::default().show(ctx, |ui| {
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        outcome = self.setup_wizard.as_mut().and_then(|wizard| wizard.show(ui));
+                    });
+                });
+                if let Some(outcome) = outcome {
+                    self.finish_setup_wizard(outcome);
+                }
+            }
             AppState::Login => {
                 egui::CentralPanel::default().show(ctx, |ui| {
                     ui.with_layout(
@@ -187,49 +591,77 @@ impl eframe::App for App {
                                 ui.add(egui::Label::new(AppleMusicStyle::secondary_text("Ingrese las credenciales de conexión a la base de datos")));
                                 ui.add_space(20.0);
 
-                                // Mostrar mensajes de error/éxito en login
-                                if let Some(ref error) = self.error_message {
-                                    ui.colored_label(egui::Color32::RED, format!("Error: {}", error));
-                                    ui.add_space(10.0);
-                                }
-                                if let Some(ref success) = self.success_message {
-                                    ui.colored_label(AppleMusicStyle::PRIMARY_BLUE, format!("Éxito: {}", success));
+                                // Base de datos sin el esquema comunitario: ofrecer crearlo
+                                if !self.missing_tables.is_empty() {
+                                    ui.horizontal(|ui| {
+                                        let button = egui::Button::new("🛠 Crear esquema");
+                                        if ui.add_enabled(!self.creating_schema, button)
+                                            .on_hover_text("Crea todas las tablas de la aplicación. Use una base de datos vacía: si alguna tabla ya existe no se aplica ningún cambio.")
+                                            .clicked()
+                                        {
+                                            self.start_schema_creation();
+                                        }
+                                        if self.creating_schema {
+                                            ui.add(egui::widgets::Spinner::new().size(16.0));
+                                        }
+                                    });
                                     ui.add_space(10.0);
                                 }
-
                                 ui.add_space(30.0);
 
                                 // Formulario de login con estilo Apple Music
                                 AppleMusicStyle::card_frame()
                                     .show(ui, |ui| {
                                         ui.set_max_width(400.0);
+
+                                        self.show_profile_selector(ui);
+                                        ui.add_space(16.0);
                                         
-                                        // Campos de configuración de base de datos
+                                        // Campos de configuración de base de datos.
+                                        // Enter en cualquiera de ellos equivale a presionar Conectar.
+                                        let mut field_responses = Vec::new();
                                         egui::Grid::new("login_grid")
                                             .num_columns(2)
                                             .spacing([16.0, 20.0])
                                             .show(ui, |ui| {
                                                 ui.add(egui::Label::new(AppleMusicStyle::secondary_text("Host:")));
-                                                ui.text_edit_singleline(&mut self.login_view.config.host);
+                                                field_responses.push(ui.text_edit_singleline(&mut self.login_view.config.host));
                                                 ui.end_row();
 
                                                 ui.add(egui::Label::new(AppleMusicStyle::secondary_text("Puerto:")));
-                                                ui.add(egui::DragValue::new(&mut self.login_view.config.port).range(1..=65535));
+                                                field_responses.push(components::port_field(ui, &mut self.login_view.config));
                                                 ui.end_row();
 
                                                 ui.add(egui::Label::new(AppleMusicStyle::secondary_text("Base de Datos:")));
-                                                ui.text_edit_singleline(&mut self.login_view.config.database);
+                                                field_responses.push(ui.text_edit_singleline(&mut self.login_view.config.database));
                                                 ui.end_row();
 
                                                 ui.add(egui::Label::new(AppleMusicStyle::secondary_text("Usuario:")));
-                                                ui.text_edit_singleline(&mut self.login_view.config.username);
+                                                field_responses.push(ui.text_edit_singleline(&mut self.login_view.config.username));
                                                 ui.end_row();
 
                                                 ui.add(egui::Label::new(AppleMusicStyle::secondary_text("Contraseña:")));
-                                                ui.add(egui::TextEdit::singleline(&mut self.login_view.config.password).password(true));
+                                                ui.horizontal(|ui| {
+                                                    field_responses.push(ui.add(
+                                                        egui::TextEdit::singleline(&mut self.login_view.config.password)
+                                                            .password(!self.login_view.show_password)
+                                                    ));
+                                                    let (icon, tooltip) = if self.login_view.show_password {
+                                                        ("🙈", "Ocultar contraseña")
+                                                    } else {
+                                                        ("👁", "Mostrar contraseña")
+                                                    };
+                                                    if ui.small_button(icon).on_hover_text(tooltip).clicked() {
+                                                        self.login_view.show_password = !self.login_view.show_password;
+                                                    }
+                                                });
                                                 ui.end_row();
                                             });
 
+                                        // Un campo de una línea pierde el foco al presionar Enter
+                                        let enter_pressed = field_responses.iter().any(|response| response.lost_focus())
+                                            && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
                                         ui.add_space(24.0);
 
                                         ui.horizontal(|ui| {
@@ -241,23 +673,72 @@ impl eframe::App for App {
                                                     .stroke(egui::Stroke::NONE)
                                                     .min_size(egui::vec2(140.0, 44.0));
                                                 
-                                                if ui.add_enabled(!self.login_view.connecting, button).clicked() {
-                                                    println!("DEBUG: Botón conectar presionado");
-                                                    if !self.login_view.config.host.is_empty() && 
-                                                       !self.login_view.config.username.is_empty() && 
-                                                       !self.login_view.config.database.is_empty() {
-                                                        println!("DEBUG: Validación pasó, iniciando conexión");
+                                                let clicked = ui.add_enabled(!self.login_view.connecting, button).clicked();
+                                                if clicked || (enter_pressed && !self.login_view.connecting) {
+                                                    if let Some(error) = self.login_view.config.validation_error() {
+                                                        self.set_error(error.to_string());
+                                                    } else {
                                                         self.login_view.connecting = true;
                                                         let config = self.login_view.config.clone();
                                                         self.start_connection(config);
                                                         ctx.request_repaint();
-                                                    } else {
-                                                        println!("DEBUG: Validación falló - campos vacíos");
-                                                        self.set_error("Por favor complete todos los campos requeridos".to_string());
                                                     }
                                                 }
                                             });
                                         });
+
+                                        ui.add_space(12.0);
+
+                                        ui.vertical_centered(|ui| {
+                                            let test_text = if self.login_view.testing { "Probando..." } else { "Probar conexión" };
+                                            let test_button = egui::Button::new(
+                                                egui::RichText::new(test_text).color(AppleMusicStyle::TEXT_SECONDARY)
+                                            )
+                                            .fill(egui::Color32::TRANSPARENT)
+                                            .rounding(egui::Rounding::same(20.0))
+                                            .stroke(egui::Stroke::new(1.0, AppleMusicStyle::TEXT_SECONDARY))
+                                            .min_size(egui::vec2(140.0, 36.0));
+
+                                            let busy = self.login_view.testing || self.login_view.connecting;
+                                            if ui.add_enabled(!busy, test_button).clicked() {
+                                                if let Some(error) = self.login_view.config.validation_error() {
+                                                    self.login_view.test_message = Some((false, error.to_string()));
+                                                } else {
+                                                    let config = self.login_view.config.clone();
+                                                    self.start_connection_test(config);
+                                                    ctx.request_repaint();
+                                                }
+                                            }
+
+                                            if let Some((success, message)) = &self.login_view.test_message {
+                                                ui.add_space(8.0);
+                                                let color = if *success { egui::Color32::GREEN } else { egui::Color32::RED };
+                                                ui.colored_label(color, message);
+                                            }
+                                        });
+
+                                        ui.add_space(16.0);
+                                        ui.separator();
+                                        ui.add_space(8.0);
+
+                                        // Perfil de conexión portable entre equipos
+                                        ui.horizontal(|ui| {
+                                            let busy = self.login_view.profile_busy();
+                                            if ui.add_enabled(!busy, egui::Button::new("📤 Exportar perfil")).clicked() {
+                                                self.login_view.export_profile();
+                                            }
+                                            if ui.add_enabled(!busy, egui::Button::new("📥 Importar perfil")).clicked() {
+                                                self.login_view.import_profile();
+                                            }
+                                        });
+                                        ui.checkbox(&mut self.login_view.include_password, "Incluir contraseña al exportar");
+                                        ui.checkbox(&mut self.login_view.include_search_history, "Incluir historial de búsqueda al exportar")
+                                            .on_hover_text("El historial contiene RUT y nombres buscados");
+
+                                        if let Some((success, message)) = &self.login_view.profile_message {
+                                            let color = if *success { egui::Color32::GREEN } else { egui::Color32::RED };
+                                            ui.colored_label(color, message);
+                                        }
                                     });
                             });
                         },
@@ -272,68 +753,128 @@ impl eframe::App for App {
                     .min_width(200.0)
                     .max_width(200.0)
                     .show(ctx, |ui| {
-                        new_state = self.sidebar.show(ui, &self.state);
+                        new_state = self.sidebar.show(ui, &self.state, &mut self.density, self.is_connected);
                     });
 
-                // Cambiar de estado si se seleccionó uno nuevo
-                if let Some(state) = new_state {
-                    self.set_state(state);
+                // Cambiar de estado si se seleccionó uno nuevo; volver al login es desconectar
+                match new_state {
+                    Some(AppState::Login) => self.request_disconnect(),
+                    Some(state) => self.set_state(state),
+                    None => {}
                 }
 
+                // Barra de estado con los totales (debe agregarse antes del panel central)
+                egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+                    self.status_bar.show(ui, self.is_connected);
+                });
+
                 egui::CentralPanel::default().show(ctx, |ui| {
-                    // Mostrar mensajes en la parte superior
-                    if self.error_message.is_some() || self.success_message.is_some() {
-                        ui.horizontal(|ui| {
-                            self.show_messages(ui);
-                            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                if ui.small_button("❌").clicked() {
-                                    self.clear_messages();
-                                }
-                            });
-                        });
-                        ui.separator();
+                    // Sin conexión, las vistas que consultan la base muestran un aviso en su lugar
+                    let offline = !self.is_connected && self.state != AppState::About;
+                    if offline
+                        && components::disconnected_notice(ui, self.reconnect_receiver.is_some())
+                        && self.reconnect_receiver.is_none()
+                    {
+                        self.start_reconnect();
                     }
 
                     // Mostrar la vista correspondiente
                     match self.state {
+                        _ if offline => {}
                         AppState::Dashboard => {
                             if self.dashboard_view.check_stats_result() {
                                 ctx.request_repaint();
                             }
-                            self.dashboard_view.show(ui);
+                            if let Some(uv_id) = self.dashboard_view.show(ui) {
+                                self.queries_view.show_personas_in_uv(uv_id);
+                                self.set_state(AppState::Queries);
+                            }
                         }
                         AppState::Queries => {
                             if self.queries_view.check_query_result() {
                                 ctx.request_repaint();
                             }
-                            self.queries_view.show(ui);
+                            if self.queries_view.show(ui) {
+                                self.status_bar.refresh();
+                            }
                         }
                         AppState::Insertions => {
                             if let Some((success, message)) = self.insertions_view.show(ui) {
                                 if success {
                                     self.set_success(message);
+                                    self.status_bar.refresh();
+                                    // Que Consultas no muestre resultados cacheados previos al cambio
+                                    match self.insertions_view.current_type().query_type() {
+                                        Some(query_type) => self.queries_view.invalidate_cache(&query_type),
+                                        None => self.queries_view.clear_cache(),
+                                    }
                                 } else {
                                     self.set_error(message);
                                 }
                                 ctx.request_repaint();
                             }
                         }
+                        AppState::CatalogAdmin => {
+                            self.catalog_admin_view.show(ui);
+                        }
+                        AppState::Search => {
+                            if let Some(record) = self.search_view.show(ui) {
+                                self.queries_view.open_record(record);
+                                self.set_state(AppState::Queries);
+                            }
+                        }
+                        AppState::SqlConsole => {
+                            self.sql_console_view.show(ui);
+                        }
                         AppState::About => {
                             self.about_view.show(ui);
                         }
                         _ => {}
                     }
+
+                    if self.confirm_disconnect.show(ui) == Some(true) {
+                        self.start_disconnect();
+                    }
                 });
             }
         }
+
+        if self.state != AppState::Login {
+            if let Some(persona) = self.rut_jump.show(ctx) {
+                self.queries_view.open_record(SelectedRecord::Persona(persona));
+                self.set_state(AppState::Queries);
+            }
+        }
+
+        // Los avisos se dibujan al final, sobre todas las vistas
+        self.toasts.lock().unwrap().show(ctx);
     }
 
     fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
-        // Cerrar conexión de base de datos al salir
+        // Cerrar la conexión de base de datos antes de que termine el proceso. Toda operación
+        // sobre la base toma el lock del DatabaseManager, así que obtenerlo espera a que
+        // terminen las escrituras en curso (y las que ya estaban en cola, el Mutex es FIFO).
+        if self.db_manager.try_lock().is_err() {
+            log::info!("Cerrando: esperando a que terminen las operaciones en curso...");
+        }
+
         let db_manager = self.db_manager.clone();
-        tokio::spawn(async move {
-            let mut db = db_manager.lock().await;
-            db.disconnect().await;
+        let runtime = self.runtime.clone();
+        let finished = tokio::task::block_in_place(|| {
+            runtime.block_on(async move {
+                tokio::time::timeout(SHUTDOWN_TIMEOUT, async move {
+                    let mut db = db_manager.lock().await;
+                    db.disconnect().await;
+                })
+                .await
+            })
         });
+
+        if finished.is_err() {
+            log::warn!(
+                "Seguían operaciones en curso tras {} s; se cerró sin esperar a que terminen",
+                SHUTDOWN_TIMEOUT.as_secs()
+            );
+        }
     }
 }
@@ -0,0 +1,175 @@
+use chrono::{Datelike, Duration, Local, Months, NaiveDate};
+use eframe::egui;
+use crate::models::Actividad;
+use crate::ui::detail::SelectedRecord;
+use crate::ui::theme::AppleMusicStyle;
+
+const MESES: [&str; 12] = [
+    "Enero", "Febrero", "Marzo", "Abril", "Mayo", "Junio",
+    "Julio", "Agosto", "Septiembre", "Octubre", "Noviembre", "Diciembre",
+];
+const DIAS_SEMANA: [&str; 7] = ["Lu", "Ma", "Mi", "Ju", "Vi", "Sá", "Do"];
+
+const DAY_CELL_HEIGHT: f32 = 72.0;
+// Barras de actividad visibles por día; el resto se resume como "+N"
+const MAX_BARS_PER_DAY: usize = 3;
+
+// Vista de calendario mensual de actividades según su rango act_fecha_ini..act_fecha_fin
+pub struct ActivityCalendar {
+    // Primer día del mes mostrado
+    month: NaiveDate,
+    selected_day: Option<NaiveDate>,
+}
+
+impl ActivityCalendar {
+    pub fn new() -> Self {
+        Self {
+            month: first_of_month(Local::now().date_naive()),
+            selected_day: None,
+        }
+    }
+
+    // Devuelve la actividad elegida en la lista del día seleccionado
+    pub fn show(&mut self, ui: &mut egui::Ui, actividades: &[Actividad], selected_id: Option<i32>) -> Option<SelectedRecord> {
+        self.show_month_header(ui);
+        ui.add_space(8.0);
+        ui.vertical(|ui| self.show_month_grid(ui, actividades));
+
+        let day = self.selected_day?;
+        ui.add_space(10.0);
+        ui.strong(format!("Actividades del {}", day.format("%d/%m/%Y")));
+
+        let mut seleccionado = None;
+        let del_dia: Vec<&Actividad> = actividades.iter().filter(|a| occurs_on(a, day)).collect();
+        if del_dia.is_empty() {
+            ui.label("Sin actividades este día");
+        }
+        for actividad in del_dia {
+            ui.horizontal(|ui| {
+                if ui.selectable_label(selected_id == Some(actividad.act_id), &actividad.act_nombre).clicked() {
+                    seleccionado = Some(SelectedRecord::Actividad(actividad.clone()));
+                }
+                if let Some(uv) = &actividad.uv_nombre {
+                    ui.weak(uv);
+                }
+            });
+        }
+        seleccionado
+    }
+
+    fn show_month_header(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if ui.button("◀").on_hover_text("Mes anterior").clicked() {
+                self.month = self.month - Months::new(1);
+            }
+            ui.label(
+                egui::RichText::new(format!("{} {}", MESES[self.month.month0() as usize], self.month.year()))
+                    .size(16.0)
+                    .strong()
+            );
+            if ui.button("▶").on_hover_text("Mes siguiente").clicked() {
+                self.month = self.month + Months::new(1);
+            }
+            if ui.button("Hoy").clicked() {
+                let today = Local::now().date_naive();
+                self.month = first_of_month(today);
+                self.selected_day = Some(today);
+            }
+        });
+    }
+
+    // Grilla de 6 semanas de lunes a domingo que cubre el mes completo
+    fn show_month_grid(&mut self, ui: &mut egui::Ui, actividades: &[Actividad]) {
+        let cell_width = (ui.available_width() / 7.0).floor();
+        let today = Local::now().date_naive();
+        let grid_start = self.month - Duration::days(self.month.weekday().num_days_from_monday() as i64);
+
+        ui.spacing_mut().item_spacing = egui::vec2(0.0, 0.0);
+        ui.horizontal(|ui| {
+            for dia in DIAS_SEMANA {
+                let (rect, _) = ui.allocate_exact_size(egui::vec2(cell_width, 20.0), egui::Sense::hover());
+                ui.painter().text(
+                    rect.center(),
+                    egui::Align2::CENTER_CENTER,
+                    dia,
+                    egui::FontId::proportional(12.0),
+                    AppleMusicStyle::TEXT_SECONDARY,
+                );
+            }
+        });
+
+        for week in 0..6 {
+            ui.horizontal(|ui| {
+                for weekday in 0..7 {
+                    let day = grid_start + Duration::days(week * 7 + weekday);
+                    let (rect, response) = ui.allocate_exact_size(egui::vec2(cell_width, DAY_CELL_HEIGHT), egui::Sense::click());
+                    if response.clicked() {
+                        self.selected_day = Some(day);
+                    }
+                    self.paint_day(ui, rect, day, day == today, response.hovered(), actividades);
+                }
+            });
+        }
+    }
+
+    fn paint_day(&self, ui: &egui::Ui, rect: egui::Rect, day: NaiveDate, is_today: bool, hovered: bool, actividades: &[Actividad]) {
+        let painter = ui.painter_at(rect);
+        let in_month = day.month() == self.month.month();
+        let cell = rect.shrink(1.0);
+
+        let fill = if self.selected_day == Some(day) {
+            AppleMusicStyle::PRIMARY_BLUE.linear_multiply(0.35)
+        } else if hovered {
+            AppleMusicStyle::BACKGROUND_LIGHT
+        } else {
+            AppleMusicStyle::CARD_BG
+        };
+        painter.rect_filled(cell, egui::Rounding::same(4.0), fill);
+        if is_today {
+            painter.rect_stroke(cell, egui::Rounding::same(4.0), egui::Stroke::new(1.5, AppleMusicStyle::PRIMARY_BLUE));
+        }
+
+        let text_color = if in_month { AppleMusicStyle::TEXT_PRIMARY } else { AppleMusicStyle::TEXT_SECONDARY.linear_multiply(0.5) };
+        painter.text(
+            cell.left_top() + egui::vec2(4.0, 2.0),
+            egui::Align2::LEFT_TOP,
+            day.day().to_string(),
+            egui::FontId::proportional(12.0),
+            text_color,
+        );
+
+        let del_dia: Vec<&Actividad> = actividades.iter().filter(|a| occurs_on(a, day)).collect();
+        let mut y = cell.top() + 18.0;
+        for actividad in del_dia.iter().take(MAX_BARS_PER_DAY) {
+            let bar = egui::Rect::from_min_size(egui::pos2(cell.left() + 2.0, y), egui::vec2(cell.width() - 4.0, 14.0));
+            painter.rect_filled(bar, egui::Rounding::same(3.0), AppleMusicStyle::SECONDARY_BLUE.linear_multiply(0.6));
+            painter.text(
+                bar.left_center() + egui::vec2(3.0, 0.0),
+                egui::Align2::LEFT_CENTER,
+                &actividad.act_nombre,
+                egui::FontId::proportional(10.0),
+                AppleMusicStyle::TEXT_PRIMARY,
+            );
+            y += 16.0;
+        }
+        if del_dia.len() > MAX_BARS_PER_DAY {
+            painter.text(
+                egui::pos2(cell.right() - 4.0, cell.top() + 2.0),
+                egui::Align2::RIGHT_TOP,
+                format!("+{}", del_dia.len() - MAX_BARS_PER_DAY),
+                egui::FontId::proportional(10.0),
+                AppleMusicStyle::TEXT_SECONDARY,
+            );
+        }
+    }
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap_or(date)
+}
+
+// Una actividad sin fecha de término ocupa solo su día de inicio
+fn occurs_on(actividad: &Actividad, day: NaiveDate) -> bool {
+    let fin = actividad.act_fecha_fin.unwrap_or(actividad.act_fecha_ini);
+    actividad.act_fecha_ini <= day && day <= fin
+}
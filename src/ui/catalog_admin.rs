@@ -0,0 +1,261 @@
+use eframe::egui;
+use std::sync::Arc;
+use tokio::sync::{Mutex, mpsc};
+use crate::database::DatabaseManager;
+use crate::ui::catalogs::{Catalogs, SharedCatalogs};
+use crate::ui::components;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CatalogKind {
+    Genero,
+    Nacionalidad,
+    MacroSector,
+    UnidadVecinal,
+}
+
+impl CatalogKind {
+    fn label(&self) -> &'static str {
+        match self {
+            CatalogKind::Genero => "Géneros",
+            CatalogKind::Nacionalidad => "Nacionalidades",
+            CatalogKind::MacroSector => "Macrosectores",
+            CatalogKind::UnidadVecinal => "Unidades Vecinales",
+        }
+    }
+}
+
+// Catálogo guardado, si fue un registro nuevo y mensaje para el usuario
+type SaveOutcome = (CatalogKind, bool, String);
+
+#[derive(Debug, Clone)]
+enum CatalogAction {
+    Rename(i32, String),
+    // Nombre y, para unidades vecinales, el macrosector al que pertenece
+    Add(String, Option<i32>),
+}
+
+// Administración de los catálogos: renombrar y agregar géneros, nacionalidades,
+// macrosectores y unidades vecinales
+pub struct CatalogAdminView {
+    db_manager: Arc<Mutex<DatabaseManager>>,
+    catalogs: SharedCatalogs,
+
+    // Registro en edición: catálogo, id y nombre nuevo
+    editing: Option<(CatalogKind, i32, String)>,
+
+    // Campos para agregar registros
+    new_genero: String,
+    new_nacionalidad: String,
+    new_macro_sector: String,
+    new_unidad_vecinal: String,
+    new_uv_macro: Option<i32>,
+
+    // Estado
+    saving: bool,
+    message: Option<(bool, String)>,
+
+    // Canal asíncrono
+    save_receiver: Option<mpsc::UnboundedReceiver<Result<SaveOutcome, String>>>,
+}
+
+impl CatalogAdminView {
+    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>, catalogs: SharedCatalogs) -> Self {
+        Self {
+            db_manager,
+            catalogs,
+            editing: None,
+            new_genero: String::new(),
+            new_nacionalidad: String::new(),
+            new_macro_sector: String::new(),
+            new_unidad_vecinal: String::new(),
+            new_uv_macro: None,
+            saving: false,
+            message: None,
+            save_receiver: None,
+        }
+    }
+
+    fn check_save_result(&mut self) {
+        if let Some(receiver) = &mut self.save_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.saving = false;
+                self.save_receiver = None;
+                match result {
+                    Ok((kind, added, message)) => {
+                        if added {
+                            self.new_name_mut(kind).clear();
+                        } else {
+                            self.editing = None;
+                        }
+                        self.message = Some((true, message));
+                        Catalogs::refresh(&self.catalogs, &self.db_manager);
+                    }
+                    Err(error_msg) => {
+                        self.message = Some((false, error_msg));
+                    }
+                }
+            }
+        }
+    }
+
+    fn new_name_mut(&mut self, kind: CatalogKind) -> &mut String {
+        match kind {
+            CatalogKind::Genero => &mut self.new_genero,
+            CatalogKind::Nacionalidad => &mut self.new_nacionalidad,
+            CatalogKind::MacroSector => &mut self.new_macro_sector,
+            CatalogKind::UnidadVecinal => &mut self.new_unidad_vecinal,
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        self.check_save_result();
+
+        ui.heading("🗂 Catálogos");
+        ui.label("Corrija o agregue los valores de los catálogos usados en los formularios.");
+        ui.add_space(10.0);
+
+        if let Some((success, ref text)) = self.message {
+            let color = if success { egui::Color32::GREEN } else { egui::Color32::RED };
+            ui.colored_label(color, text);
+            ui.add_space(5.0);
+        }
+
+        // Copia de los catálogos para no mantener el lock mientras se dibuja
+        let (generos, nacionalidades, macro_sectores, unidades_vecinales) = {
+            let catalogs = self.catalogs.lock().unwrap();
+            (
+                catalogs.generos.iter().map(|g| (g.gen_id, g.gen_genero.clone())).collect::<Vec<_>>(),
+                catalogs.nacionalidades.iter().map(|n| (n.nac_id, n.nac_nacionalidad.clone())).collect::<Vec<_>>(),
+                catalogs.macro_sectores.clone(),
+                catalogs.unidades_vecinales.clone(),
+            )
+        };
+        let macro_entries: Vec<(i32, String)> = macro_sectores.iter().map(|m| (m.mac_id, m.mac_nombre.clone())).collect();
+        let uv_entries: Vec<(i32, String)> = unidades_vecinales.iter().map(|u| (u.uv_id, u.uv_nombre.clone())).collect();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            self.show_catalog(ui, CatalogKind::Genero, &generos);
+            self.show_catalog(ui, CatalogKind::Nacionalidad, &nacionalidades);
+            self.show_catalog(ui, CatalogKind::MacroSector, &macro_entries);
+            self.show_catalog(ui, CatalogKind::UnidadVecinal, &uv_entries);
+
+            // Las unidades vecinales nuevas necesitan un macrosector
+            ui.horizontal(|ui| {
+                ui.label("Macrosector de la nueva UV:");
+                components::macro_sector_combo(ui, "catalog_new_uv_macro", &mut self.new_uv_macro, &macro_sectores, "Seleccione...");
+            });
+        });
+    }
+
+    fn show_catalog(&mut self, ui: &mut egui::Ui, kind: CatalogKind, entries: &[(i32, String)]) {
+        egui::CollapsingHeader::new(format!("{} ({})", kind.label(), entries.len()))
+            .id_source(kind.label())
+            .default_open(false)
+            .show(ui, |ui| {
+                let mut action = None;
+                let mut cancel = false;
+                let saving = self.saving;
+                egui::Grid::new(format!("catalog_grid_{:?}", kind))
+                    .num_columns(2)
+                    .striped(true)
+                    .spacing([15.0, 6.0])
+                    .show(ui, |ui| {
+                        for (id, nombre) in entries {
+                            match &mut self.editing {
+                                Some((editing_kind, editing_id, new_name)) if *editing_kind == kind && editing_id == id => {
+                                    ui.text_edit_singleline(new_name);
+                                    ui.horizontal(|ui| {
+                                        let duplicated = name_taken(entries, new_name, Some(*id));
+                                        let valid = !new_name.trim().is_empty() && !duplicated && !saving;
+                                        if ui.add_enabled(valid, egui::Button::new("💾 Guardar")).clicked() {
+                                            action = Some(CatalogAction::Rename(*id, new_name.trim().to_string()));
+                                        }
+                                        if ui.button("Cancelar").clicked() {
+                                            cancel = true;
+                                        }
+                                        if duplicated {
+                                            ui.colored_label(egui::Color32::RED, "Ese nombre ya existe");
+                                        }
+                                    });
+                                }
+                                _ => {
+                                    ui.label(nombre);
+                                    if ui.small_button("✏ Renombrar").clicked() {
+                                        self.editing = Some((kind, *id, nombre.clone()));
+                                    }
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if cancel {
+                    self.editing = None;
+                }
+
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    let needs_macro = kind == CatalogKind::UnidadVecinal;
+                    let macro_id = self.new_uv_macro;
+                    let new_name = self.new_name_mut(kind);
+                    ui.add(egui::TextEdit::singleline(new_name).hint_text("Nuevo nombre").desired_width(200.0));
+                    let duplicated = name_taken(entries, new_name, None);
+                    let valid = !new_name.trim().is_empty() && !duplicated && !saving && (!needs_macro || macro_id.is_some());
+                    if ui.add_enabled(valid, egui::Button::new("➕ Agregar")).clicked() {
+                        action = Some(CatalogAction::Add(new_name.trim().to_string(), macro_id));
+                    }
+                    if duplicated {
+                        ui.colored_label(egui::Color32::RED, "Ese nombre ya existe");
+                    } else if needs_macro && macro_id.is_none() && !new_name.trim().is_empty() {
+                        ui.label("Seleccione el macrosector abajo");
+                    }
+                });
+
+                if let Some(action) = action {
+                    self.save(kind, action);
+                }
+            });
+    }
+
+    fn save(&mut self, kind: CatalogKind, action: CatalogAction) {
+        self.saving = true;
+        self.message = None;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.save_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = match action {
+                CatalogAction::Rename(id, nombre) => {
+                    let renamed = match kind {
+                        CatalogKind::Genero => db.update_genero(id, &nombre).await,
+                        CatalogKind::Nacionalidad => db.update_nacionalidad(id, &nombre).await,
+                        CatalogKind::MacroSector => db.update_macro_sector(id, &nombre).await,
+                        CatalogKind::UnidadVecinal => db.update_unidad_vecinal(id, &nombre).await,
+                    };
+                    renamed.map(|_| (kind, false, format!("Registro renombrado a '{}'", nombre)))
+                }
+                CatalogAction::Add(nombre, macro_id) => {
+                    let inserted = match kind {
+                        CatalogKind::Genero => db.insert_genero(&nombre).await,
+                        CatalogKind::Nacionalidad => db.insert_nacionalidad(&nombre).await,
+                        CatalogKind::MacroSector => db.insert_macro_sector(&nombre).await,
+                        CatalogKind::UnidadVecinal => match macro_id {
+                            Some(macro_id) => db.insert_unidad_vecinal(&nombre, macro_id).await,
+                            None => Err(anyhow::anyhow!("Seleccione un macrosector")),
+                        },
+                    };
+                    inserted.map(|_| (kind, true, format!("'{}' agregado a {}", nombre, kind.label())))
+                }
+            };
+            let _ = tx.send(result.map_err(|e| format!("Error al guardar: {}", e)));
+        });
+    }
+}
+
+// Compara sin distinguir mayúsculas, igual que la verificación de la base de datos
+fn name_taken(entries: &[(i32, String)], nombre: &str, except_id: Option<i32>) -> bool {
+    let nombre = nombre.trim().to_lowercase();
+    entries.iter().any(|(id, existing)| Some(*id) != except_id && existing.to_lowercase() == nombre)
+}
@@ -0,0 +1,110 @@
+use chrono::{DateTime, Local};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use crate::database::DatabaseManager;
+use crate::models::{Genero, MacroSector, Nacionalidad, PageRequest, Taller, UnidadVecinal};
+
+// Catálogos compartidos entre vistas; App es dueño de la instancia
+pub type SharedCatalogs = Arc<std::sync::Mutex<Catalogs>>;
+
+#[derive(Debug, Default)]
+pub struct Catalogs {
+    pub generos: Vec<Genero>,
+    pub nacionalidades: Vec<Nacionalidad>,
+    pub unidades_vecinales: Vec<UnidadVecinal>,
+    pub macro_sectores: Vec<MacroSector>,
+    pub talleres: Vec<Taller>,
+
+    // Se incrementa con cada actualización para que las vistas detecten cambios
+    pub version: u64,
+    // Momento de la última recarga completada y si hay una en curso
+    pub loaded_at: Option<DateTime<Local>>,
+    pub refreshing: bool,
+    // Motivo por el que falló la última recarga; los catálogos anteriores se conservan
+    pub error: Option<String>,
+    // Generación de la última recarga solicitada; las recargas anteriores se descartan
+    generation: u64,
+}
+
+impl Catalogs {
+    pub fn new_shared() -> SharedCatalogs {
+        Arc::new(std::sync::Mutex::new(Catalogs::default()))
+    }
+
+    // Vacía los catálogos al desconectar; las recargas aún en curso se descartan
+    pub fn clear(catalogs: &SharedCatalogs) {
+        let mut guard = catalogs.lock().unwrap();
+        let (version, generation) = (guard.version, guard.generation);
+        *guard = Catalogs {
+            version: version + 1,
+            generation: generation + 1,
+            ..Catalogs::default()
+        };
+    }
+
+    // Recarga los catálogos desde la base de datos en segundo plano
+    pub fn refresh(catalogs: &SharedCatalogs, db_manager: &Arc<Mutex<DatabaseManager>>) {
+        let generation = {
+            let mut guard = catalogs.lock().unwrap();
+            guard.generation += 1;
+            guard.refreshing = true;
+            guard.generation
+        };
+
+        let catalogs = catalogs.clone();
+        let db_manager = db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            if !db.is_connected() {
+                drop(db);
+                let mut guard = catalogs.lock().unwrap();
+                if generation < guard.generation {
+                    return;
+                }
+                guard.error = Some("Sin conexión a la base de datos".to_string());
+                guard.refreshing = false;
+                return;
+            }
+            let generos = db.get_generos().await;
+            let nacionalidades = db.get_nacionalidades().await;
+            let unidades = db.get_unidades_vecinales().await;
+            let sectores = db.get_macro_sectores().await;
+            let talleres = db.get_talleres(&PageRequest::all()).await;
+            drop(db);
+
+            let mut guard = catalogs.lock().unwrap();
+            if generation < guard.generation {
+                return;
+            }
+            guard.error = [
+                generos.as_ref().err(),
+                nacionalidades.as_ref().err(),
+                unidades.as_ref().err(),
+                sectores.as_ref().err(),
+                talleres.as_ref().err(),
+            ]
+            .into_iter()
+            .flatten()
+            .next()
+            .map(|e| e.to_string());
+            if let Ok(generos) = generos {
+                guard.generos = generos;
+            }
+            if let Ok(nacionalidades) = nacionalidades {
+                guard.nacionalidades = nacionalidades;
+            }
+            if let Ok(unidades) = unidades {
+                guard.unidades_vecinales = unidades;
+            }
+            if let Ok(sectores) = sectores {
+                guard.macro_sectores = sectores;
+            }
+            if let Ok(talleres) = talleres {
+                guard.talleres = talleres.rows;
+            }
+            guard.version += 1;
+            guard.loaded_at = Some(Local::now());
+            guard.refreshing = false;
+        });
+    }
+}
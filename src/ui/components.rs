@@ -1,5 +1,10 @@
 // Componentes UI reutilizables
 use eframe::egui;
+use egui_extras::{Column, TableBuilder, TableRow};
+use std::collections::HashSet;
+use crate::models::{DatabaseConfig, MacroSector, Taller, UnidadVecinal};
+use crate::ui::field_help;
+use crate::utils;
 
 #[allow(dead_code)]
 pub struct LoadingSpinner {
@@ -80,3 +85,545 @@ pub fn show_success_dialog(ui: &mut egui::Ui, message: &str) {
             });
         });
 }
+
+// Diálogo modal de confirmación (Confirmar/Cancelar) centrado sobre un fondo oscurecido
+// que bloquea el resto de la interfaz mientras está abierto
+#[derive(Default)]
+pub struct ConfirmDialog {
+    open: bool,
+    title: String,
+    message: String,
+}
+
+impl ConfirmDialog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn open(&mut self, title: impl Into<String>, message: impl Into<String>) {
+        self.title = title.into();
+        self.message = message.into();
+        self.open = true;
+    }
+
+    pub fn close(&mut self) {
+        self.open = false;
+    }
+
+    // Debe llamarse en cada frame. Devuelve Some(true) al confirmar, Some(false) al cancelar
+    // (también con Escape) y None mientras sigue abierto o si está cerrado.
+    pub fn show(&mut self, ui: &mut egui::Ui) -> Option<bool> {
+        if !self.open {
+            return None;
+        }
+        let ctx = ui.ctx().clone();
+        let screen = ctx.screen_rect();
+
+        // Fondo oscurecido que absorbe los clics dirigidos a la interfaz de abajo
+        egui::Area::new(egui::Id::new("confirm_dialog_backdrop"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(screen.min)
+            .show(&ctx, |ui| {
+                ui.painter().rect_filled(screen, 0.0, egui::Color32::from_black_alpha(160));
+                ui.allocate_rect(screen, egui::Sense::click());
+            });
+
+        let mut answer = None;
+        let window = egui::Window::new(self.title.as_str())
+            .id(egui::Id::new("confirm_dialog"))
+            .order(egui::Order::Foreground)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(&ctx, |ui| {
+                ui.label(self.message.as_str());
+                ui.add_space(12.0);
+                ui.horizontal(|ui| {
+                    if ui.button("Confirmar").clicked() {
+                        answer = Some(true);
+                    }
+                    if ui.button("Cancelar").clicked() {
+                        answer = Some(false);
+                    }
+                });
+            });
+        // El diálogo siempre queda por encima del fondo, aunque se haga clic en este
+        if let Some(window) = window {
+            ctx.move_to_top(window.response.layer_id);
+        }
+        if ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            answer = Some(false);
+        }
+
+        if answer.is_some() {
+            self.open = false;
+        }
+        answer
+    }
+}
+
+// Estado de las vistas que dependen de la base cuando no hay conexión, en lugar de
+// grillas vacías o estadísticas en cero. Devuelve true al pulsar "Reconectar".
+pub fn disconnected_notice(ui: &mut egui::Ui, reconnecting: bool) -> bool {
+    let mut clicked = false;
+    ui.vertical_centered(|ui| {
+        ui.add_space(60.0);
+        ui.label(egui::RichText::new("🔌").size(40.0));
+        ui.add_space(10.0);
+        ui.heading("Sin conexión a la base de datos — reconéctese");
+        ui.add_space(6.0);
+        ui.label(egui::RichText::new("Los datos de esta vista se mostrarán al restablecer la conexión.").color(egui::Color32::GRAY));
+        ui.add_space(16.0);
+        if reconnecting {
+            ui.add(egui::widgets::Spinner::new().size(16.0));
+            ui.label("Reconectando...");
+        } else {
+            clicked = ui.button("🔄 Reconectar").clicked();
+        }
+    });
+    clicked
+}
+
+// Filas de formulario para un egui::Grid de dos columnas (etiqueta y control).
+// Cada helper agrega el `:` a la etiqueta y cierra la fila con end_row.
+
+// Etiqueta de un campo con el ícono ℹ si el diccionario de datos tiene ayuda para él
+pub fn field_label(ui: &mut egui::Ui, label: &str) {
+    ui.horizontal(|ui| {
+        ui.label(format!("{}:", label));
+        if let Some(help) = field_help::help_for(label) {
+            ui.label(egui::RichText::new("ℹ").small().weak()).on_hover_text(help);
+        }
+    });
+}
+
+// Aviso pequeño en rojo bajo un campo
+pub fn field_hint(ui: &mut egui::Ui, error: Option<&str>) {
+    if let Some(error) = error {
+        ui.label(egui::RichText::new(error).small().color(egui::Color32::RED));
+    }
+}
+
+// Aviso pequeño en amarillo bajo un campo; no impide continuar
+pub fn field_warning(ui: &mut egui::Ui, warning: Option<&str>) {
+    if let Some(warning) = warning {
+        ui.label(egui::RichText::new(warning).small().color(egui::Color32::YELLOW));
+    }
+}
+
+// Error de una fecha de filtro escrita como texto; vacío significa "sin límite"
+pub fn filter_date_error(text: &str) -> Option<&'static str> {
+    let text = text.trim();
+    (!text.is_empty() && utils::parse_date(text).is_none()).then_some("Fecha inválida")
+}
+
+// Fecha de un filtro: `value` se actualiza al escribir y queda en None mientras el texto
+// no sea una fecha válida, con el aviso bajo el campo. Devuelve true si el texto cambió.
+pub fn filter_date_field(ui: &mut egui::Ui, text: &mut String, value: &mut Option<chrono::NaiveDate>) -> bool {
+    let mut changed = false;
+    ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            if ui.text_edit_singleline(text).changed() {
+                *value = utils::parse_date(text.trim());
+                changed = true;
+            }
+            ui.small("(dd/mm/yyyy)");
+        });
+        field_hint(ui, filter_date_error(text));
+    });
+    changed
+}
+
+// Selector de puerto con el error o la advertencia debajo
+pub fn port_field(ui: &mut egui::Ui, config: &mut DatabaseConfig) -> egui::Response {
+    ui.vertical(|ui| {
+        let response = ui.add(egui::DragValue::new(&mut config.port).range(1..=65535));
+        field_hint(ui, config.port_error());
+        field_warning(ui, config.port_warning());
+        response
+    }).inner
+}
+
+pub fn text_row(ui: &mut egui::Ui, label: &str, value: &mut String) -> egui::Response {
+    field_label(ui, label);
+    let response = ui.text_edit_singleline(value);
+    ui.end_row();
+    response
+}
+
+// Campo de texto con el error de validación bajo él
+pub fn checked_text_row(ui: &mut egui::Ui, label: &str, value: &mut String, error: Option<&str>) {
+    field_label(ui, label);
+    ui.vertical(|ui| {
+        ui.text_edit_singleline(value);
+        field_hint(ui, error);
+    });
+    ui.end_row();
+}
+
+// Fecha escrita como dd/mm/yyyy; `error` se muestra bajo el campo
+pub fn date_row(ui: &mut egui::Ui, label: &str, value: &mut String, error: Option<&str>) {
+    field_label(ui, label);
+    ui.vertical(|ui| {
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(value);
+            ui.small("(dd/mm/yyyy)");
+        });
+        field_hint(ui, error);
+    });
+    ui.end_row();
+}
+
+// ComboBox de un catálogo; `option` da el id y el texto de cada elemento.
+// Devuelve true si la selección cambió.
+pub fn combo_row<T>(
+    ui: &mut egui::Ui,
+    label: &str,
+    id: &str,
+    selected: &mut Option<i32>,
+    items: &[T],
+    option: impl Fn(&T) -> (i32, &str),
+) -> bool {
+    let before = *selected;
+    field_label(ui, label);
+    egui::ComboBox::from_id_source(id)
+        .selected_text(
+            items.iter()
+                .map(&option)
+                .find(|(item_id, _)| Some(*item_id) == before)
+                .map(|(_, text)| text)
+                .unwrap_or("Seleccionar...")
+        )
+        .show_ui(ui, |ui| {
+            for item in items {
+                let (item_id, text) = option(item);
+                ui.selectable_value(selected, Some(item_id), text);
+            }
+        });
+    ui.end_row();
+    *selected != before
+}
+
+// ComboBox de macrosector. `none_label` es el texto de la opción sin selección.
+// Devuelve true si la selección cambió.
+pub fn macro_sector_combo(
+    ui: &mut egui::Ui,
+    id: &str,
+    selected: &mut Option<i32>,
+    macro_sectores: &[MacroSector],
+    none_label: &str,
+) -> bool {
+    let before = *selected;
+    egui::ComboBox::from_id_source(id)
+        .selected_text(
+            selected
+                .and_then(|id| macro_sectores.iter().find(|m| m.mac_id == id))
+                .map(|m| m.mac_nombre.clone())
+                .unwrap_or_else(|| none_label.to_string())
+        )
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, none_label);
+            for macro_sector in macro_sectores {
+                ui.selectable_value(selected, Some(macro_sector.mac_id), &macro_sector.mac_nombre);
+            }
+        });
+    *selected != before
+}
+
+// ComboBox de taller. `none_label` es el texto de la opción sin selección.
+// Devuelve true si la selección cambió.
+pub fn taller_combo(
+    ui: &mut egui::Ui,
+    id: &str,
+    selected: &mut Option<i32>,
+    talleres: &[Taller],
+    none_label: &str,
+) -> bool {
+    let before = *selected;
+    egui::ComboBox::from_id_source(id)
+        .selected_text(
+            selected
+                .and_then(|id| talleres.iter().find(|t| t.tal_id == id))
+                .map(|t| t.tal_nombre.clone())
+                .unwrap_or_else(|| none_label.to_string())
+        )
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, none_label);
+            for taller in talleres {
+                ui.selectable_value(selected, Some(taller.tal_id), &taller.tal_nombre);
+            }
+        });
+    *selected != before
+}
+
+// ComboBox con una opción vacía al inicio. Devuelve true si la selección cambió.
+pub fn optional_combo<T>(
+    ui: &mut egui::Ui,
+    id: &str,
+    selected: &mut Option<i32>,
+    items: &[T],
+    option: impl Fn(&T) -> (i32, &str),
+    none_label: &str,
+) -> bool {
+    let before = *selected;
+    egui::ComboBox::from_id_source(id)
+        .selected_text(
+            items.iter()
+                .map(&option)
+                .find(|(item_id, _)| Some(*item_id) == before)
+                .map(|(_, text)| text)
+                .unwrap_or(none_label)
+        )
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, none_label);
+            for item in items {
+                let (item_id, text) = option(item);
+                ui.selectable_value(selected, Some(item_id), text);
+            }
+        });
+    *selected != before
+}
+
+// ComboBox de unidad vecinal que solo ofrece las UV del macrosector indicado.
+// Devuelve true si la selección cambió.
+pub fn uv_combo(
+    ui: &mut egui::Ui,
+    id: &str,
+    selected: &mut Option<i32>,
+    uvs: &[UnidadVecinal],
+    macro_filter: Option<i32>,
+    none_label: &str,
+) -> bool {
+    let before = *selected;
+    egui::ComboBox::from_id_source(id)
+        .selected_text(
+            selected
+                .and_then(|id| uvs.iter().find(|u| u.uv_id == id))
+                .map(|u| u.uv_nombre.clone())
+                .unwrap_or_else(|| none_label.to_string())
+        )
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected, None, none_label);
+            for uv in uvs {
+                if macro_filter.is_some_and(|mac_id| uv.uv_macid != mac_id) {
+                    continue;
+                }
+                ui.selectable_value(selected, Some(uv.uv_id), &uv.uv_nombre);
+            }
+        });
+    *selected != before
+}
+
+// Descarta la UV seleccionada si no pertenece al macrosector elegido
+pub fn clear_invalid_uv(selected: &mut Option<i32>, uvs: &[UnidadVecinal], macro_filter: Option<i32>) {
+    let (Some(uv_id), Some(mac_id)) = (*selected, macro_filter) else {
+        return;
+    };
+    if !uvs.iter().any(|u| u.uv_id == uv_id && u.uv_macid == mac_id) {
+        *selected = None;
+    }
+}
+
+// Etiqueta de estado con fondo de color redondeado
+pub fn pill(ui: &mut egui::Ui, text: &str, color: egui::Color32) {
+    egui::Frame::none()
+        .fill(color.linear_multiply(0.25))
+        .rounding(egui::Rounding::same(8.0))
+        .inner_margin(egui::Margin::symmetric(8.0, 1.0))
+        .show(ui, |ui| {
+            ui.label(egui::RichText::new(text).small().color(color));
+        });
+}
+
+// Largo máximo de un email en las tablas de resultados; el completo queda en el tooltip
+const EMAIL_CELL_MAX_LEN: usize = 30;
+
+// Email como enlace mailto: que abre el cliente de correo; un guion si no hay email
+pub fn email_cell(ui: &mut egui::Ui, email: Option<&str>) {
+    match email.filter(|email| !email.trim().is_empty()) {
+        Some(email) => {
+            ui.hyperlink_to(utils::truncate_text(email, EMAIL_CELL_MAX_LEN), format!("mailto:{}", email))
+                .on_hover_text(email);
+        }
+        None => {
+            ui.label("-");
+        }
+    }
+}
+
+// Alto de fila de las tablas de resultados; depende de la densidad elegida (ver theme.rs)
+fn result_row_height(ui: &egui::Ui) -> f32 {
+    ui.spacing().interact_size.y + 4.0
+}
+
+fn results_table_builder(ui: &mut egui::Ui, column_count: usize) -> TableBuilder<'_> {
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+        // Las celdas detectan clics para el menú contextual de las filas
+        .sense(egui::Sense::click())
+        .columns(Column::auto().at_least(60.0).clip(true), column_count.saturating_sub(1))
+        .column(Column::remainder().at_least(60.0))
+}
+
+// Acción elegida en el desplegable de historial de un campo de búsqueda
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HistoryAction {
+    None,
+    Picked,
+    Clear,
+}
+
+// Campo de texto que, al tener el foco, ofrece debajo las búsquedas anteriores que
+// contienen lo escrito. Elegir una la copia en el campo (`HistoryAction::Picked`).
+pub fn history_text_edit(ui: &mut egui::Ui, value: &mut String, history: &[String]) -> (egui::Response, HistoryAction) {
+    let response = ui.text_edit_singleline(value);
+    let popup_id = response.id.with("historial");
+
+    let needle = value.trim().to_lowercase();
+    let matches: Vec<&String> = history.iter()
+        .filter(|entry| **entry != value.trim() && entry.to_lowercase().contains(&needle))
+        .collect();
+
+    if matches.is_empty() {
+        if ui.memory(|m| m.is_popup_open(popup_id)) {
+            ui.memory_mut(|m| m.close_popup());
+        }
+        return (response, HistoryAction::None);
+    }
+    if response.gained_focus() || response.clicked() || (response.has_focus() && response.changed()) {
+        ui.memory_mut(|m| m.open_popup(popup_id));
+    }
+
+    let mut action = HistoryAction::None;
+    egui::popup_below_widget(ui, popup_id, &response, egui::PopupCloseBehavior::CloseOnClickOutside, |ui| {
+        for entry in matches {
+            if ui.selectable_label(false, entry.as_str()).clicked() {
+                *value = entry.clone();
+                action = HistoryAction::Picked;
+            }
+        }
+        ui.separator();
+        if ui.small_button("🗑 Borrar historial").clicked() {
+            action = HistoryAction::Clear;
+        }
+    });
+    if action != HistoryAction::None {
+        ui.memory_mut(|m| m.close_popup());
+    }
+
+    (response, action)
+}
+
+// Menú "⚙ Columnas" para elegir qué columnas de una tabla de resultados se muestran.
+// La primera columna (la que abre el detalle) no se puede ocultar. Devuelve true si
+// cambió la selección.
+pub fn columns_menu(ui: &mut egui::Ui, headers: &[&str], hidden: &mut Vec<String>) -> bool {
+    let mut changed = false;
+    ui.menu_button("⚙ Columnas", |ui| {
+        for header in headers.iter().skip(1) {
+            let mut visible = !hidden.iter().any(|h| h == header);
+            if ui.checkbox(&mut visible, *header).changed() {
+                if visible {
+                    hidden.retain(|h| h != header);
+                } else {
+                    hidden.push(header.to_string());
+                }
+                changed = true;
+            }
+        }
+    });
+    changed
+}
+
+// Menú contextual (clic derecho) de una fila de resultados con acciones de copiar al
+// portapapeles. Cada acción es (etiqueta, texto); se calculan solo al abrir el menú.
+pub fn copy_context_menu(row: &TableRow<'_, '_>, actions: impl FnOnce() -> Vec<(&'static str, String)>) {
+    row.response().context_menu(|ui| {
+        for (label, text) in actions() {
+            if ui.button(label).clicked() {
+                ui.output_mut(|o| o.copied_text = text);
+                ui.close_menu();
+            }
+        }
+    });
+}
+
+// Tabla de resultados virtualizada: solo se dibujan las filas visibles, con encabezado
+// fijo y columnas redimensionables. `add_row` recibe el índice de la fila a dibujar.
+pub fn results_table(
+    ui: &mut egui::Ui,
+    id: &str,
+    headers: &[&str],
+    row_count: usize,
+    mut add_row: impl FnMut(usize, &mut TableRow<'_, '_>),
+) {
+    ui.push_id(id, |ui| {
+        let row_height = result_row_height(ui);
+        results_table_builder(ui, headers.len())
+            .header(row_height, |mut header| {
+                for title in headers {
+                    header.col(|ui| {
+                        ui.strong(*title);
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(row_height, row_count, |mut row| {
+                    let index = row.index();
+                    add_row(index, &mut row);
+                });
+            });
+    });
+}
+
+// Igual que `results_table`, con una primera columna de casillas para selección múltiple.
+// `row_ids` es el id de cada fila; la casilla del encabezado marca o desmarca todas.
+pub fn selectable_results_table(
+    ui: &mut egui::Ui,
+    id: &str,
+    headers: &[&str],
+    row_ids: &[i32],
+    selected: &mut HashSet<i32>,
+    mut add_row: impl FnMut(usize, &mut TableRow<'_, '_>),
+) {
+    ui.push_id(id, |ui| {
+        let row_height = result_row_height(ui);
+        results_table_builder(ui, headers.len() + 1)
+            .header(row_height, |mut header| {
+                header.col(|ui| {
+                    let mut all = !row_ids.is_empty() && row_ids.iter().all(|id| selected.contains(id));
+                    if ui.checkbox(&mut all, "").on_hover_text("Seleccionar todos").changed() {
+                        if all {
+                            selected.extend(row_ids.iter().copied());
+                        } else {
+                            selected.clear();
+                        }
+                    }
+                });
+                for title in headers {
+                    header.col(|ui| {
+                        ui.strong(*title);
+                    });
+                }
+            })
+            .body(|body| {
+                body.rows(row_height, row_ids.len(), |mut row| {
+                    let index = row.index();
+                    let row_id = row_ids[index];
+                    row.col(|ui| {
+                        let mut checked = selected.contains(&row_id);
+                        if ui.checkbox(&mut checked, "").changed() {
+                            if checked {
+                                selected.insert(row_id);
+                            } else {
+                                selected.remove(&row_id);
+                            }
+                        }
+                    });
+                    add_row(index, &mut row);
+                });
+            });
+    });
+}
@@ -1,56 +1,239 @@
 use eframe::egui;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, mpsc};
 use crate::database::DatabaseManager;
-use crate::models::DashboardStats;
+use crate::models::{DashboardStats, RECENT_ACTIVIDADES_LIMIT};
+use crate::report;
 use crate::ui::theme::AppleMusicStyle;
+use crate::utils;
+
+// Intervalos de actualización automática ofrecidos (None = desactivada)
+const AUTO_REFRESH_OPTIONS: [(Option<Duration>, &str); 4] = [
+    (None, "Auto: off"),
+    (Some(Duration::from_secs(30)), "Auto: 30s"),
+    (Some(Duration::from_secs(60)), "Auto: 1m"),
+    (Some(Duration::from_secs(300)), "Auto: 5m"),
+];
+
+// Reintentos automáticos tras un error al cargar las estadísticas; la espera se duplica
+// en cada intento (2s, 4s, 8s)
+const STATS_RETRY_ATTEMPTS: u32 = 3;
+const STATS_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+// (uv_id, nombre, personas)
+type UvCounts = Vec<(i32, String, i64)>;
 
 pub struct DashboardView {
     db_manager: Arc<Mutex<DatabaseManager>>,
     stats: Option<DashboardStats>,
     loading: bool,
     last_refresh: std::time::Instant,
+    auto_refresh: Option<Duration>,
     stats_receiver: Option<mpsc::UnboundedReceiver<Result<DashboardStats, String>>>,
+    // Último error de carga; las estadísticas anteriores (si hay) se siguen mostrando
+    stats_error: Option<String>,
+    failed_attempts: u32,
+    retry_at: Option<Instant>,
+
+    // Generación del reporte PDF
+    generating_report: bool,
+    report_message: Option<(bool, String)>,
+    report_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+
+    // Detalle de un macrosector: personas por unidad vecinal
+    drilldown: Option<(i32, String)>,
+    uv_counts: UvCounts,
+    loading_uv_counts: bool,
+    uv_counts_error: Option<String>,
+    uv_counts_receiver: Option<mpsc::UnboundedReceiver<Result<UvCounts, String>>>,
 }
 
 impl DashboardView {
+    // Las estadísticas se cargan recién al conectar (initialize_data)
     pub fn new(db_manager: Arc<Mutex<DatabaseManager>>) -> Self {
-        let mut dashboard = Self {
+        Self {
             db_manager,
             stats: None,
             loading: false,
             last_refresh: std::time::Instant::now(),
+            auto_refresh: None,
             stats_receiver: None,
-        };
-        dashboard.refresh_stats();
-        dashboard
+            stats_error: None,
+            failed_attempts: 0,
+            retry_at: None,
+            generating_report: false,
+            report_message: None,
+            report_receiver: None,
+            drilldown: None,
+            uv_counts: Vec::new(),
+            loading_uv_counts: false,
+            uv_counts_error: None,
+            uv_counts_receiver: None,
+        }
+    }
+
+    pub fn initialize_data(&mut self) {
+        self.failed_attempts = 0;
+        self.retry_at = None;
+        self.refresh_stats();
     }
 
     pub fn check_stats_result(&mut self) -> bool {
         if let Some(receiver) = &mut self.stats_receiver {
             if let Ok(result) = receiver.try_recv() {
                 self.loading = false;
+                self.stats_receiver = None;
                 match result {
                     Ok(stats) => {
                         self.stats = Some(stats);
-                        self.stats_receiver = None;
-                        return true;
+                        self.stats_error = None;
+                        self.failed_attempts = 0;
                     }
-                    Err(_error_msg) => {
-                        // En caso de error, mostrar datos vacíos
-                        self.stats = Some(DashboardStats::default());
-                        self.stats_receiver = None;
-                        return true;
+                    Err(error_msg) => {
+                        // No se muestran ceros, que parecerían una base vacía
+                        log::warn!("{}", error_msg);
+                        self.stats_error = Some(error_msg);
+                        self.failed_attempts += 1;
+                        self.retry_at = (self.failed_attempts <= STATS_RETRY_ATTEMPTS)
+                            .then(|| Instant::now() + STATS_RETRY_DELAY * 2u32.pow(self.failed_attempts - 1));
                     }
                 }
+                return true;
             }
         }
         false
     }
 
-    pub fn show(&mut self, ui: &mut egui::Ui) {
+    fn check_report_result(&mut self) {
+        if let Some(receiver) = &mut self.report_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.generating_report = false;
+                self.report_receiver = None;
+                self.report_message = match result {
+                    Ok(success_msg) => Some((true, success_msg)),
+                    Err(error_msg) => Some((false, error_msg)),
+                };
+            }
+        }
+    }
+
+    fn check_uv_counts_result(&mut self) {
+        if let Some(receiver) = &mut self.uv_counts_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.loading_uv_counts = false;
+                self.uv_counts_receiver = None;
+                match result {
+                    Ok(counts) => {
+                        self.uv_counts = counts;
+                        self.uv_counts_error = None;
+                    }
+                    Err(error_msg) => {
+                        self.uv_counts.clear();
+                        self.uv_counts_error = Some(error_msg);
+                    }
+                }
+            }
+        }
+    }
+
+    fn open_drilldown(&mut self, mac_id: i32, nombre: String) {
+        self.drilldown = Some((mac_id, nombre));
+        self.uv_counts.clear();
+        self.uv_counts_error = None;
+        self.loading_uv_counts = true;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.uv_counts_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = db.personas_por_uv_in_macro(mac_id).await
+                .map_err(|e| format!("Error al cargar unidades vecinales: {}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    // Ventana con las personas por unidad vecinal del macrosector elegido.
+    // Devuelve la UV en que se hizo clic.
+    fn show_drilldown(&mut self, ctx: &egui::Context) -> Option<i32> {
+        let (_, nombre) = self.drilldown.as_ref()?;
+        let mut open = true;
+        let mut picked = None;
+        egui::Window::new(format!("Macrosector {}", nombre))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                if self.loading_uv_counts {
+                    ui.add(egui::widgets::Spinner::new().size(16.0));
+                    ctx.request_repaint();
+                    return;
+                }
+                if let Some(error_msg) = &self.uv_counts_error {
+                    ui.colored_label(egui::Color32::RED, error_msg);
+                    return;
+                }
+                if self.uv_counts.is_empty() {
+                    ui.label("Este macrosector no tiene unidades vecinales");
+                    return;
+                }
+                ui.label(AppleMusicStyle::secondary_text("Clic en una unidad vecinal para ver sus personas en Consultas"));
+                ui.add_space(6.0);
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    egui::Grid::new("uv_counts_grid")
+                        .num_columns(2)
+                        .striped(true)
+                        .spacing([20.0, 6.0])
+                        .show(ui, |ui| {
+                            for (uv_id, uv_nombre, count) in &self.uv_counts {
+                                if ui.selectable_label(false, uv_nombre).clicked() {
+                                    picked = Some(*uv_id);
+                                }
+                                ui.label(utils::format_number(*count));
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        if !open || picked.is_some() {
+            self.drilldown = None;
+        }
+        picked
+    }
+
+    // Devuelve la unidad vecinal elegida en el detalle de un macrosector, para
+    // abrir sus personas en Consultas
+    pub fn show(&mut self, ui: &mut egui::Ui) -> Option<i32> {
         // Check for async stats results
         self.check_stats_result();
+        self.check_report_result();
+        self.check_uv_counts_result();
+
+        if let Some(retry_at) = self.retry_at {
+            let now = Instant::now();
+            if now >= retry_at {
+                self.retry_at = None;
+                if !self.loading {
+                    self.refresh_stats();
+                }
+            } else {
+                ui.ctx().request_repaint_after(retry_at - now);
+            }
+        }
+
+        // Actualización automática; `loading` evita solapar consultas lentas
+        if let Some(interval) = self.auto_refresh {
+            let elapsed = self.last_refresh.elapsed();
+            if elapsed >= interval {
+                if !self.loading {
+                    self.refresh_stats();
+                }
+            } else {
+                ui.ctx().request_repaint_after(interval - elapsed);
+            }
+        }
 
         // Header con estilo Apple Music
         AppleMusicStyle::card_frame().show(ui, |ui| {
@@ -67,27 +250,69 @@ impl DashboardView {
                         .min_size(egui::vec2(100.0, 32.0));
                     
                     if ui.add_enabled(!self.loading, button).clicked() {
-                        self.refresh_stats();
+                        self.initialize_data();
+                    }
+
+                    ui.add_space(8.0);
+
+                    let report_button = egui::Button::new("🖨️ Generar Reporte PDF")
+                        .fill(AppleMusicStyle::CARD_BG)
+                        .rounding(egui::Rounding::same(8.0))
+                        .stroke(egui::Stroke::new(1.0, AppleMusicStyle::SECONDARY_BLUE))
+                        .min_size(egui::vec2(100.0, 32.0));
+                    let can_report = self.stats.is_some() && !self.loading && !self.generating_report;
+                    if ui.add_enabled(can_report, report_button).clicked() {
+                        self.generate_report();
                     }
+                    if self.generating_report {
+                        ui.add(egui::widgets::Spinner::new().size(16.0));
+                    }
+                    
+                    ui.add_space(8.0);
+
+                    let auto_label = AUTO_REFRESH_OPTIONS.iter()
+                        .find(|(interval, _)| *interval == self.auto_refresh)
+                        .map(|(_, label)| *label)
+                        .unwrap_or("Auto: off");
+                    egui::ComboBox::from_id_source("dashboard_auto_refresh")
+                        .selected_text(auto_label)
+                        .width(90.0)
+                        .show_ui(ui, |ui| {
+                            for (interval, label) in AUTO_REFRESH_OPTIONS {
+                                ui.selectable_value(&mut self.auto_refresh, interval, label);
+                            }
+                        });
                     
                     ui.add_space(16.0);
                     
                     // Tiempo desde última actualización
-                    let elapsed = self.last_refresh.elapsed().as_secs();
-                    let minutes = elapsed / 60;
-                    let remaining_seconds = elapsed % 60;
-                    
-                    let time_text = if minutes > 0 {
-                        format!("Actualizado hace {}m {}s", minutes, remaining_seconds)
-                    } else {
-                        format!("Actualizado hace {}s", remaining_seconds)
-                    };
-                    
-                    ui.add(egui::Label::new(AppleMusicStyle::secondary_text(&time_text)));
+                    if self.stats.is_some() {
+                        let time_text = format!("Actualizado hace {}", utils::format_elapsed(self.last_refresh.elapsed()));
+                        ui.add(egui::Label::new(AppleMusicStyle::secondary_text(&time_text)));
+                    }
                 });
             });
         });
 
+        if let Some((success, ref text)) = self.report_message {
+            ui.add_space(10.0);
+            let color = if success { egui::Color32::GREEN } else { egui::Color32::RED };
+            ui.colored_label(color, text);
+        }
+
+        if let Some(error_msg) = self.stats_error.clone() {
+            ui.add_space(10.0);
+            ui.horizontal(|ui| {
+                ui.colored_label(egui::Color32::RED, format!("⚠ {}", error_msg));
+                if let Some(retry_at) = self.retry_at {
+                    let secs = retry_at.saturating_duration_since(Instant::now()).as_secs() + 1;
+                    ui.label(AppleMusicStyle::secondary_text(&format!("Reintentando en {}s", secs)));
+                } else if !self.loading && ui.button("Reintentar").clicked() {
+                    self.initialize_data();
+                }
+            });
+        }
+
         ui.add_space(20.0);
 
         if self.loading {
@@ -95,16 +320,26 @@ impl DashboardView {
                 ui.add(egui::widgets::Spinner::new().size(32.0));
                 ui.label("Cargando estadísticas...");
             });
-            return;
+            return None;
         }
 
+        let mut clicked_macro = None;
         if let Some(ref stats) = self.stats {
-            self.show_stats_cards(ui, stats);
-            ui.add_space(20.0);
-            self.show_charts(ui, stats);
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                self.show_stats_cards(ui, stats);
+                ui.add_space(20.0);
+                clicked_macro = self.show_charts(ui, stats);
+                ui.add_space(20.0);
+                self.show_recent_actividades(ui, stats);
+            });
         } else {
             self.show_placeholder(ui);
         }
+
+        if let Some((mac_id, nombre)) = clicked_macro {
+            self.open_drilldown(mac_id, nombre);
+        }
+        self.show_drilldown(ui.ctx())
     }
 
     fn show_stats_cards(&self, ui: &mut egui::Ui, stats: &DashboardStats) {
@@ -113,20 +348,25 @@ impl DashboardView {
 
         // Grid de tarjetas de estadísticas
         egui::Grid::new("stats_grid")
-            .num_columns(4)
+            .num_columns(5)
             .spacing([15.0, 15.0])
             .show(ui, |ui| {
                 // Tarjeta de Personas
-                self.stat_card(ui, "👥", "Personas Mayores", stats.total_personas.to_string(), egui::Color32::LIGHT_BLUE);
+                self.stat_card(ui, "👥", "Personas Mayores", utils::format_number(stats.total_personas), egui::Color32::LIGHT_BLUE);
                 
                 // Tarjeta de Organizaciones
-                self.stat_card(ui, "🏢", "Organizaciones", stats.total_organizaciones.to_string(), egui::Color32::LIGHT_GREEN);
+                self.stat_card(ui, "🏢", "Organizaciones", utils::format_number(stats.total_organizaciones), egui::Color32::LIGHT_GREEN);
                 
                 // Tarjeta de Actividades
-                self.stat_card(ui, "🎯", "Actividades", stats.total_actividades.to_string(), egui::Color32::from_rgb(255, 165, 0));
+                self.stat_card(ui, "🎯", "Actividades", utils::format_number(stats.total_actividades), egui::Color32::from_rgb(255, 165, 0));
                 
                 // Tarjeta de Viajes
-                self.stat_card(ui, "🚌", "Viajes", stats.total_viajes.to_string(), egui::Color32::LIGHT_RED);
+                self.stat_card(ui, "🚌", "Viajes", utils::format_number(stats.total_viajes), egui::Color32::LIGHT_RED);
+
+                // Tarjeta de Centros (solo si la tabla existe)
+                if let Some(total_centros) = stats.total_centros {
+                    self.stat_card(ui, "🏠", "Centros Comunitarios", utils::format_number(total_centros), egui::Color32::from_rgb(186, 85, 211));
+                }
                 ui.end_row();
             });
     }
@@ -147,11 +387,15 @@ impl DashboardView {
             });
     }
 
-    fn show_charts(&self, ui: &mut egui::Ui, stats: &DashboardStats) {
+    // Devuelve el macrosector (id, nombre) en que se hizo clic, si hubo uno
+    fn show_charts(&self, ui: &mut egui::Ui, stats: &DashboardStats) -> Option<(i32, String)> {
+        let mut clicked_macro = None;
         ui.horizontal(|ui| {
+            let column_width = ui.available_width() / 3.0 - 14.0;
+
             // Columna izquierda - Distribución por macrosector
             ui.vertical(|ui| {
-                ui.set_min_width(ui.available_width() / 2.0 - 10.0);
+                ui.set_min_width(column_width);
                 ui.label("Distribución de Personas por Macrosector");
                 ui.add_space(10.0);
                 
@@ -162,20 +406,72 @@ impl DashboardView {
                     .show(ui, |ui| {
                         ui.set_min_height(200.0);
                         
+                        let max = stats.personas_por_macro.iter().map(|(_, _, count)| *count).max().unwrap_or(0);
                         if stats.personas_por_macro.is_empty() {
                             ui.centered_and_justified(|ui| {
                                 ui.label("No hay datos disponibles");
                             });
                         } else {
-                            for (macro_name, count) in &stats.personas_por_macro {
-                                ui.horizontal(|ui| {
-                                    ui.label(macro_name);
-                                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                                        ui.label(count.to_string());
-                                    });
+                            // Clic en un macrosector para ver sus unidades vecinales
+                            egui::Grid::new("macro_chart")
+                                .num_columns(3)
+                                .spacing([10.0, 12.0])
+                                .show(ui, |ui| {
+                                    for (mac_id, macro_name, count) in &stats.personas_por_macro {
+                                        let label = ui.selectable_label(false, macro_name);
+                                        let bar = ui.add(
+                                            egui::ProgressBar::new(*count as f32 / max.max(1) as f32)
+                                                .desired_width(column_width - 130.0)
+                                                .fill(egui::Color32::LIGHT_GREEN)
+                                        ).interact(egui::Sense::click());
+                                        if label.clicked() || bar.clicked() {
+                                            clicked_macro = Some((*mac_id, macro_name.clone()));
+                                        }
+                                        bar.on_hover_text("Ver unidades vecinales");
+                                        ui.label(utils::format_number(*count));
+                                        ui.end_row();
+                                    }
+                                });
+                        }
+                    });
+            });
+
+            ui.add_space(20.0);
+
+            // Columna central - Distribución por rango de edad
+            ui.vertical(|ui| {
+                ui.set_min_width(column_width);
+                ui.label("Distribución de Edades");
+                ui.add_space(10.0);
+
+                egui::Frame::none()
+                    .fill(egui::Color32::from_gray(30))
+                    .rounding(egui::Rounding::same(5.0))
+                    .inner_margin(egui::Margin::same(10.0))
+                    .show(ui, |ui| {
+                        ui.set_min_height(200.0);
+
+                        let max = stats.edades_por_rango.iter().map(|(_, count)| *count).max().unwrap_or(0);
+                        if max == 0 {
+                            ui.centered_and_justified(|ui| {
+                                ui.label("No hay datos disponibles");
+                            });
+                        } else {
+                            egui::Grid::new("edades_chart")
+                                .num_columns(3)
+                                .spacing([10.0, 12.0])
+                                .show(ui, |ui| {
+                                    for (rango, count) in &stats.edades_por_rango {
+                                        ui.label(rango);
+                                        ui.add(
+                                            egui::ProgressBar::new(*count as f32 / max as f32)
+                                                .desired_width(column_width - 110.0)
+                                                .fill(egui::Color32::LIGHT_BLUE)
+                                        );
+                                        ui.label(utils::format_number(*count));
+                                        ui.end_row();
+                                    }
                                 });
-                                ui.separator();
-                            }
                         }
                     });
             });
@@ -200,22 +496,100 @@ impl DashboardView {
                             .spacing([10.0, 10.0])
                             .show(ui, |ui| {
                                 ui.label("🎯 Actividades este mes:");
-                                ui.label(stats.actividades_mes_actual.to_string());
+                                ui.label(utils::format_number(stats.actividades_mes_actual));
                                 ui.end_row();
                                 
                                 ui.label("👤 Nuevas personas:");
-                                ui.label(stats.nuevas_personas_mes.to_string());
+                                ui.label(utils::format_number(stats.nuevas_personas_mes));
+                                ui.end_row();
+
+                                ui.label("🙋 Promedio de participantes por actividad:");
+                                ui.label(format!("{:.1}", stats.promedio_participantes));
                                 ui.end_row();
                             });
                     });
             });
         });
+        clicked_macro
+    }
+
+    fn show_recent_actividades(&self, ui: &mut egui::Ui, stats: &DashboardStats) {
+        ui.label(format!("Últimas {} Actividades", RECENT_ACTIVIDADES_LIMIT));
+        ui.add_space(10.0);
+
+        egui::Frame::none()
+            .fill(egui::Color32::from_gray(30))
+            .rounding(egui::Rounding::same(5.0))
+            .inner_margin(egui::Margin::same(10.0))
+            .show(ui, |ui| {
+                ui.set_min_width(ui.available_width());
+
+                if stats.ultimas_actividades.is_empty() {
+                    ui.label("Aún no hay actividades registradas");
+                    return;
+                }
+                egui::Grid::new("recent_actividades")
+                    .num_columns(3)
+                    .striped(true)
+                    .spacing([20.0, 8.0])
+                    .show(ui, |ui| {
+                        for actividad in &stats.ultimas_actividades {
+                            ui.label(&actividad.act_nombre);
+                            ui.label(utils::format_date(&actividad.act_fecha_ini));
+                            ui.label(actividad.uv_nombre.as_deref().unwrap_or("N/A"));
+                            ui.end_row();
+                        }
+                    });
+            });
     }
 
+    // Sin estadísticas cargadas: conectando, o el error ya se muestra arriba
     fn show_placeholder(&self, ui: &mut egui::Ui) {
         ui.with_layout(egui::Layout::centered_and_justified(egui::Direction::TopDown), |ui| {
-            ui.label("📊");
-            ui.label("Haga clic en 'Actualizar datos' para cargar las estadísticas");
+            if self.stats_error.is_some() {
+                ui.label("📊 No se pudieron cargar las estadísticas");
+            } else {
+                ui.label("Conectando...");
+            }
+        });
+    }
+
+    fn generate_report(&mut self) {
+        let Some(stats) = self.stats.clone() else {
+            return;
+        };
+        self.generating_report = true;
+        self.report_message = None;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.report_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let database = db_manager.lock().await
+                .database_name()
+                .unwrap_or_else(|| "desconocida".to_string());
+
+            let file_name = format!("reporte_{}.pdf", chrono::Local::now().format("%Y-%m"));
+            let Some(handle) = rfd::AsyncFileDialog::new()
+                .set_title("Guardar reporte")
+                .set_file_name(file_name)
+                .add_filter("PDF", &["pdf"])
+                .save_file()
+                .await
+            else {
+                let _ = tx.send(Err("Generación de reporte cancelada".to_string()));
+                return;
+            };
+            let path = handle.path().to_path_buf();
+
+            let result = tokio::task::spawn_blocking(move || {
+                report::write_dashboard_pdf(&stats, &database, &path)
+                    .map(|_| format!("Reporte guardado en {}", path.display()))
+                    .map_err(|e| format!("Error al generar reporte: {}", e))
+            }).await;
+
+            let _ = tx.send(result.unwrap_or_else(|e| Err(format!("Error al generar reporte: {}", e))));
         });
     }
 
@@ -0,0 +1,643 @@
+use eframe::egui;
+use std::sync::Arc;
+use tokio::sync::{Mutex, mpsc};
+use tokio::sync::mpsc::error::TryRecvError;
+use crate::database::DatabaseManager;
+use chrono::NaiveDateTime;
+use crate::models::*;
+use crate::config::AppConfig;
+use crate::report;
+use crate::utils;
+use crate::ui::components::{self, ConfirmDialog};
+use crate::ui::grupo_personas::{GrupoPersonas, GrupoPersonasPanel};
+use crate::ui::telefonos::TelefonosPanel;
+use crate::ui::toasts::SharedToasts;
+
+// Registro seleccionado en los resultados de una consulta
+#[derive(Debug, Clone)]
+pub enum SelectedRecord {
+    Persona(PersonaMayor),
+    Organizacion(OrganizacionComunitaria),
+    Actividad(Actividad),
+}
+
+impl SelectedRecord {
+    // Registro completo en JSON tal como lo serializa serde. Incluye los campos de join
+    // (género, nacionalidad, unidad vecinal) porque dan contexto fuera de la app; las
+    // fechas salen en ISO 8601 (AAAA-MM-DD y AAAA-MM-DDTHH:MM:SS)
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        match self {
+            SelectedRecord::Persona(persona) => serde_json::to_string_pretty(persona),
+            SelectedRecord::Organizacion(org) => serde_json::to_string_pretty(org),
+            SelectedRecord::Actividad(actividad) => serde_json::to_string_pretty(actividad),
+        }
+    }
+}
+
+// Texto opcional: vacío se guarda como NULL
+fn optional_text(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+// Campos editables del registro seleccionado, como texto mientras se escriben.
+// RUT, catálogos y unidad vecinal no se editan aquí (ver `DatabaseManager::update_persona`)
+#[derive(Debug, Clone)]
+enum EditForm {
+    Persona {
+        primer_nombre: String,
+        segundo_nombre: String,
+        primer_apellido: String,
+        segundo_apellido: String,
+        fecha_nacimiento: String,
+        direccion: String,
+        email: String,
+    },
+    Organizacion {
+        nombre: String,
+        direccion: String,
+        fecha_constitucion: String,
+        personalidad_juridica: String,
+        email: String,
+    },
+    Actividad {
+        nombre: String,
+        fecha_inicio: String,
+        fecha_fin: String,
+        descripcion: String,
+    },
+}
+
+impl EditForm {
+    fn from_record(record: &SelectedRecord) -> Self {
+        match record {
+            SelectedRecord::Persona(p) => EditForm::Persona {
+                primer_nombre: p.per_prinombre.clone(),
+                segundo_nombre: p.per_segnombre.clone().unwrap_or_default(),
+                primer_apellido: p.per_priapellido.clone(),
+                segundo_apellido: p.per_segapellido.clone().unwrap_or_default(),
+                fecha_nacimiento: utils::format_date(&p.per_fechadenac),
+                direccion: p.per_direccion.clone(),
+                email: p.per_email.clone().unwrap_or_default(),
+            },
+            SelectedRecord::Organizacion(o) => EditForm::Organizacion {
+                nombre: o.org_nombre.clone(),
+                direccion: o.org_direccion.clone(),
+                fecha_constitucion: utils::format_date(&o.org_fechaconst),
+                personalidad_juridica: o.org_perjuridica.clone(),
+                email: o.org_email.clone().unwrap_or_default(),
+            },
+            SelectedRecord::Actividad(a) => EditForm::Actividad {
+                nombre: a.act_nombre.clone(),
+                fecha_inicio: utils::format_date(&a.act_fecha_ini),
+                fecha_fin: a.act_fecha_fin.as_ref().map(utils::format_date).unwrap_or_default(),
+                descripcion: a.act_descripcion.clone().unwrap_or_default(),
+            },
+        }
+    }
+
+    // Copia del registro con los cambios aplicados, o el primer error de validación
+    fn apply(&self, record: &SelectedRecord, min_age: i32) -> Result<SelectedRecord, String> {
+        let required = |value: &str, label: &str| {
+            let value = value.trim();
+            if value.is_empty() { Err(format!("{} es obligatorio", label)) } else { Ok(value.to_string()) }
+        };
+        let date = |value: &str, label: &str| {
+            utils::parse_date(value).ok_or_else(|| format!("{}: fecha inválida, use dd/mm/yyyy", label))
+        };
+        let email = |value: &str| match optional_text(value) {
+            Some(email) if !utils::validate_email(&email) => Err("Email inválido".to_string()),
+            email => Ok(email),
+        };
+
+        match (self, record) {
+            (
+                EditForm::Persona { primer_nombre, segundo_nombre, primer_apellido, segundo_apellido, fecha_nacimiento, direccion, email: email_text },
+                SelectedRecord::Persona(persona),
+            ) => {
+                let fecha = date(fecha_nacimiento, "Fecha de nacimiento")?;
+                if let Some(error) = utils::birthdate_error(&fecha, min_age) {
+                    return Err(error);
+                }
+                Ok(SelectedRecord::Persona(PersonaMayor {
+                    per_prinombre: required(primer_nombre, "Primer nombre")?,
+                    per_segnombre: optional_text(segundo_nombre),
+                    per_priapellido: required(primer_apellido, "Primer apellido")?,
+                    per_segapellido: optional_text(segundo_apellido),
+                    per_fechadenac: fecha,
+                    per_direccion: required(direccion, "Dirección")?,
+                    per_email: email(email_text)?,
+                    ..persona.clone()
+                }))
+            }
+            (
+                EditForm::Organizacion { nombre, direccion, fecha_constitucion, personalidad_juridica, email: email_text },
+                SelectedRecord::Organizacion(org),
+            ) => {
+                let personalidad_juridica = utils::normalize_personalidad_juridica(personalidad_juridica)
+                    .ok_or_else(|| "Personalidad jurídica inválida, ej. 1234 o 1234-R".to_string())?;
+                Ok(SelectedRecord::Organizacion(OrganizacionComunitaria {
+                    org_nombre: required(nombre, "Nombre")?,
+                    org_direccion: required(direccion, "Dirección")?,
+                    org_fechaconst: date(fecha_constitucion, "Fecha constitución")?,
+                    org_perjuridica: personalidad_juridica,
+                    org_email: email(email_text)?,
+                    ..org.clone()
+                }))
+            }
+            (
+                EditForm::Actividad { nombre, fecha_inicio, fecha_fin, descripcion },
+                SelectedRecord::Actividad(actividad),
+            ) => {
+                let inicio = date(fecha_inicio, "Fecha inicio")?;
+                let fin = match fecha_fin.trim() {
+                    "" => None,
+                    fin => Some(date(fin, "Fecha fin")?),
+                };
+                if fin.is_some_and(|fin| fin < inicio) {
+                    return Err("La fecha de fin no puede ser anterior a la fecha de inicio".to_string());
+                }
+                Ok(SelectedRecord::Actividad(Actividad {
+                    act_nombre: required(nombre, "Nombre")?,
+                    act_fecha_ini: inicio,
+                    act_fecha_fin: fin,
+                    act_descripcion: optional_text(descripcion),
+                    ..actividad.clone()
+                }))
+            }
+            _ => Err("El formulario no corresponde al registro seleccionado".to_string()),
+        }
+    }
+
+    fn show(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("detail_edit")
+            .num_columns(2)
+            .spacing([10.0, 6.0])
+            .show(ui, |ui| match self {
+                EditForm::Persona { primer_nombre, segundo_nombre, primer_apellido, segundo_apellido, fecha_nacimiento, direccion, email } => {
+                    components::text_row(ui, "Primer Nombre", primer_nombre);
+                    components::text_row(ui, "Segundo Nombre", segundo_nombre);
+                    components::text_row(ui, "Primer Apellido", primer_apellido);
+                    components::text_row(ui, "Segundo Apellido", segundo_apellido);
+                    components::date_row(ui, "Fecha de Nacimiento", fecha_nacimiento, None);
+                    components::text_row(ui, "Dirección", direccion);
+                    components::text_row(ui, "Email", email);
+                }
+                EditForm::Organizacion { nombre, direccion, fecha_constitucion, personalidad_juridica, email } => {
+                    components::text_row(ui, "Nombre", nombre);
+                    components::text_row(ui, "Dirección", direccion);
+                    components::date_row(ui, "Fecha Constitución", fecha_constitucion, None);
+                    components::text_row(ui, "Personalidad Jurídica", personalidad_juridica);
+                    components::text_row(ui, "Email", email);
+                }
+                EditForm::Actividad { nombre, fecha_inicio, fecha_fin, descripcion } => {
+                    components::text_row(ui, "Nombre", nombre);
+                    components::date_row(ui, "Fecha Inicio", fecha_inicio, None);
+                    components::date_row(ui, "Fecha Fin", fecha_fin, None);
+                    components::field_label(ui, "Descripción");
+                    ui.text_edit_multiline(descripcion);
+                    ui.end_row();
+                }
+            });
+    }
+}
+
+// Panel lateral con todos los campos del registro seleccionado
+pub struct DetailPanel {
+    db_manager: Arc<Mutex<DatabaseManager>>,
+    selected: Option<SelectedRecord>,
+    telefonos_panel: TelefonosPanel,
+    personas_panel: GrupoPersonasPanel,
+
+    // Estado de eliminación
+    confirm_delete: ConfirmDialog,
+    deleting: bool,
+    message: Option<(bool, String)>,
+    delete_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+
+    // Edición de los campos de texto y fechas del registro
+    edit_form: Option<EditForm>,
+    min_age: i32,
+    saving: bool,
+    update_receiver: Option<mpsc::UnboundedReceiver<Result<SelectedRecord, String>>>,
+
+    // Exportación de la ficha PDF de una persona; el resultado se avisa con un toast
+    toasts: SharedToasts,
+    exporting_ficha: bool,
+    ficha_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+}
+
+impl DetailPanel {
+    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>, toasts: SharedToasts) -> Self {
+        Self {
+            telefonos_panel: TelefonosPanel::new(db_manager.clone()),
+            personas_panel: GrupoPersonasPanel::new(db_manager.clone()),
+            db_manager,
+            selected: None,
+            confirm_delete: ConfirmDialog::new(),
+            deleting: false,
+            message: None,
+            delete_receiver: None,
+            edit_form: None,
+            min_age: AppConfig::load().min_age(),
+            saving: false,
+            update_receiver: None,
+            toasts,
+            exporting_ficha: false,
+            ficha_receiver: None,
+        }
+    }
+
+    pub fn open(&mut self, record: SelectedRecord) {
+        match &record {
+            SelectedRecord::Persona(persona) => {
+                let nombre = format!("{} {}", persona.per_prinombre, persona.per_priapellido);
+                self.telefonos_panel.open(TelefonoEntidad::Persona, persona.per_id, nombre);
+                self.personas_panel.close();
+            }
+            SelectedRecord::Organizacion(org) => {
+                self.telefonos_panel.open(TelefonoEntidad::Organizacion, org.org_id, org.org_nombre.clone());
+                self.personas_panel.open(GrupoPersonas::Miembros, org.org_id, org.org_nombre.clone());
+            }
+            SelectedRecord::Actividad(actividad) => {
+                self.telefonos_panel.close();
+                self.personas_panel.open(GrupoPersonas::Participantes, actividad.act_id, actividad.act_nombre.clone());
+            }
+        }
+        self.selected = Some(record);
+        self.confirm_delete.close();
+        self.edit_form = None;
+        self.message = None;
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.selected.is_some()
+    }
+
+    pub fn close(&mut self) {
+        self.selected = None;
+        self.edit_form = None;
+        self.confirm_delete.close();
+        self.telefonos_panel.close();
+        self.personas_panel.close();
+    }
+
+    // Devuelve el id del registro seleccionado si coincide con el tipo consultado
+    pub fn selected_persona_id(&self) -> Option<i32> {
+        match &self.selected {
+            Some(SelectedRecord::Persona(p)) => Some(p.per_id),
+            _ => None,
+        }
+    }
+
+    pub fn selected_organizacion_id(&self) -> Option<i32> {
+        match &self.selected {
+            Some(SelectedRecord::Organizacion(o)) => Some(o.org_id),
+            _ => None,
+        }
+    }
+
+    pub fn selected_actividad_id(&self) -> Option<i32> {
+        match &self.selected {
+            Some(SelectedRecord::Actividad(a)) => Some(a.act_id),
+            _ => None,
+        }
+    }
+
+    // Procesa el resultado de una eliminación; devuelve true si se eliminó un registro
+    fn check_delete_result(&mut self) -> bool {
+        if let Some(receiver) = &mut self.delete_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.deleting = false;
+                self.delete_receiver = None;
+                match result {
+                    Ok(success_msg) => {
+                        self.close();
+                        self.message = Some((true, success_msg));
+                        return true;
+                    }
+                    Err(error_msg) => {
+                        self.message = Some((false, error_msg));
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    // Procesa el resultado de una edición; devuelve true si se guardaron cambios
+    fn check_update_result(&mut self) -> bool {
+        if let Some(receiver) = &mut self.update_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.saving = false;
+                self.update_receiver = None;
+                match result {
+                    Ok(record) => {
+                        self.selected = Some(record);
+                        self.edit_form = None;
+                        self.message = Some((true, "Cambios guardados".to_string()));
+                        return true;
+                    }
+                    Err(error_msg) => {
+                        self.message = Some((false, error_msg));
+                    }
+                }
+            }
+        }
+        false
+    }
+
+    fn save_edit(&mut self, record: &SelectedRecord) {
+        let Some(form) = &self.edit_form else {
+            return;
+        };
+        let updated = match form.apply(record, self.min_age) {
+            Ok(updated) => updated,
+            Err(error_msg) => {
+                self.message = Some((false, error_msg));
+                return;
+            }
+        };
+        self.saving = true;
+        self.message = None;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.update_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = match &updated {
+                SelectedRecord::Persona(p) => db.update_persona(p).await,
+                SelectedRecord::Organizacion(o) => db.update_organizacion(o).await,
+                SelectedRecord::Actividad(a) => db.update_actividad(a).await,
+            };
+            let result = match result {
+                Ok(0) => Err("El registro ya no existe".to_string()),
+                Ok(_) => Ok(updated),
+                Err(e) => Err(format!("Error al guardar los cambios: {}", e)),
+            };
+            let _ = tx.send(result);
+        });
+    }
+
+    // Cerrar el diálogo de archivo sin elegir uno cierra el canal sin enviar nada
+    fn check_ficha_result(&mut self) {
+        if let Some(receiver) = &mut self.ficha_receiver {
+            let result = match receiver.try_recv() {
+                Ok(result) => Some(result),
+                Err(TryRecvError::Disconnected) => None,
+                Err(TryRecvError::Empty) => return,
+            };
+            self.exporting_ficha = false;
+            self.ficha_receiver = None;
+            let mut toasts = self.toasts.lock().unwrap();
+            match result {
+                Some(Ok(success_msg)) => toasts.success(success_msg),
+                Some(Err(error_msg)) => toasts.error(error_msg),
+                None => {}
+            }
+        }
+    }
+
+    // Pide dónde guardar, reúne los vínculos de la persona y escribe el PDF fuera del hilo de la UI
+    fn export_ficha(&mut self, persona: &PersonaMayor) {
+        self.exporting_ficha = true;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.ficha_receiver = Some(rx);
+
+        let persona = persona.clone();
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let Some(handle) = rfd::AsyncFileDialog::new()
+                .set_title("Exportar ficha")
+                .set_file_name(format!("ficha_{}.pdf", persona.per_rut))
+                .add_filter("PDF", &["pdf"])
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            let path = handle.path().to_path_buf();
+
+            let ficha = match db_manager.lock().await.get_persona_ficha(persona).await {
+                Ok(ficha) => ficha,
+                Err(e) => {
+                    let _ = tx.send(Err(format!("Error al obtener los datos de la ficha: {}", e)));
+                    return;
+                }
+            };
+            let result = tokio::task::spawn_blocking(move || {
+                report::write_persona_pdf(&ficha, &path)
+                    .map(|_| format!("Ficha guardada en {}", path.display()))
+                    .map_err(|e| format!("Error al generar la ficha: {}", e))
+            }).await;
+
+            let _ = tx.send(result.unwrap_or_else(|e| Err(format!("Error al generar la ficha: {}", e))));
+        });
+    }
+
+    // Muestra el último mensaje de eliminación aunque el panel esté cerrado
+    pub fn show_message(&self, ui: &mut egui::Ui) {
+        if let Some((success, ref text)) = self.message {
+            let color = if success { egui::Color32::GREEN } else { egui::Color32::RED };
+            ui.colored_label(color, text);
+        }
+    }
+
+    // Devuelve true cuando un registro fue eliminado o modificado y los resultados deben recargarse
+    pub fn show(&mut self, ui: &mut egui::Ui) -> bool {
+        let deleted = self.check_delete_result() | self.check_update_result();
+        self.check_ficha_result();
+        if self.exporting_ficha || self.saving {
+            ui.ctx().request_repaint_after(std::time::Duration::from_millis(250));
+        }
+
+        let Some(record) = self.selected.clone() else {
+            return deleted;
+        };
+
+        ui.horizontal(|ui| {
+            ui.heading("📋 Detalle");
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("✖").on_hover_text("Cerrar").clicked() {
+                    self.close();
+                }
+            });
+        });
+        ui.separator();
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            if let Some(form) = &mut self.edit_form {
+                form.show(ui);
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    if ui.add_enabled(!self.saving, egui::Button::new("💾 Guardar")).clicked() {
+                        self.save_edit(&record);
+                    }
+                    if ui.add_enabled(!self.saving, egui::Button::new("Cancelar")).clicked() {
+                        self.edit_form = None;
+                        self.message = None;
+                    }
+                    if self.saving {
+                        ui.add(egui::widgets::Spinner::new().size(14.0));
+                    }
+                });
+                self.show_message(ui);
+                return;
+            }
+
+            match &record {
+                SelectedRecord::Persona(persona) => Self::show_persona(ui, persona),
+                SelectedRecord::Organizacion(org) => Self::show_organizacion(ui, org),
+                SelectedRecord::Actividad(actividad) => Self::show_actividad(ui, actividad),
+            }
+
+            if self.telefonos_panel.is_open() {
+                ui.add_space(15.0);
+                ui.separator();
+                self.telefonos_panel.show(ui);
+            }
+
+            if self.personas_panel.is_open() {
+                ui.add_space(15.0);
+                ui.separator();
+                self.personas_panel.show(ui);
+            }
+
+            ui.add_space(15.0);
+            ui.separator();
+            self.show_message(ui);
+
+            // Acciones sobre el registro
+            ui.horizontal(|ui| {
+                if ui.add_enabled(!self.deleting, egui::Button::new("✏ Editar")).clicked() {
+                    self.edit_form = Some(EditForm::from_record(&record));
+                    self.message = None;
+                }
+                if ui.add_enabled(!self.deleting, egui::Button::new("🗑 Eliminar")).clicked() {
+                    self.confirm_delete.open("Eliminar registro", "¿Eliminar este registro? Esta acción no se puede deshacer.");
+                }
+                if let SelectedRecord::Persona(persona) = &record {
+                    if ui.add_enabled(!self.exporting_ficha, egui::Button::new("📄 Exportar ficha PDF"))
+                        .on_hover_text("Ficha de una página con los datos, la edad y los vínculos de la persona")
+                        .clicked()
+                    {
+                        self.export_ficha(persona);
+                    }
+                }
+                if ui.button("📋 Copiar como JSON").clicked() {
+                    self.message = Some(match record.to_json() {
+                        Ok(json) => {
+                            ui.output_mut(|o| o.copied_text = json);
+                            (true, "Registro copiado al portapapeles como JSON".to_string())
+                        }
+                        Err(e) => (false, format!("No se pudo serializar el registro: {}", e)),
+                    });
+                }
+                if self.deleting || self.exporting_ficha {
+                    ui.add(egui::widgets::Spinner::new().size(14.0));
+                }
+            });
+        });
+
+        if self.confirm_delete.show(ui) == Some(true) {
+            self.delete_selected(&record);
+        }
+
+        deleted
+    }
+
+    fn field(ui: &mut egui::Ui, label: &str, value: &str) {
+        ui.strong(label);
+        ui.label(value);
+        ui.end_row();
+    }
+
+    // Fechas de registro y última modificación; la modificación solo se muestra si existe
+    fn audit_fields(ui: &mut egui::Ui, registro: &Option<NaiveDateTime>, modificacion: &Option<NaiveDateTime>) {
+        Self::field(ui, "Registrado:", &utils::format_optional_date(&registro.map(|fecha| fecha.date())));
+        if let Some(modificacion) = modificacion {
+            Self::field(ui, "Modificado:", &utils::format_date(&modificacion.date()));
+        }
+    }
+
+    fn show_persona(ui: &mut egui::Ui, persona: &PersonaMayor) {
+        egui::Grid::new("persona_detail")
+            .num_columns(2)
+            .spacing([10.0, 6.0])
+            .show(ui, |ui| {
+                Self::field(ui, "RUT:", &persona.per_rut);
+                Self::field(ui, "Primer nombre:", &persona.per_prinombre);
+                Self::field(ui, "Segundo nombre:", persona.per_segnombre.as_deref().unwrap_or("-"));
+                Self::field(ui, "Primer apellido:", &persona.per_priapellido);
+                Self::field(ui, "Segundo apellido:", persona.per_segapellido.as_deref().unwrap_or("-"));
+                Self::field(ui, "Fecha de nacimiento:", &utils::format_date(&persona.per_fechadenac));
+                Self::field(ui, "Edad:", &format!("{} años", utils::calculate_age(&persona.per_fechadenac)));
+                Self::field(ui, "Género:", persona.gen_genero.as_deref().unwrap_or("N/A"));
+                Self::field(ui, "Nacionalidad:", persona.nac_nacionalidad.as_deref().unwrap_or("N/A"));
+                Self::field(ui, "Dirección:", &persona.per_direccion);
+                Self::field(ui, "Email:", persona.per_email.as_deref().unwrap_or("-"));
+                Self::field(ui, "Unidad Vecinal:", persona.uv_nombre.as_deref().unwrap_or("N/A"));
+                Self::audit_fields(ui, &persona.per_fecha_registro, &persona.per_fecha_modificacion);
+            });
+    }
+
+    fn show_organizacion(ui: &mut egui::Ui, org: &OrganizacionComunitaria) {
+        egui::Grid::new("org_detail")
+            .num_columns(2)
+            .spacing([10.0, 6.0])
+            .show(ui, |ui| {
+                Self::field(ui, "Nombre:", &org.org_nombre);
+                Self::field(ui, "Dirección:", &org.org_direccion);
+                Self::field(ui, "Fecha constitución:", &utils::format_date(&org.org_fechaconst));
+                Self::field(ui, "Personalidad jurídica:", &org.org_perjuridica);
+                Self::field(ui, "Email:", org.org_email.as_deref().unwrap_or("-"));
+                Self::field(ui, "Unidad Vecinal:", org.uv_nombre.as_deref().unwrap_or("N/A"));
+                Self::audit_fields(ui, &org.org_fecha_registro, &org.org_fecha_modificacion);
+            });
+    }
+
+    fn show_actividad(ui: &mut egui::Ui, actividad: &Actividad) {
+        egui::Grid::new("act_detail")
+            .num_columns(2)
+            .spacing([10.0, 6.0])
+            .show(ui, |ui| {
+                Self::field(ui, "Nombre:", &actividad.act_nombre);
+                Self::field(ui, "Fecha inicio:", &utils::format_date(&actividad.act_fecha_ini));
+                Self::field(ui, "Fecha fin:", &utils::format_optional_date(&actividad.act_fecha_fin));
+                Self::field(ui, "Unidad Vecinal:", actividad.uv_nombre.as_deref().unwrap_or("N/A"));
+                Self::field(ui, "Taller:", actividad.tal_nombre.as_deref().unwrap_or("-"));
+                Self::audit_fields(ui, &actividad.act_fecha_registro, &actividad.act_fecha_modificacion);
+            });
+        ui.add_space(8.0);
+        ui.strong("Descripción:");
+        ui.label(actividad.act_descripcion.as_deref().unwrap_or("-"));
+    }
+
+    fn delete_selected(&mut self, record: &SelectedRecord) {
+        self.deleting = true;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.delete_receiver = Some(rx);
+
+        let record = record.clone();
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = match record {
+                SelectedRecord::Persona(p) => db.delete_persona(p.per_id).await
+                    .map(|_| format!("Persona {} eliminada", p.per_rut))
+                    .map_err(|e| format!("Error al eliminar persona: {}", e)),
+                SelectedRecord::Organizacion(o) => db.delete_organizacion(o.org_id).await
+                    .map(|_| format!("Organización '{}' eliminada", o.org_nombre))
+                    .map_err(|e| format!("Error al eliminar organización: {}", e)),
+                SelectedRecord::Actividad(a) => db.delete_actividad(a.act_id).await
+                    .map(|_| format!("Actividad '{}' eliminada", a.act_nombre))
+                    .map_err(|e| format!("Error al eliminar actividad: {}", e)),
+            };
+            let _ = tx.send(result);
+        });
+    }
+}
@@ -0,0 +1,33 @@
+// Diccionario de datos: explicación de cada campo, indexada por la etiqueta que se muestra.
+// Lo usan tanto los formularios de inserción como los filtros de consultas.
+const FIELD_HELP: &[(&str, &str)] = &[
+    ("RUT", "Rol Único Tributario con dígito verificador, ej. 12345678-9 o 12.345.678-K. Se guarda sin puntos."),
+    ("Género", "Género con que se registra la persona. Las opciones se administran en Catálogos."),
+    ("Nacionalidad", "Nacionalidad de la persona. Las opciones se administran en Catálogos."),
+    ("Fecha de Nacimiento", "Formato dd/mm/aaaa o aaaa-mm-dd. Debe cumplir la edad mínima configurada para personas mayores."),
+    ("Dirección", "Calle, número y villa o población donde vive o funciona."),
+    ("Email", "Correo de contacto, ej. nombre@dominio.cl. Es opcional."),
+    ("Macrosector", "Agrupación de varias unidades vecinales. Elegirlo filtra la lista de unidades vecinales."),
+    ("Unidad Vecinal", "Territorio vecinal (UV) al que pertenece el registro. Cada UV forma parte de un macrosector."),
+    ("Personalidad Jurídica", "Número de registro de la organización, ej. 1234 o 1234-R. Se aceptan prefijos como \"PJ\" o \"N°\"."),
+    ("Fecha Constitución", "Fecha en que se constituyó la organización, en formato dd/mm/aaaa o aaaa-mm-dd."),
+    ("Fecha Inicio", "Primer día de la actividad, en formato dd/mm/aaaa o aaaa-mm-dd."),
+    ("Fecha Fin", "Último día de la actividad; no puede ser anterior al inicio. Vacío si no tiene término definido."),
+    ("Taller", "Taller al que pertenece la actividad, si corresponde."),
+    ("Código", "Código corto que identifica el beneficio en reportes y exportaciones."),
+    ("Constituida desde", "Muestra organizaciones constituidas en esta fecha o después (dd/mm/aaaa)."),
+    ("Constituida hasta", "Muestra organizaciones constituidas en esta fecha o antes (dd/mm/aaaa)."),
+    ("Inicio desde", "Muestra actividades que comienzan en esta fecha o después (dd/mm/aaaa)."),
+    ("Inicio hasta", "Muestra actividades que comienzan en esta fecha o antes (dd/mm/aaaa)."),
+    ("Estado", "Próxima: aún no comienza. En curso: comenzó y no ha terminado. Finalizada: su fecha de fin ya pasó."),
+    ("Destino", "Lugar de destino del viaje; basta con parte del nombre."),
+    ("Salida desde", "Muestra viajes que salen en esta fecha o después (dd/mm/aaaa)."),
+    ("Salida hasta", "Muestra viajes que salen en esta fecha o antes (dd/mm/aaaa)."),
+];
+
+// Texto de ayuda de un campo, si existe
+pub fn help_for(label: &str) -> Option<&'static str> {
+    FIELD_HELP.iter()
+        .find(|(field, _)| *field == label)
+        .map(|(_, help)| *help)
+}
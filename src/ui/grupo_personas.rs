@@ -0,0 +1,254 @@
+use eframe::egui;
+use std::sync::Arc;
+use tokio::sync::{Mutex, mpsc};
+use crate::database::DatabaseManager;
+use crate::models::PersonaMayor;
+use crate::utils;
+
+// Grupo de personas asociado a un registro: miembros de una organización
+// o participantes de una actividad
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GrupoPersonas {
+    Miembros,
+    Participantes,
+}
+
+impl GrupoPersonas {
+    fn titulo(&self) -> &'static str {
+        match self {
+            GrupoPersonas::Miembros => "👥 Miembros de",
+            GrupoPersonas::Participantes => "🙋 Participantes de",
+        }
+    }
+
+    fn singular(&self) -> &'static str {
+        match self {
+            GrupoPersonas::Miembros => "miembro",
+            GrupoPersonas::Participantes => "participante",
+        }
+    }
+
+    fn plural(&self) -> &'static str {
+        match self {
+            GrupoPersonas::Miembros => "miembros",
+            GrupoPersonas::Participantes => "participantes",
+        }
+    }
+}
+
+// Sub-panel con las personas de una organización o actividad
+pub struct GrupoPersonasPanel {
+    db_manager: Arc<Mutex<DatabaseManager>>,
+
+    // Registro seleccionado: (grupo, id, nombre a mostrar)
+    registro: Option<(GrupoPersonas, i32, String)>,
+    personas: Vec<PersonaMayor>,
+
+    // RUT de la persona a agregar
+    nuevo_rut: String,
+
+    // Estado
+    loading: bool,
+    message: Option<(bool, String)>,
+
+    // Canales asíncronos
+    list_receiver: Option<mpsc::UnboundedReceiver<Result<Vec<PersonaMayor>, String>>>,
+    action_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+}
+
+impl GrupoPersonasPanel {
+    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>) -> Self {
+        Self {
+            db_manager,
+            registro: None,
+            personas: Vec::new(),
+            nuevo_rut: String::new(),
+            loading: false,
+            message: None,
+            list_receiver: None,
+            action_receiver: None,
+        }
+    }
+
+    pub fn open(&mut self, grupo: GrupoPersonas, id: i32, nombre: String) {
+        self.registro = Some((grupo, id, nombre));
+        self.personas.clear();
+        self.nuevo_rut.clear();
+        self.message = None;
+        self.load_personas();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.registro.is_some()
+    }
+
+    pub fn close(&mut self) {
+        self.registro = None;
+        self.list_receiver = None;
+        self.action_receiver = None;
+        self.loading = false;
+    }
+
+    fn check_results(&mut self) {
+        if let Some(receiver) = &mut self.list_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.loading = false;
+                self.list_receiver = None;
+                match result {
+                    Ok(personas) => self.personas = personas,
+                    Err(error_msg) => self.message = Some((false, error_msg)),
+                }
+            }
+        }
+
+        if let Some(receiver) = &mut self.action_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.action_receiver = None;
+                match result {
+                    Ok(success_msg) => {
+                        self.message = Some((true, success_msg));
+                        self.nuevo_rut.clear();
+                        self.load_personas();
+                    }
+                    Err(error_msg) => {
+                        self.loading = false;
+                        self.message = Some((false, error_msg));
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        self.check_results();
+
+        let Some((grupo, _, nombre)) = self.registro.clone() else {
+            return;
+        };
+
+        ui.label(egui::RichText::new(format!("{} {}", grupo.titulo(), nombre)).strong());
+        ui.add_space(5.0);
+
+        if let Some((success, ref text)) = self.message {
+            let color = if success { egui::Color32::GREEN } else { egui::Color32::RED };
+            ui.colored_label(color, text);
+        }
+
+        // Personas registradas en el grupo
+        let mut to_remove = None;
+        if self.personas.is_empty() && !self.loading {
+            ui.label(format!("Sin {} registrados", grupo.plural()));
+        } else {
+            egui::Grid::new("grupo_personas_list")
+                .striped(true)
+                .spacing([10.0, 6.0])
+                .show(ui, |ui| {
+                    for persona in &self.personas {
+                        ui.label(&persona.per_rut);
+                        ui.label(format!("{} {}", persona.per_prinombre, persona.per_priapellido));
+                        if ui.small_button("✖").on_hover_text(format!("Quitar {}", grupo.singular())).clicked() {
+                            to_remove = Some(persona.clone());
+                        }
+                        ui.end_row();
+                    }
+                });
+        }
+
+        if let Some(persona) = to_remove {
+            self.remove_persona(persona);
+        }
+
+        ui.add_space(8.0);
+
+        // Agregar persona por RUT
+        ui.horizontal(|ui| {
+            let response = ui.add(egui::TextEdit::singleline(&mut self.nuevo_rut).hint_text("RUT 12345678-9").desired_width(140.0));
+            let valid = utils::validate_rut_check_digit(&utils::normalize_rut(&self.nuevo_rut));
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            if (ui.add_enabled(valid && !self.loading, egui::Button::new(format!("➕ Agregar {}", grupo.singular()))).clicked() || submitted)
+                && valid && !self.loading
+            {
+                self.add_persona();
+            }
+            if self.loading {
+                ui.add(egui::widgets::Spinner::new().size(14.0));
+            }
+        });
+    }
+
+    fn load_personas(&mut self) {
+        let Some((grupo, id, _)) = self.registro.clone() else {
+            return;
+        };
+        self.loading = true;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.list_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = match grupo {
+                GrupoPersonas::Miembros => db.get_miembros(id).await,
+                GrupoPersonas::Participantes => db.get_participantes(id).await,
+            };
+            let result = result.map_err(|e| format!("Error al cargar {}: {}", grupo.plural(), e));
+            let _ = tx.send(result);
+        });
+    }
+
+    fn add_persona(&mut self) {
+        let Some((grupo, id, _)) = self.registro.clone() else {
+            return;
+        };
+        self.loading = true;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.action_receiver = Some(rx);
+
+        let rut = self.nuevo_rut.trim().to_string();
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = match db.get_persona_by_rut(&rut).await {
+                Ok(Some(persona)) => {
+                    let added = match grupo {
+                        GrupoPersonas::Miembros => db.add_miembro(id, persona.per_id).await,
+                        GrupoPersonas::Participantes => db.add_participante(id, persona.per_id).await,
+                    };
+                    match added {
+                        Ok(_) => Ok(format!("{} {} agregado como {}", persona.per_prinombre, persona.per_priapellido, grupo.singular())),
+                        Err(e) => Err(format!("Error al agregar {}: {}", grupo.singular(), e)),
+                    }
+                }
+                Ok(None) => Err(format!("No existe una persona con RUT {}", utils::normalize_rut(&rut))),
+                Err(e) => Err(format!("Error al buscar la persona: {}", e)),
+            };
+            let _ = tx.send(result);
+        });
+    }
+
+    fn remove_persona(&mut self, persona: PersonaMayor) {
+        let Some((grupo, id, _)) = self.registro.clone() else {
+            return;
+        };
+        self.loading = true;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.action_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let removed = match grupo {
+                GrupoPersonas::Miembros => db.remove_miembro(id, persona.per_id).await,
+                GrupoPersonas::Participantes => db.remove_participante(id, persona.per_id).await,
+            };
+            let result = match removed {
+                Ok(_) => Ok(format!("{} {} ya no es {}", persona.per_prinombre, persona.per_priapellido, grupo.singular())),
+                Err(e) => Err(format!("Error al quitar {}: {}", grupo.singular(), e)),
+            };
+            let _ = tx.send(result);
+        });
+    }
+}
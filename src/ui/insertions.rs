@@ -1,62 +1,186 @@
 use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, mpsc};
+use crate::config::{AppConfig, PersonaDefaults};
 use crate::database::DatabaseManager;
 use crate::models::*;
-
-// Función para formatear RUT automáticamente
+use crate::ui::catalogs::{Catalogs, SharedCatalogs};
+use crate::ui::components::{self, field_hint, ConfirmDialog};
+use crate::ui::persona_import::PersonaImportDialog;
+use crate::ui::recent_records::RecentRecordsPanel;
+use crate::ui::queries::QueryType;
+use crate::utils;
+
+// Tipo enviado, mensaje e ID del registro insertado
+type InsertionOutcome = (InsertionType, String, i32);
+
+// Formatea un RUT mientras se escribe: "12.345.678k" -> "12345678-K". Es estable sobre su
+// propia salida, así que un RUT ya formateado no cambia ("12345678-9" sigue igual).
+// Solo cuentan los dígitos ASCII y la K; otros dígitos Unicode (p. ej. "²") se descartan
+// y la K solo vale como dígito verificador, por lo que una K intermedia se elimina.
+// Sin número delante se devuelve solo el verificador ("K" -> "K", "5K" -> "5-K").
 fn format_rut(input: &str) -> String {
-    // Remover todo excepto números y K/k
-    let clean: String = input.chars()
-        .filter(|c| c.is_numeric() || c.to_uppercase().next() == Some('K'))
+    let significant: Vec<char> = input.chars()
+        .filter(|c| c.is_ascii_digit() || c.eq_ignore_ascii_case(&'k'))
+        .map(|c| c.to_ascii_uppercase())
         .collect();
-    
-    if clean.is_empty() {
+    let Some((dv, numero)) = significant.split_last() else {
         return String::new();
+    };
+    let numero: String = numero.iter().filter(|c| c.is_ascii_digit()).collect();
+    if numero.is_empty() {
+        return dv.to_string();
     }
-    
-    // Si es muy corto, devolver como está
-    if clean.len() < 2 {
-        return clean;
+    format!("{}-{}", numero, dv)
+}
+
+// Campo de RUT con formato progresivo: el guión del verificador aparece mientras se
+// escribe y el cursor se mantiene después del mismo dígito que antes de formatear
+fn rut_input(ui: &mut egui::Ui, rut: &mut String) {
+    let mut output = egui::TextEdit::singleline(rut).show(ui);
+    if !output.response.changed() {
+        return;
     }
-    
-    // Separar número del dígito verificador
-    let (numero, dv) = clean.split_at(clean.len() - 1);
-    
-    // Formatear solo si tenemos al menos 1 dígito más el verificador
-    if numero.is_empty() {
-        return clean;
+
+    let is_significant = |c: char| c.is_ascii_digit() || c.eq_ignore_ascii_case(&'k');
+    let cursor = output.cursor_range
+        .map(|range| range.primary.ccursor.index)
+        .unwrap_or_else(|| rut.chars().count());
+    let significant_before = rut.chars().take(cursor).filter(|c| is_significant(*c)).count();
+
+    let formatted = format_rut(rut);
+    if formatted == *rut {
+        return;
     }
-    
-    format!("{}-{}", numero, dv.to_uppercase())
+
+    // Posición en el texto formateado tras `significant_before` caracteres significativos
+    let mut new_cursor = 0;
+    let mut seen = 0;
+    for c in formatted.chars() {
+        if seen == significant_before {
+            break;
+        }
+        new_cursor += 1;
+        if is_significant(c) {
+            seen += 1;
+        }
+    }
+
+    *rut = formatted;
+    output.state.cursor.set_char_range(Some(egui::text::CCursorRange::one(egui::text::CCursor::new(new_cursor))));
+    output.state.store(ui.ctx(), output.response.id);
 }
 
-// Función para validar formato RUT chileno
-fn validate_rut_format(rut: &str) -> bool {
-    // Patrón: 7-8 dígitos, guión, dígito verificador (0-9 o K)
-    let re = regex::Regex::new(r"^[0-9]{7,8}-[0-9Kk]$").unwrap();
-    re.is_match(rut)
+// Errores por campo del formulario de persona, calculados en cada frame
+#[derive(Debug, Default)]
+struct PersonaFieldErrors {
+    rut: Option<&'static str>,
+    email: Option<&'static str>,
+    fecha_nacimiento: Option<String>,
+}
+
+impl PersonaFieldErrors {
+    fn is_empty(&self) -> bool {
+        self.rut.is_none() && self.email.is_none() && self.fecha_nacimiento.is_none()
+    }
 }
 
-// Tipos de actualizaciones de catálogo
-#[derive(Debug, Clone)]
-enum CatalogUpdate {
-    Generos(Vec<Genero>),
-    Nacionalidades(Vec<Nacionalidad>),
-    UnidadesVecinales(Vec<UnidadVecinal>),
-    MacroSectores(Vec<MacroSector>),
+// Error de un campo de fecha con contenido que no es una fecha válida
+fn date_field_error(value: &str) -> Option<&'static str> {
+    if !value.trim().is_empty() && utils::parse_date(value).is_none() {
+        Some("Fecha inválida, use dd/mm/yyyy")
+    } else {
+        None
+    }
+}
+
+// Error de una personalidad jurídica escrita que no sigue el formato esperado
+fn personalidad_juridica_error(value: &str) -> Option<&'static str> {
+    if !value.trim().is_empty() && utils::normalize_personalidad_juridica(value).is_none() {
+        Some("Formato inválido, use 1234 o 1234-R")
+    } else {
+        None
+    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
-enum InsertionType {
+// Botón de guardar que queda deshabilitado mientras falten campos obligatorios;
+// el tooltip indica cuáles faltan
+fn save_button(ui: &mut egui::Ui, label: &str, missing: &[&str], has_errors: bool) -> bool {
+    let mut reasons = Vec::new();
+    if !missing.is_empty() {
+        reasons.push(format!("Campos obligatorios pendientes: {}", missing.join(", ")));
+    }
+    if has_errors {
+        reasons.push("Corrija los campos marcados en rojo".to_string());
+    }
+
+    ui.add_enabled(reasons.is_empty(), egui::Button::new(label))
+        .on_disabled_hover_text(reasons.join("\n"))
+        .clicked()
+}
+
+// Botón de limpiar: si el formulario tiene datos pide confirmación antes de borrarlos.
+// Devuelve true cuando el formulario debe limpiarse.
+fn clear_button(ui: &mut egui::Ui, label: &str, has_data: bool, confirm: &mut bool) -> bool {
+    if !*confirm {
+        if ui.button(label).clicked() {
+            if has_data {
+                *confirm = true;
+            } else {
+                return true;
+            }
+        }
+        return false;
+    }
+
+    ui.label("¿Descartar los datos ingresados?");
+    if ui.button("Sí, limpiar").clicked() {
+        *confirm = false;
+        return true;
+    }
+    if ui.button("Cancelar").clicked() {
+        *confirm = false;
+    }
+    false
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum InsertionType {
     Persona,
     Organizacion,
     Actividad,
     MacroSector,
     UnidadVecinal,
     Taller,
+    Beneficio,
+    Centro,
 }
 
+impl InsertionType {
+    // Consulta cuyos resultados cambian al insertar este tipo. Macrosectores y unidades
+    // vecinales no tienen una propia: sus nombres aparecen en varios listados.
+    pub fn query_type(&self) -> Option<QueryType> {
+        match self {
+            InsertionType::Persona => Some(QueryType::Personas),
+            InsertionType::Organizacion => Some(QueryType::Organizaciones),
+            InsertionType::Actividad => Some(QueryType::Actividades),
+            InsertionType::Taller => Some(QueryType::Talleres),
+            InsertionType::Beneficio => Some(QueryType::Beneficios),
+            InsertionType::Centro => Some(QueryType::Centros),
+            InsertionType::MacroSector | InsertionType::UnidadVecinal => None,
+        }
+    }
+}
+
+// Cantidad de UV recientes ofrecidas como acceso rápido
+const MAX_RECENT_UVS: usize = 5;
+
+// Tiempo durante el cual se puede deshacer la última inserción
+const UNDO_WINDOW: Duration = Duration::from_secs(10);
+
 pub struct InsertionsView {
     db_manager: Arc<Mutex<DatabaseManager>>,
     insertion_type: InsertionType,
@@ -68,25 +192,50 @@ pub struct InsertionsView {
     macro_sector_form: MacroSectorForm,
     unidad_vecinal_form: UnidadVecinalForm,
     taller_form: TallerForm,
+    beneficio_form: BeneficioForm,
+    centro_form: CentroForm,
     
-    // Catálogos
+    // Catálogos (copia local de los catálogos compartidos)
+    catalogs: SharedCatalogs,
+    catalogs_version: u64,
+    catalogs_loaded: bool,
     generos: Vec<Genero>,
     nacionalidades: Vec<Nacionalidad>,
     unidades_vecinales: Vec<UnidadVecinal>,
     macro_sectores: Vec<MacroSector>,
+    talleres: Vec<Taller>,
+    // Macrosector usado para acotar las UV ofrecidas en los formularios
+    uv_macro_filter: Option<i32>,
+    persona_defaults: PersonaDefaults,
+    // UV usadas en las últimas inserciones exitosas (la más reciente primero)
+    recent_uvs: VecDeque<i32>,
+    // Edad mínima de las personas registradas (configurable en app_config.json)
+    min_age: i32,
+    pending_recent_uv: Option<i32>,
     
     // Estado
     loading: bool,
-    catalogs_loaded: bool,
+    // Confirmación pendiente para limpiar un formulario con datos
+    confirm_clear: bool,
     
-    // Canales asíncronos para inserciones
-    insertion_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+    // Canales asíncronos para inserciones (mensaje, id asignado)
+    insertion_receiver: Option<mpsc::UnboundedReceiver<Result<InsertionOutcome, String>>>,
     
-    // Canales para cargar catálogos
-    catalog_receiver: Option<mpsc::UnboundedReceiver<CatalogUpdate>>,
+    // Última inserción exitosa que aún puede deshacerse
+    last_insertion: Option<(InsertionType, i32, Instant)>,
+    undo_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+
+    // Importación masiva de personas desde CSV
+    persona_import: PersonaImportDialog,
+    recent_records: RecentRecordsPanel,
+
+    // Aviso de nombre repetido antes de guardar macrosectores y organizaciones
+    duplicate_confirm: ConfirmDialog,
+    duplicate_pending: Option<InsertionType>,
+    duplicate_receiver: Option<mpsc::UnboundedReceiver<(InsertionType, Result<NameMatch, String>)>>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct PersonaForm {
     rut: String,
     primer_nombre: String,
@@ -101,7 +250,7 @@ struct PersonaForm {
     unidad_vecinal_id: Option<i32>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct OrganizacionForm {
     nombre: String,
     direccion: String,
@@ -111,50 +260,83 @@ struct OrganizacionForm {
     unidad_vecinal_id: Option<i32>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct ActividadForm {
     nombre: String,
     fecha_inicio: String,
     fecha_fin: String,
     descripcion: String,
     unidad_vecinal_id: Option<i32>,
+    taller_id: Option<i32>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct MacroSectorForm {
     nombre: String,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct UnidadVecinalForm {
     nombre: String,
     macro_sector_id: Option<i32>,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 struct TallerForm {
     nombre: String,
 }
 
+#[derive(Debug, Clone, Default, PartialEq)]
+struct CentroForm {
+    nombre: String,
+    direccion: String,
+    unidad_vecinal_id: Option<i32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct BeneficioForm {
+    codigo: String,
+    descripcion: String,
+}
+
 impl InsertionsView {
-    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>) -> Self {
+    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>, catalogs: SharedCatalogs) -> Self {
+        let config = AppConfig::load();
+        let min_age = config.min_age();
         Self {
+            persona_import: PersonaImportDialog::new(db_manager.clone()),
+            recent_records: RecentRecordsPanel::new(db_manager.clone()),
             db_manager,
-            insertion_type: InsertionType::Persona,
+            insertion_type: config.insertion_type.unwrap_or(InsertionType::Persona),
             persona_form: PersonaForm::default(),
+            persona_defaults: config.persona_defaults,
             organizacion_form: OrganizacionForm::default(),
             actividad_form: ActividadForm::default(),
             macro_sector_form: MacroSectorForm::default(),
             unidad_vecinal_form: UnidadVecinalForm::default(),
             taller_form: TallerForm::default(),
+            beneficio_form: BeneficioForm::default(),
+            centro_form: CentroForm::default(),
+            catalogs,
+            catalogs_version: 0,
+            catalogs_loaded: false,
             generos: Vec::new(),
             nacionalidades: Vec::new(),
             unidades_vecinales: Vec::new(),
             macro_sectores: Vec::new(),
+            talleres: Vec::new(),
+            uv_macro_filter: None,
+            min_age,
+            recent_uvs: config.recent_uvs.into_iter().take(MAX_RECENT_UVS).collect(),
+            pending_recent_uv: None,
             loading: false,
-            catalogs_loaded: false,
+            confirm_clear: false,
             insertion_receiver: None,
-            catalog_receiver: None,
+            last_insertion: None,
+            undo_receiver: None,
+            duplicate_confirm: ConfirmDialog::new(),
+            duplicate_pending: None,
+            duplicate_receiver: None,
         }
     }
 
@@ -164,23 +346,33 @@ impl InsertionsView {
                 self.loading = false;
                 self.insertion_receiver = None;
                 match result {
-                    Ok(success_msg) => {
-                        // Limpiar formulario correspondiente después del éxito
-                        match self.insertion_type {
-                            InsertionType::Persona => self.persona_form = PersonaForm::default(),
+                    Ok((insertion_type, success_msg, id)) => {
+                        self.recent_records.invalidate();
+
+                        if let Some(uv_id) = self.pending_recent_uv.take() {
+                            self.remember_uv(uv_id);
+                        }
+
+                        // Limpiar el formulario del tipo que se envió, aunque el usuario haya cambiado de tipo
+                        match insertion_type {
+                            InsertionType::Persona => self.persona_form = self.default_persona_form(),
                             InsertionType::Organizacion => self.organizacion_form = OrganizacionForm::default(),
                             InsertionType::Actividad => self.actividad_form = ActividadForm::default(),
                             InsertionType::MacroSector => self.macro_sector_form = MacroSectorForm::default(),
                             InsertionType::UnidadVecinal => self.unidad_vecinal_form = UnidadVecinalForm::default(),
                             InsertionType::Taller => self.taller_form = TallerForm::default(),
+                            InsertionType::Beneficio => self.beneficio_form = BeneficioForm::default(),
+                            InsertionType::Centro => self.centro_form = CentroForm::default(),
                         }
+                        self.last_insertion = Some((insertion_type, id, Instant::now()));
                         
                         // Refrescar catálogos después de inserción exitosa
-                        self.load_catalogs();
+                        Catalogs::refresh(&self.catalogs, &self.db_manager);
                         
                         return Some((true, success_msg));
                     }
                     Err(error_msg) => {
+                        self.pending_recent_uv = None;
                         return Some((false, error_msg));
                     }
                 }
@@ -189,49 +381,208 @@ impl InsertionsView {
         None
     }
 
-    // Función para procesar actualizaciones de catálogo
-    pub fn check_catalog_updates(&mut self) {
-        if let Some(receiver) = &mut self.catalog_receiver {
-            while let Ok(update) = receiver.try_recv() {
-                match update {
-                    CatalogUpdate::Generos(generos) => {
-                        self.generos = generos;
-                    }
-                    CatalogUpdate::Nacionalidades(nacionalidades) => {
-                        self.nacionalidades = nacionalidades;
-                    }
-                    CatalogUpdate::UnidadesVecinales(unidades) => {
-                        self.unidades_vecinales = unidades;
+    fn check_undo_result(&mut self) -> Option<(bool, String)> {
+        if let Some(receiver) = &mut self.undo_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.undo_receiver = None;
+                return match result {
+                    Ok(success_msg) => {
+                        Catalogs::refresh(&self.catalogs, &self.db_manager);
+                        self.recent_records.invalidate();
+                        Some((true, success_msg))
                     }
-                    CatalogUpdate::MacroSectores(sectores) => {
-                        self.macro_sectores = sectores;
+                    Err(error_msg) => Some((false, error_msg)),
+                };
+            }
+        }
+        None
+    }
+
+    fn undo_last_insertion(&mut self) {
+        let Some((insertion_type, id, _)) = self.last_insertion.take() else {
+            return;
+        };
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.undo_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = match insertion_type {
+                InsertionType::Persona => db.delete_persona(id).await,
+                InsertionType::Organizacion => db.delete_organizacion(id).await,
+                InsertionType::Actividad => db.delete_actividad(id).await,
+                InsertionType::MacroSector => db.delete_macro_sector(id).await,
+                InsertionType::UnidadVecinal => db.delete_unidad_vecinal(id).await,
+                InsertionType::Taller => db.delete_taller(id).await,
+                InsertionType::Beneficio => db.delete_beneficio(id).await,
+                InsertionType::Centro => db.delete_centro(id).await,
+            };
+            let message = match result {
+                Ok(0) => Err(format!("El registro con ID {} ya no existe", id)),
+                Ok(_) => Ok(format!("Inserción deshecha (ID: {})", id)),
+                Err(e) => Err(format!("Error al deshacer inserción: {}", e)),
+            };
+            let _ = tx.send(message);
+        });
+    }
+
+    fn remember_uv(&mut self, uv_id: i32) {
+        self.recent_uvs.retain(|id| *id != uv_id);
+        self.recent_uvs.push_front(uv_id);
+        self.recent_uvs.truncate(MAX_RECENT_UVS);
+
+        let recent_uvs: Vec<i32> = self.recent_uvs.iter().copied().collect();
+        AppConfig::update(|config| config.recent_uvs = recent_uvs);
+    }
+
+    // Selector de UV con accesos rápidos a las UV usadas recientemente
+    fn uv_selector(
+        ui: &mut egui::Ui,
+        id: &str,
+        selected: &mut Option<i32>,
+        uvs: &[UnidadVecinal],
+        recent_uvs: &VecDeque<i32>,
+        macro_filter: Option<i32>,
+    ) {
+        ui.vertical(|ui| {
+            let recientes: Vec<&UnidadVecinal> = recent_uvs.iter()
+                .filter_map(|uv_id| uvs.iter().find(|u| u.uv_id == *uv_id))
+                .filter(|uv| macro_filter.is_none_or(|mac_id| uv.uv_macid == mac_id))
+                .collect();
+            if !recientes.is_empty() {
+                ui.horizontal_wrapped(|ui| {
+                    ui.small("Recientes:");
+                    for uv in recientes {
+                        if ui.selectable_label(*selected == Some(uv.uv_id), &uv.uv_nombre).clicked() {
+                            *selected = Some(uv.uv_id);
+                        }
                     }
-                }
+                });
             }
+            components::uv_combo(ui, id, selected, uvs, macro_filter, "Seleccionar...");
+        });
+    }
+
+    fn on_insertion_type_changed(&mut self) {
+        self.confirm_clear = false;
+        let insertion_type = self.insertion_type.clone();
+        AppConfig::update(|config| config.insertion_type = Some(insertion_type));
+    }
+
+    // Con un catálogo vacío el formulario no se puede completar (p. ej. en una base nueva):
+    // se avisa bajo el campo y se ofrece ir al formulario que crea el primer registro
+    fn empty_catalog_hint(&mut self, ui: &mut egui::Ui, target: InsertionType) {
+        let (empty, message, link) = match target {
+            InsertionType::MacroSector => (self.macro_sectores.is_empty(), "No hay macrosectores —", "crear uno primero"),
+            InsertionType::UnidadVecinal => (self.unidades_vecinales.is_empty(), "No hay unidades vecinales —", "crear una primero"),
+            _ => return,
+        };
+        // Mientras los catálogos no se cargan, una lista vacía no significa nada
+        if !empty || !self.catalogs_loaded {
+            return;
+        }
+        ui.horizontal_wrapped(|ui| {
+            ui.label(egui::RichText::new(message).small().color(egui::Color32::YELLOW));
+            if ui.link(egui::RichText::new(link).small()).clicked() {
+                self.insertion_type = target;
+                self.on_insertion_type_changed();
+            }
+        });
+    }
+
+    // Copia los catálogos compartidos cuando cambian
+    fn sync_catalogs(&mut self) {
+        let catalogs = self.catalogs.lock().unwrap();
+        self.catalogs_loaded = catalogs.loaded_at.is_some();
+        if catalogs.version != self.catalogs_version {
+            // Un formulario sin tocar se vuelve a pre-llenar con los catálogos nuevos
+            let untouched = self.persona_form == self.default_persona_form();
+            self.catalogs_version = catalogs.version;
+            self.generos = catalogs.generos.clone();
+            self.nacionalidades = catalogs.nacionalidades.clone();
+            self.unidades_vecinales = catalogs.unidades_vecinales.clone();
+            self.macro_sectores = catalogs.macro_sectores.clone();
+            self.talleres = catalogs.talleres.clone();
+            if untouched {
+                self.persona_form = self.default_persona_form();
+            }
+        }
+    }
+
+    // Formulario de persona vacío con los valores por defecto que aún existen en los catálogos
+    fn default_persona_form(&self) -> PersonaForm {
+        let defaults = &self.persona_defaults;
+        PersonaForm {
+            genero_id: defaults.genero_id.filter(|id| self.generos.iter().any(|g| g.gen_id == *id)),
+            nacionalidad_id: defaults.nacionalidad_id.filter(|id| self.nacionalidades.iter().any(|n| n.nac_id == *id)),
+            unidad_vecinal_id: defaults.unidad_vecinal_id.filter(|id| self.unidades_vecinales.iter().any(|u| u.uv_id == *id)),
+            ..PersonaForm::default()
         }
     }
 
+    fn show_persona_defaults(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new("⚙ Valores por defecto")
+            .id_source("persona_defaults")
+            .show(ui, |ui| {
+                ui.small("Se pre-llenan en cada persona nueva; útil al registrar un barrio completo.");
+                ui.add_space(5.0);
+                let before = self.default_persona_form();
+                let mut defaults = self.persona_defaults;
+                let mut changed = false;
+                egui::Grid::new("persona_defaults_grid")
+                    .num_columns(2)
+                    .spacing([15.0, 8.0])
+                    .show(ui, |ui| {
+                        components::field_label(ui, "Género");
+                        changed |= components::optional_combo(ui, "default_genero", &mut defaults.genero_id, &self.generos,
+                            |g| (g.gen_id, g.gen_genero.as_str()), "Sin valor por defecto");
+                        ui.end_row();
+
+                        components::field_label(ui, "Nacionalidad");
+                        changed |= components::optional_combo(ui, "default_nacionalidad", &mut defaults.nacionalidad_id, &self.nacionalidades,
+                            |n| (n.nac_id, n.nac_nacionalidad.as_str()), "Sin valor por defecto");
+                        ui.end_row();
+
+                        components::field_label(ui, "Unidad Vecinal");
+                        changed |= components::uv_combo(ui, "default_uv", &mut defaults.unidad_vecinal_id, &self.unidades_vecinales, None, "Sin valor por defecto");
+                        ui.end_row();
+                    });
+
+                if changed {
+                    self.persona_defaults = defaults;
+                    AppConfig::update(|config| config.persona_defaults = defaults);
+                    // Si el formulario no se ha tocado, se aplican los nuevos valores de inmediato
+                    if self.persona_form == before {
+                        self.persona_form = self.default_persona_form();
+                    }
+                }
+            });
+    }
+
     pub fn show(&mut self, ui: &mut egui::Ui) -> Option<(bool, String)> {
         // Check for async insertion results
         if let Some((success, message)) = self.check_insertion_result() {
             return Some((success, message));
         }
+        if let Some((success, message)) = self.check_undo_result() {
+            return Some((success, message));
+        }
+        if let Some(error_msg) = self.check_duplicate_result() {
+            return Some((false, error_msg));
+        }
         
-        // Check for catalog updates
-        self.check_catalog_updates();
+        self.sync_catalogs();
+        let import_result = self.persona_import.show(ui.ctx(), &self.generos, &self.nacionalidades, &self.unidades_vecinales);
 
         ui.heading("➕ Inserción de Datos");
         ui.add_space(10.0);
 
-        // Cargar catálogos solo una vez al inicio
-        if !self.catalogs_loaded {
-            self.catalogs_loaded = true;
-            self.load_catalogs();
-        }
-
         // Selector de tipo de inserción
         ui.horizontal(|ui| {
             ui.label("Tipo de registro:");
+            let previous_insertion_type = self.insertion_type.clone();
             egui::ComboBox::from_id_source("insertion_type")
                 .selected_text(format!("{:?}", self.insertion_type))
                 .show_ui(ui, |ui| {
@@ -241,7 +592,50 @@ impl InsertionsView {
                     ui.selectable_value(&mut self.insertion_type, InsertionType::MacroSector, "Macrosector");
                     ui.selectable_value(&mut self.insertion_type, InsertionType::UnidadVecinal, "Unidad Vecinal");
                     ui.selectable_value(&mut self.insertion_type, InsertionType::Taller, "Taller");
+                    ui.selectable_value(&mut self.insertion_type, InsertionType::Beneficio, "Beneficio");
+                    ui.selectable_value(&mut self.insertion_type, InsertionType::Centro, "Centro Comunitario");
                 });
+
+            if previous_insertion_type != self.insertion_type {
+                self.on_insertion_type_changed();
+            }
+
+            // Deshacer la última inserción mientras dure la ventana
+            if let Some((_, _, saved_at)) = self.last_insertion {
+                let elapsed = saved_at.elapsed();
+                if elapsed < UNDO_WINDOW {
+                    let remaining = (UNDO_WINDOW - elapsed).as_secs() + 1;
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let button = egui::Button::new(format!("↩️ Deshacer última inserción ({}s)", remaining));
+                        if ui.add_enabled(self.undo_receiver.is_none(), button).clicked() {
+                            self.undo_last_insertion();
+                        }
+                    });
+                    ui.ctx().request_repaint_after(Duration::from_millis(250));
+                } else {
+                    self.last_insertion = None;
+                }
+            }
+        });
+
+        // Recarga manual de catálogos (p. ej. macrosectores creados desde otra sesión)
+        ui.horizontal(|ui| {
+            let (loaded_at, refreshing, error) = {
+                let catalogs = self.catalogs.lock().unwrap();
+                (catalogs.loaded_at, catalogs.refreshing, catalogs.error.clone())
+            };
+            if ui.add_enabled(!refreshing, egui::Button::new("🔄 Recargar catálogos")).clicked() {
+                Catalogs::refresh(&self.catalogs, &self.db_manager);
+            }
+            if refreshing {
+                ui.add(egui::widgets::Spinner::new().size(12.0));
+                ui.ctx().request_repaint_after(Duration::from_millis(250));
+            } else if let Some(error) = error {
+                // Sin catálogos las listas desplegables quedarían vacías sin explicación
+                ui.colored_label(egui::Color32::RED, format!("⚠ No se pudieron cargar los catálogos: {}", error));
+            } else if let Some(loaded_at) = loaded_at {
+                ui.small(format!("Catálogos cargados a las {}", loaded_at.format("%H:%M:%S")));
+            }
         });
 
         ui.add_space(15.0);
@@ -255,143 +649,241 @@ impl InsertionsView {
                 InsertionType::MacroSector => self.show_macro_sector_form(ui),
                 InsertionType::UnidadVecinal => self.show_unidad_vecinal_form(ui),
                 InsertionType::Taller => self.show_taller_form(ui),
+                InsertionType::Beneficio => self.show_beneficio_form(ui),
+                InsertionType::Centro => self.show_centro_form(ui),
             }
+
+            // Verificación de lo recién ingresado
+            ui.add_space(15.0);
+            self.recent_records.show(ui);
         });
+
+        match self.duplicate_confirm.show(ui) {
+            Some(true) => {
+                if let Some(insertion_type) = self.duplicate_pending.take() {
+                    self.save_after_duplicate_check(insertion_type);
+                }
+            }
+            Some(false) => self.duplicate_pending = None,
+            None => {}
+        }
         
-        None
+        if import_result.as_ref().is_some_and(|(success, _)| *success) {
+            self.recent_records.invalidate();
+        }
+        import_result
+    }
+
+    pub fn current_type(&self) -> &InsertionType {
+        &self.insertion_type
+    }
+
+    // Indica si algún formulario tiene datos escritos que se perderían al desconectar
+    pub fn has_unsaved_data(&self) -> bool {
+        self.persona_form != self.default_persona_form()
+            || self.organizacion_form != OrganizacionForm::default()
+            || self.actividad_form != ActividadForm::default()
+            || self.macro_sector_form != MacroSectorForm::default()
+            || self.unidad_vecinal_form != UnidadVecinalForm::default()
+            || self.taller_form != TallerForm::default()
+            || self.beneficio_form != BeneficioForm::default()
+            || self.centro_form != CentroForm::default()
+    }
+
+    // Antes de guardar un macrosector u organización revisa si ya existe uno con el mismo
+    // nombre; si existe, el guardado espera a que el usuario lo confirme
+    fn check_duplicate_then_save(&mut self, insertion_type: InsertionType) {
+        if self.duplicate_receiver.is_some() || self.duplicate_pending.is_some() {
+            return;
+        }
+        let db_manager = self.db_manager.clone();
+        let check = match insertion_type {
+            InsertionType::MacroSector => {
+                let nombre = self.macro_sector_form.nombre.trim().to_string();
+                if nombre.is_empty() {
+                    return;
+                }
+                tokio::spawn(async move {
+                    let db = db_manager.lock().await;
+                    db.macro_sector_name_match(&nombre).await
+                })
+            }
+            InsertionType::Organizacion => {
+                if !self.validate_organizacion_form() {
+                    return;
+                }
+                let nombre = self.organizacion_form.nombre.trim().to_string();
+                tokio::spawn(async move {
+                    let db = db_manager.lock().await;
+                    db.org_name_match(&nombre).await
+                })
+            }
+            _ => {
+                self.save_after_duplicate_check(insertion_type);
+                return;
+            }
+        };
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.duplicate_receiver = Some(rx);
+        tokio::spawn(async move {
+            let result = match check.await {
+                Ok(result) => result.map_err(|e| format!("Error al verificar duplicados: {}", e)),
+                Err(e) => Err(format!("Error al verificar duplicados: {}", e)),
+            };
+            let _ = tx.send((insertion_type, result));
+        });
+    }
+
+    // Devuelve el error de la verificación, si falló
+    fn check_duplicate_result(&mut self) -> Option<String> {
+        let receiver = self.duplicate_receiver.as_mut()?;
+        let (insertion_type, result) = receiver.try_recv().ok()?;
+        self.duplicate_receiver = None;
+        match result {
+            Ok(NameMatch::Exact) => Some(match insertion_type {
+                InsertionType::Organizacion => "Ya existe una organización con ese nombre".to_string(),
+                _ => "Ya existe un macrosector con ese nombre".to_string(),
+            }),
+            Ok(NameMatch::Variant) => {
+                let message = match insertion_type {
+                    InsertionType::Organizacion => "Ya existe una organización con un nombre casi igual (difiere en mayúsculas o espacios). ¿Continuar de todos modos?",
+                    _ => "Ya existe un macrosector con un nombre casi igual (difiere en mayúsculas o espacios). ¿Continuar de todos modos?",
+                };
+                self.duplicate_confirm.open("Posible duplicado", message);
+                self.duplicate_pending = Some(insertion_type);
+                None
+            }
+            Ok(NameMatch::None) => {
+                self.save_after_duplicate_check(insertion_type);
+                None
+            }
+            Err(error_msg) => Some(error_msg),
+        }
+    }
+
+    fn save_after_duplicate_check(&mut self, insertion_type: InsertionType) {
+        match insertion_type {
+            InsertionType::MacroSector => self.save_macro_sector(),
+            InsertionType::Organizacion => self.save_organizacion(),
+            _ => {}
+        }
+    }
+
+    // El filtro de macrosector es el mismo en todos los formularios: al cambiarlo se
+    // descarta en cada uno la UV que ya no le corresponde, no solo en el visible
+    fn clear_invalid_form_uvs(&mut self) {
+        let uvs = &self.unidades_vecinales;
+        for selected in [
+            &mut self.persona_form.unidad_vecinal_id,
+            &mut self.organizacion_form.unidad_vecinal_id,
+            &mut self.actividad_form.unidad_vecinal_id,
+            &mut self.centro_form.unidad_vecinal_id,
+        ] {
+            components::clear_invalid_uv(selected, uvs, self.uv_macro_filter);
+        }
+    }
+
+    // Guarda el formulario activo, igual que su botón "Guardar" (atajo Ctrl+S)
+    pub fn save_current_form(&mut self) {
+        if self.loading {
+            return;
+        }
+        match self.insertion_type {
+            InsertionType::Persona => {
+                if self.validate_persona_form() {
+                    self.save_persona();
+                }
+            }
+            InsertionType::Organizacion => self.check_duplicate_then_save(InsertionType::Organizacion),
+            InsertionType::Actividad => self.save_actividad(),
+            InsertionType::MacroSector => self.check_duplicate_then_save(InsertionType::MacroSector),
+            InsertionType::UnidadVecinal => self.save_unidad_vecinal(),
+            InsertionType::Taller => self.save_taller(),
+            InsertionType::Beneficio => self.save_beneficio(),
+            InsertionType::Centro => self.save_centro(),
+        }
     }
 
     fn show_persona_form(&mut self, ui: &mut egui::Ui) {
+        let errors = self.persona_field_errors();
+
         egui::Frame::none()
             .fill(egui::Color32::from_gray(25))
             .rounding(egui::Rounding::same(5.0))
             .inner_margin(egui::Margin::same(15.0))
             .show(ui, |ui| {
-                ui.heading("👤 Nueva Persona Mayor");
+                ui.horizontal(|ui| {
+                    ui.heading("👤 Nueva Persona Mayor");
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        let busy = self.persona_import.is_busy();
+                        if ui.add_enabled(!busy, egui::Button::new("📥 Importar CSV"))
+                            .on_hover_text("Cargar varias personas desde un archivo CSV")
+                            .clicked()
+                        {
+                            self.persona_import.pick_file();
+                        }
+                        if busy {
+                            ui.add(egui::widgets::Spinner::new().size(16.0));
+                        }
+                    });
+                });
+                self.persona_import.show_error(ui);
                 ui.add_space(10.0);
 
                 egui::Grid::new("persona_form")
                     .num_columns(2)
                     .spacing([15.0, 10.0])
                     .show(ui, |ui| {
-                        ui.label("RUT:");
-                        ui.horizontal(|ui| {
-                            let response = ui.text_edit_singleline(&mut self.persona_form.rut);
-                            
-                            // Formatear RUT automáticamente al perder foco
-                            if response.lost_focus() {
-                                self.persona_form.rut = format_rut(&self.persona_form.rut);
-                            }
-                            
-                            ui.small("(ej: 12345678-9)");
-                        });
-                        ui.end_row();
-
-                        ui.label("Primer Nombre:");
-                        ui.text_edit_singleline(&mut self.persona_form.primer_nombre);
-                        ui.end_row();
-
-                        ui.label("Segundo Nombre:");
-                        ui.text_edit_singleline(&mut self.persona_form.segundo_nombre);
-                        ui.end_row();
-
-                        ui.label("Primer Apellido:");
-                        ui.text_edit_singleline(&mut self.persona_form.primer_apellido);
-                        ui.end_row();
-
-                        ui.label("Segundo Apellido:");
-                        ui.text_edit_singleline(&mut self.persona_form.segundo_apellido);
-                        ui.end_row();
-
-                        ui.label("Género:");
-                        egui::ComboBox::from_id_source("persona_genero")
-                            .selected_text(
-                                self.persona_form.genero_id
-                                    .and_then(|id| self.generos.iter().find(|g| g.gen_id == id))
-                                    .map(|g| g.gen_genero.clone())
-                                    .unwrap_or_else(|| "Seleccionar...".to_string())
-                            )
-                            .show_ui(ui, |ui| {
-                                for genero in &self.generos {
-                                    ui.selectable_value(
-                                        &mut self.persona_form.genero_id,
-                                        Some(genero.gen_id),
-                                        &genero.gen_genero
-                                    );
-                                }
-                            });
-                        ui.end_row();
-
-                        ui.label("Nacionalidad:");
-                        egui::ComboBox::from_id_source("persona_nacionalidad")
-                            .selected_text(
-                                self.persona_form.nacionalidad_id
-                                    .and_then(|id| self.nacionalidades.iter().find(|n| n.nac_id == id))
-                                    .map(|n| n.nac_nacionalidad.clone())
-                                    .unwrap_or_else(|| "Seleccionar...".to_string())
-                            )
-                            .show_ui(ui, |ui| {
-                                for nacionalidad in &self.nacionalidades {
-                                    ui.selectable_value(
-                                        &mut self.persona_form.nacionalidad_id,
-                                        Some(nacionalidad.nac_id),
-                                        &nacionalidad.nac_nacionalidad
-                                    );
-                                }
+                        components::field_label(ui, "RUT");
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                rut_input(ui, &mut self.persona_form.rut);
+                                ui.small("(ej: 12345678-9)");
                             });
-                        ui.end_row();
-
-                        ui.label("Fecha de Nacimiento:");
-                        ui.horizontal(|ui| {
-                            ui.text_edit_singleline(&mut self.persona_form.fecha_nacimiento);
-                            ui.small("(dd/mm/yyyy)");
+                            field_hint(ui, errors.rut);
                         });
                         ui.end_row();
 
-                        ui.label("Dirección:");
-                        ui.text_edit_singleline(&mut self.persona_form.direccion);
-                        ui.end_row();
-
-                        ui.label("Email:");
-                        ui.text_edit_singleline(&mut self.persona_form.email);
+                        components::text_row(ui, "Primer Nombre", &mut self.persona_form.primer_nombre);
+                        components::text_row(ui, "Segundo Nombre", &mut self.persona_form.segundo_nombre);
+                        components::text_row(ui, "Primer Apellido", &mut self.persona_form.primer_apellido);
+                        components::text_row(ui, "Segundo Apellido", &mut self.persona_form.segundo_apellido);
+                        components::combo_row(ui, "Género", "persona_genero", &mut self.persona_form.genero_id, &self.generos,
+                            |g| (g.gen_id, g.gen_genero.as_str()));
+                        components::combo_row(ui, "Nacionalidad", "persona_nacionalidad", &mut self.persona_form.nacionalidad_id, &self.nacionalidades,
+                            |n| (n.nac_id, n.nac_nacionalidad.as_str()));
+                        components::date_row(ui, "Fecha de Nacimiento", &mut self.persona_form.fecha_nacimiento, errors.fecha_nacimiento.as_deref());
+                        components::text_row(ui, "Dirección", &mut self.persona_form.direccion);
+                        components::checked_text_row(ui, "Email", &mut self.persona_form.email, errors.email);
+
+                        components::field_label(ui, "Macrosector");
+                        if components::macro_sector_combo(ui, "persona_uv_macro", &mut self.uv_macro_filter, &self.macro_sectores, "Todos") {
+                            self.clear_invalid_form_uvs();
+                        }
                         ui.end_row();
 
-                        ui.label("Unidad Vecinal:");
-                        egui::ComboBox::from_id_source("persona_uv")
-                            .selected_text(
-                                self.persona_form.unidad_vecinal_id
-                                    .and_then(|id| self.unidades_vecinales.iter().find(|u| u.uv_id == id))
-                                    .map(|u| u.uv_nombre.clone())
-                                    .unwrap_or_else(|| "Seleccionar...".to_string())
-                            )
-                            .show_ui(ui, |ui| {
-                                for uv in &self.unidades_vecinales {
-                                    ui.selectable_value(
-                                        &mut self.persona_form.unidad_vecinal_id,
-                                        Some(uv.uv_id),
-                                        &uv.uv_nombre
-                                    );
-                                }
-                            });
+                        components::field_label(ui, "Unidad Vecinal");
+                        ui.vertical(|ui| {
+                            Self::uv_selector(ui, "persona_uv", &mut self.persona_form.unidad_vecinal_id, &self.unidades_vecinales, &self.recent_uvs, self.uv_macro_filter);
+                            self.empty_catalog_hint(ui, InsertionType::UnidadVecinal);
+                        });
                         ui.end_row();
                     });
 
+                ui.add_space(10.0);
+                self.show_persona_defaults(ui);
+
                 ui.add_space(20.0);
 
                 ui.horizontal(|ui| {
-                    if ui.button("💾 Guardar Persona").clicked() {
-                        if self.validate_persona_form() {
-                            self.save_persona();
-                        } else {
-                            // Aquí podrías mostrar un mensaje de error específico
-                            if self.persona_form.email.trim().len() > 0 && (!self.persona_form.email.contains('@') || !self.persona_form.email.contains('.')) {
-                                println!("Email inválido");
-                            }
-                        }
+                    if save_button(ui, "💾 Guardar Persona", &self.missing_persona_fields(), !errors.is_empty()) {
+                        self.save_persona();
                     }
 
-                    if ui.button("🧹 Limpiar Formulario").clicked() {
-                        self.persona_form = PersonaForm::default();
+                    let has_data = self.persona_form != self.default_persona_form();
+                    if clear_button(ui, "🧹 Limpiar Formulario", has_data, &mut self.confirm_clear) {
+                        self.persona_form = self.default_persona_form();
                     }
 
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
@@ -413,61 +905,68 @@ impl InsertionsView {
                 ui.heading("🏢 Nueva Organización Comunitaria");
                 ui.add_space(10.0);
 
+                let fecha_error = date_field_error(&self.organizacion_form.fecha_constitucion);
+                let perjuridica_error = personalidad_juridica_error(&self.organizacion_form.personalidad_juridica);
+
                 egui::Grid::new("org_form")
                     .num_columns(2)
                     .spacing([15.0, 10.0])
                     .show(ui, |ui| {
-                        ui.label("Nombre:");
+                        components::field_label(ui, "Nombre");
                         ui.text_edit_singleline(&mut self.organizacion_form.nombre);
                         ui.end_row();
 
-                        ui.label("Dirección:");
+                        components::field_label(ui, "Dirección");
                         ui.text_edit_singleline(&mut self.organizacion_form.direccion);
                         ui.end_row();
 
-                        ui.label("Fecha Constitución:");
-                        ui.horizontal(|ui| {
-                            ui.text_edit_singleline(&mut self.organizacion_form.fecha_constitucion);
-                            ui.small("(dd/mm/yyyy)");
+                        components::field_label(ui, "Fecha Constitución");
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.text_edit_singleline(&mut self.organizacion_form.fecha_constitucion);
+                                ui.small("(dd/mm/yyyy)");
+                            });
+                            field_hint(ui, fecha_error);
                         });
                         ui.end_row();
 
-                        ui.label("Personalidad Jurídica:");
-                        ui.text_edit_singleline(&mut self.organizacion_form.personalidad_juridica);
+                        components::field_label(ui, "Personalidad Jurídica");
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.text_edit_singleline(&mut self.organizacion_form.personalidad_juridica);
+                                ui.small("(ej. 1234 o 1234-R)");
+                            });
+                            field_hint(ui, perjuridica_error);
+                        });
                         ui.end_row();
 
-                        ui.label("Email:");
+                        components::field_label(ui, "Email");
                         ui.text_edit_singleline(&mut self.organizacion_form.email);
                         ui.end_row();
 
-                        ui.label("Unidad Vecinal:");
-                        egui::ComboBox::from_id_source("org_uv")
-                            .selected_text(
-                                self.organizacion_form.unidad_vecinal_id
-                                    .and_then(|id| self.unidades_vecinales.iter().find(|u| u.uv_id == id))
-                                    .map(|u| u.uv_nombre.clone())
-                                    .unwrap_or_else(|| "Seleccionar...".to_string())
-                            )
-                            .show_ui(ui, |ui| {
-                                for uv in &self.unidades_vecinales {
-                                    ui.selectable_value(
-                                        &mut self.organizacion_form.unidad_vecinal_id,
-                                        Some(uv.uv_id),
-                                        &uv.uv_nombre
-                                    );
-                                }
-                            });
+                        components::field_label(ui, "Macrosector");
+                        if components::macro_sector_combo(ui, "org_uv_macro", &mut self.uv_macro_filter, &self.macro_sectores, "Todos") {
+                            self.clear_invalid_form_uvs();
+                        }
+                        ui.end_row();
+
+                        components::field_label(ui, "Unidad Vecinal");
+                        ui.vertical(|ui| {
+                            Self::uv_selector(ui, "org_uv", &mut self.organizacion_form.unidad_vecinal_id, &self.unidades_vecinales, &self.recent_uvs, self.uv_macro_filter);
+                            self.empty_catalog_hint(ui, InsertionType::UnidadVecinal);
+                        });
                         ui.end_row();
                     });
 
                 ui.add_space(20.0);
 
                 ui.horizontal(|ui| {
-                    if ui.button("💾 Guardar Organización").clicked() {
-                        self.save_organizacion();
+                    if save_button(ui, "💾 Guardar Organización", &self.missing_organizacion_fields(), fecha_error.is_some() || perjuridica_error.is_some()) {
+                        self.check_duplicate_then_save(InsertionType::Organizacion);
                     }
 
-                    if ui.button("🧹 Limpiar Formulario").clicked() {
+                    let has_data = self.organizacion_form != OrganizacionForm::default();
+                    if clear_button(ui, "🧹 Limpiar Formulario", has_data, &mut self.confirm_clear) {
                         self.organizacion_form = OrganizacionForm::default();
                     }
                 });
@@ -483,54 +982,67 @@ impl InsertionsView {
                 ui.heading("🎯 Nueva Actividad");
                 ui.add_space(10.0);
 
+                let (inicio_error, fin_error) = self.actividad_date_errors();
+
                 egui::Grid::new("act_form")
                     .num_columns(2)
                     .spacing([15.0, 10.0])
                     .show(ui, |ui| {
-                        ui.label("Nombre:");
+                        components::field_label(ui, "Nombre");
                         ui.text_edit_singleline(&mut self.actividad_form.nombre);
                         ui.end_row();
 
-                        ui.label("Fecha Inicio:");
-                        ui.text_edit_singleline(&mut self.actividad_form.fecha_inicio);
+                        components::field_label(ui, "Fecha Inicio");
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.text_edit_singleline(&mut self.actividad_form.fecha_inicio);
+                                ui.small("(dd/mm/yyyy)");
+                            });
+                            field_hint(ui, inicio_error);
+                        });
                         ui.end_row();
 
-                        ui.label("Fecha Fin:");
-                        ui.text_edit_singleline(&mut self.actividad_form.fecha_fin);
+                        components::field_label(ui, "Fecha Fin");
+                        ui.vertical(|ui| {
+                            ui.horizontal(|ui| {
+                                ui.text_edit_singleline(&mut self.actividad_form.fecha_fin);
+                                ui.small("(dd/mm/yyyy)");
+                            });
+                            field_hint(ui, fin_error);
+                        });
                         ui.end_row();
 
-                        ui.label("Descripción:");
+                        components::field_label(ui, "Descripción");
                         ui.text_edit_multiline(&mut self.actividad_form.descripcion);
                         ui.end_row();
 
-                        ui.label("Unidad Vecinal:");
-                        egui::ComboBox::from_id_source("act_uv")
-                            .selected_text(
-                                self.actividad_form.unidad_vecinal_id
-                                    .and_then(|id| self.unidades_vecinales.iter().find(|u| u.uv_id == id))
-                                    .map(|u| u.uv_nombre.clone())
-                                    .unwrap_or_else(|| "Seleccionar...".to_string())
-                            )
-                            .show_ui(ui, |ui| {
-                                for uv in &self.unidades_vecinales {
-                                    ui.selectable_value(
-                                        &mut self.actividad_form.unidad_vecinal_id,
-                                        Some(uv.uv_id),
-                                        &uv.uv_nombre
-                                    );
-                                }
-                            });
+                        components::field_label(ui, "Macrosector");
+                        if components::macro_sector_combo(ui, "act_uv_macro", &mut self.uv_macro_filter, &self.macro_sectores, "Todos") {
+                            self.clear_invalid_form_uvs();
+                        }
+                        ui.end_row();
+
+                        components::field_label(ui, "Unidad Vecinal");
+                        ui.vertical(|ui| {
+                            Self::uv_selector(ui, "act_uv", &mut self.actividad_form.unidad_vecinal_id, &self.unidades_vecinales, &self.recent_uvs, self.uv_macro_filter);
+                            self.empty_catalog_hint(ui, InsertionType::UnidadVecinal);
+                        });
+                        ui.end_row();
+
+                        components::field_label(ui, "Taller");
+                        components::taller_combo(ui, "act_taller", &mut self.actividad_form.taller_id, &self.talleres, "Sin taller");
                         ui.end_row();
                     });
 
                 ui.add_space(20.0);
 
                 ui.horizontal(|ui| {
-                    if ui.button("💾 Guardar Actividad").clicked() {
+                    if save_button(ui, "💾 Guardar Actividad", &self.missing_actividad_fields(), inicio_error.is_some() || fin_error.is_some()) {
                         self.save_actividad();
                     }
 
-                    if ui.button("🧹 Limpiar Formulario").clicked() {
+                    let has_data = self.actividad_form != ActividadForm::default();
+                    if clear_button(ui, "🧹 Limpiar Formulario", has_data, &mut self.confirm_clear) {
                         self.actividad_form = ActividadForm::default();
                     }
                 });
@@ -547,18 +1059,19 @@ impl InsertionsView {
                 ui.add_space(10.0);
 
                 ui.horizontal(|ui| {
-                    ui.label("Nombre:");
+                    components::field_label(ui, "Nombre");
                     ui.text_edit_singleline(&mut self.macro_sector_form.nombre);
                 });
 
                 ui.add_space(20.0);
 
                 ui.horizontal(|ui| {
-                    if ui.button("💾 Guardar Macrosector").clicked() {
-                        self.save_macro_sector();
+                    if save_button(ui, "💾 Guardar Macrosector", &self.missing_macro_sector_fields(), false) {
+                        self.check_duplicate_then_save(InsertionType::MacroSector);
                     }
 
-                    if ui.button("🧹 Limpiar").clicked() {
+                    let has_data = self.macro_sector_form != MacroSectorForm::default();
+                    if clear_button(ui, "🧹 Limpiar", has_data, &mut self.confirm_clear) {
                         self.macro_sector_form = MacroSectorForm::default();
                     }
                 });
@@ -578,38 +1091,27 @@ impl InsertionsView {
                     .num_columns(2)
                     .spacing([15.0, 10.0])
                     .show(ui, |ui| {
-                        ui.label("Nombre:");
+                        components::field_label(ui, "Nombre");
                         ui.text_edit_singleline(&mut self.unidad_vecinal_form.nombre);
                         ui.end_row();
 
-                        ui.label("Macrosector:");
-                        egui::ComboBox::from_id_source("uv_macro")
-                            .selected_text(
-                                self.unidad_vecinal_form.macro_sector_id
-                                    .and_then(|id| self.macro_sectores.iter().find(|m| m.mac_id == id))
-                                    .map(|m| m.mac_nombre.clone())
-                                    .unwrap_or_else(|| "Seleccionar...".to_string())
-                            )
-                            .show_ui(ui, |ui| {
-                                for macro_sector in &self.macro_sectores {
-                                    ui.selectable_value(
-                                        &mut self.unidad_vecinal_form.macro_sector_id,
-                                        Some(macro_sector.mac_id),
-                                        &macro_sector.mac_nombre
-                                    );
-                                }
-                            });
+                        components::field_label(ui, "Macrosector");
+                        ui.vertical(|ui| {
+                            components::macro_sector_combo(ui, "uv_macro", &mut self.unidad_vecinal_form.macro_sector_id, &self.macro_sectores, "Seleccionar...");
+                            self.empty_catalog_hint(ui, InsertionType::MacroSector);
+                        });
                         ui.end_row();
                     });
 
                 ui.add_space(20.0);
 
                 ui.horizontal(|ui| {
-                    if ui.button("💾 Guardar Unidad Vecinal").clicked() {
+                    if save_button(ui, "💾 Guardar Unidad Vecinal", &self.missing_unidad_vecinal_fields(), false) {
                         self.save_unidad_vecinal();
                     }
 
-                    if ui.button("🧹 Limpiar").clicked() {
+                    let has_data = self.unidad_vecinal_form != UnidadVecinalForm::default();
+                    if clear_button(ui, "🧹 Limpiar", has_data, &mut self.confirm_clear) {
                         self.unidad_vecinal_form = UnidadVecinalForm::default();
                     }
                 });
@@ -626,76 +1128,123 @@ impl InsertionsView {
                 ui.add_space(10.0);
 
                 ui.horizontal(|ui| {
-                    ui.label("Nombre:");
+                    components::field_label(ui, "Nombre");
                     ui.text_edit_singleline(&mut self.taller_form.nombre);
                 });
 
                 ui.add_space(20.0);
 
                 ui.horizontal(|ui| {
-                    if ui.button("💾 Guardar Taller").clicked() {
+                    if save_button(ui, "💾 Guardar Taller", &self.missing_taller_fields(), false) {
                         self.save_taller();
                     }
 
-                    if ui.button("🧹 Limpiar").clicked() {
+                    let has_data = self.taller_form != TallerForm::default();
+                    if clear_button(ui, "🧹 Limpiar", has_data, &mut self.confirm_clear) {
                         self.taller_form = TallerForm::default();
                     }
                 });
             });
     }
 
-    fn load_catalogs(&mut self) {
-        // Crear canal para recibir actualizaciones de catálogo
-        let (tx, rx) = mpsc::unbounded_channel();
-        self.catalog_receiver = Some(rx);
-        
-        let db_manager = self.db_manager.clone();
-        
-        // Cargar géneros
-        let tx_generos = tx.clone();
-        let db_generos = db_manager.clone();
-        tokio::spawn(async move {
-            let db = db_generos.lock().await;
-            if let Ok(generos) = db.get_generos().await {
-                let _ = tx_generos.send(CatalogUpdate::Generos(generos));
-            }
-        });
-        
-        // Cargar nacionalidades
-        let tx_nacionalidades = tx.clone();
-        let db_nacionalidades = db_manager.clone();
-        tokio::spawn(async move {
-            let db = db_nacionalidades.lock().await;
-            if let Ok(nacionalidades) = db.get_nacionalidades().await {
-                let _ = tx_nacionalidades.send(CatalogUpdate::Nacionalidades(nacionalidades));
-            }
-        });
-        
-        // Cargar unidades vecinales
-        let tx_unidades = tx.clone();
-        let db_unidades = db_manager.clone();
-        tokio::spawn(async move {
-            let db = db_unidades.lock().await;
-            if let Ok(unidades) = db.get_unidades_vecinales().await {
-                let _ = tx_unidades.send(CatalogUpdate::UnidadesVecinales(unidades));
-            }
-        });
-        
-        // Cargar macrosectores
-        let tx_sectores = tx;
-        let db_sectores = db_manager;
-        tokio::spawn(async move {
-            let db = db_sectores.lock().await;
-            if let Ok(sectores) = db.get_macro_sectores().await {
-                let _ = tx_sectores.send(CatalogUpdate::MacroSectores(sectores));
-            }
-        });
+    fn show_beneficio_form(&mut self, ui: &mut egui::Ui) {
+        egui::Frame::none()
+            .fill(egui::Color32::from_gray(25))
+            .rounding(egui::Rounding::same(5.0))
+            .inner_margin(egui::Margin::same(15.0))
+            .show(ui, |ui| {
+                ui.heading("🎁 Nuevo Beneficio");
+                ui.add_space(10.0);
+
+                egui::Grid::new("ben_form")
+                    .num_columns(2)
+                    .spacing([15.0, 10.0])
+                    .show(ui, |ui| {
+                        components::field_label(ui, "Código");
+                        ui.text_edit_singleline(&mut self.beneficio_form.codigo);
+                        ui.end_row();
+
+                        components::field_label(ui, "Descripción");
+                        ui.text_edit_singleline(&mut self.beneficio_form.descripcion);
+                        ui.end_row();
+                    });
+
+                ui.add_space(20.0);
+
+                ui.horizontal(|ui| {
+                    if save_button(ui, "💾 Guardar Beneficio", &self.missing_beneficio_fields(), false) {
+                        self.save_beneficio();
+                    }
+
+                    let has_data = self.beneficio_form != BeneficioForm::default();
+                    if clear_button(ui, "🧹 Limpiar", has_data, &mut self.confirm_clear) {
+                        self.beneficio_form = BeneficioForm::default();
+                    }
+                });
+            });
+    }
+
+    fn show_centro_form(&mut self, ui: &mut egui::Ui) {
+        egui::Frame::none()
+            .fill(egui::Color32::from_gray(25))
+            .rounding(egui::Rounding::same(5.0))
+            .inner_margin(egui::Margin::same(15.0))
+            .show(ui, |ui| {
+                ui.heading("🏠 Nuevo Centro Comunitario");
+                ui.add_space(10.0);
+
+                egui::Grid::new("cen_form")
+                    .num_columns(2)
+                    .spacing([15.0, 10.0])
+                    .show(ui, |ui| {
+                        components::field_label(ui, "Nombre");
+                        ui.text_edit_singleline(&mut self.centro_form.nombre);
+                        ui.end_row();
+
+                        components::field_label(ui, "Dirección");
+                        ui.text_edit_singleline(&mut self.centro_form.direccion);
+                        ui.end_row();
+
+                        components::field_label(ui, "Macrosector");
+                        if components::macro_sector_combo(ui, "cen_uv_macro", &mut self.uv_macro_filter, &self.macro_sectores, "Todos") {
+                            self.clear_invalid_form_uvs();
+                        }
+                        ui.end_row();
+
+                        components::field_label(ui, "Unidad Vecinal");
+                        ui.vertical(|ui| {
+                            Self::uv_selector(ui, "cen_uv", &mut self.centro_form.unidad_vecinal_id, &self.unidades_vecinales, &self.recent_uvs, self.uv_macro_filter);
+                            self.empty_catalog_hint(ui, InsertionType::UnidadVecinal);
+                        });
+                        ui.end_row();
+                    });
+
+                ui.add_space(20.0);
+
+                ui.horizontal(|ui| {
+                    if save_button(ui, "💾 Guardar Centro", &self.missing_centro_fields(), false) {
+                        self.save_centro();
+                    }
+
+                    let has_data = self.centro_form != CentroForm::default();
+                    if clear_button(ui, "🧹 Limpiar Formulario", has_data, &mut self.confirm_clear) {
+                        self.centro_form = CentroForm::default();
+                    }
+                });
+            });
     }
 
     fn save_persona(&mut self) {
         if self.validate_persona_form() {
+            let form = &self.persona_form;
+            let (Some(genero_id), Some(nacionalidad_id), Some(unidad_vecinal_id)) =
+                (form.genero_id, form.nacionalidad_id, form.unidad_vecinal_id) else {
+                return;
+            };
             self.loading = true;
             
+            self.pending_recent_uv = self.persona_form.unidad_vecinal_id;
+            self.last_insertion = None;
             let (tx, rx) = mpsc::unbounded_channel();
             self.insertion_receiver = Some(rx);
             
@@ -715,23 +1264,25 @@ impl InsertionsView {
                 } else { 
                     Some(self.persona_form.segundo_apellido.clone()) 
                 },
-                per_genid: self.persona_form.genero_id.unwrap_or(1),
-                per_nacid: self.persona_form.nacionalidad_id.unwrap_or(1),
-                per_fechadenac: chrono::NaiveDate::parse_from_str(&self.persona_form.fecha_nacimiento, "%Y-%m-%d")
-                    .unwrap_or_else(|_| chrono::NaiveDate::from_ymd_opt(1950, 1, 1).unwrap()),
+                per_genid: genero_id,
+                per_nacid: nacionalidad_id,
+                per_fechadenac: utils::parse_date(self.persona_form.fecha_nacimiento.trim())
+                    .unwrap_or_else(|| chrono::NaiveDate::from_ymd_opt(1950, 1, 1).unwrap()),
                 per_direccion: self.persona_form.direccion.clone(),
                 per_email: if self.persona_form.email.trim().is_empty() { 
                     None 
                 } else {
                     // Validación muy básica - dejar que PostgreSQL haga la validación final
                     let email = self.persona_form.email.trim();
-                    if email.len() > 0 {
+                    if !email.is_empty() {
                         Some(email.to_string())
                     } else {
                         None
                     }
                 },
-                per_uvid: self.persona_form.unidad_vecinal_id.unwrap_or(1),
+                per_uvid: unidad_vecinal_id,
+                per_fecha_registro: None,
+                per_fecha_modificacion: None,
                 gen_genero: None,
                 nac_nacionalidad: None,
                 uv_nombre: None,
@@ -744,7 +1295,7 @@ impl InsertionsView {
                 
                 match result {
                     Ok(id) => {
-                        let _ = tx.send(Ok(format!("Persona guardada exitosamente con ID: {}", id)));
+                        let _ = tx.send(Ok((InsertionType::Persona, format!("Persona guardada exitosamente con ID: {}", id), id)));
                     }
                     Err(e) => {
                         let _ = tx.send(Err(format!("Error al guardar persona: {}", e)));
@@ -756,24 +1307,36 @@ impl InsertionsView {
 
     fn save_organizacion(&mut self) {
         if self.validate_organizacion_form() {
+            let Some(unidad_vecinal_id) = self.organizacion_form.unidad_vecinal_id else {
+                return;
+            };
             // Crear canal para comunicación asíncrona
+            self.pending_recent_uv = self.organizacion_form.unidad_vecinal_id;
+            self.last_insertion = None;
             let (tx, rx) = mpsc::unbounded_channel();
             self.insertion_receiver = Some(rx);
             
+            let Some(fecha_constitucion) = utils::parse_date(&self.organizacion_form.fecha_constitucion) else {
+                return;
+            };
+            let Some(personalidad_juridica) = utils::normalize_personalidad_juridica(&self.organizacion_form.personalidad_juridica) else {
+                return;
+            };
             let organizacion = OrganizacionComunitaria {
                 org_id: 0, // Se generará automáticamente
                 org_nombre: self.organizacion_form.nombre.clone(),
                 org_direccion: self.organizacion_form.direccion.clone(),
-                org_uvid: self.organizacion_form.unidad_vecinal_id.unwrap_or(1),
-                org_fechaconst: chrono::NaiveDate::parse_from_str(&self.organizacion_form.fecha_constitucion, "%Y-%m-%d")
-                    .unwrap_or_else(|_| chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
-                org_perjuridica: self.organizacion_form.personalidad_juridica.clone(),
+                org_uvid: unidad_vecinal_id,
+                org_fechaconst: fecha_constitucion,
+                org_perjuridica: personalidad_juridica,
                 org_email: if self.organizacion_form.email.trim().is_empty() { 
                     None 
                 } else {
                     Some(self.organizacion_form.email.trim().to_string())
                 },
                 // Campos adicionales que no están en el formulario
+                org_fecha_registro: None,
+                org_fecha_modificacion: None,
                 uv_nombre: None,
             };
             
@@ -784,7 +1347,7 @@ impl InsertionsView {
                 
                 match result {
                     Ok(id) => {
-                        let _ = tx.send(Ok(format!("Organización guardada exitosamente con ID: {}", id)));
+                        let _ = tx.send(Ok((InsertionType::Organizacion, format!("Organización guardada exitosamente con ID: {}", id), id)));
                     }
                     Err(e) => {
                         let _ = tx.send(Err(format!("Error al guardar organización: {}", e)));
@@ -796,28 +1359,35 @@ impl InsertionsView {
 
     fn save_actividad(&mut self) {
         if self.validate_actividad_form() {
+            let Some(unidad_vecinal_id) = self.actividad_form.unidad_vecinal_id else {
+                return;
+            };
             // Crear canal para comunicación asíncrona
+            self.pending_recent_uv = self.actividad_form.unidad_vecinal_id;
+            self.last_insertion = None;
             let (tx, rx) = mpsc::unbounded_channel();
             self.insertion_receiver = Some(rx);
             
+            let Some(fecha_inicio) = utils::parse_date(&self.actividad_form.fecha_inicio) else {
+                return;
+            };
             let actividad = Actividad {
                 act_id: 0, // Se generará automáticamente
                 act_nombre: self.actividad_form.nombre.clone(),
-                act_uvid: self.actividad_form.unidad_vecinal_id.unwrap_or(1),
-                act_fecha_ini: chrono::NaiveDate::parse_from_str(&self.actividad_form.fecha_inicio, "%Y-%m-%d")
-                    .unwrap_or_else(|_| chrono::NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
-                act_fecha_fin: if self.actividad_form.fecha_fin.trim().is_empty() {
-                    None
-                } else {
-                    chrono::NaiveDate::parse_from_str(&self.actividad_form.fecha_fin, "%Y-%m-%d").ok()
-                },
+                act_uvid: unidad_vecinal_id,
+                act_fecha_ini: fecha_inicio,
+                act_fecha_fin: utils::parse_date(&self.actividad_form.fecha_fin),
                 act_descripcion: if self.actividad_form.descripcion.trim().is_empty() {
                     None
                 } else {
                     Some(self.actividad_form.descripcion.clone())
                 },
+                act_talid: self.actividad_form.taller_id,
                 // Campos adicionales
+                act_fecha_registro: None,
+                act_fecha_modificacion: None,
                 uv_nombre: None,
+                tal_nombre: None,
             };
             
             let db_manager = self.db_manager.clone();
@@ -827,7 +1397,7 @@ impl InsertionsView {
                 
                 match result {
                     Ok(id) => {
-                        let _ = tx.send(Ok(format!("Actividad guardada exitosamente con ID: {}", id)));
+                        let _ = tx.send(Ok((InsertionType::Actividad, format!("Actividad guardada exitosamente con ID: {}", id), id)));
                     }
                     Err(e) => {
                         let _ = tx.send(Err(format!("Error al guardar actividad: {}", e)));
@@ -840,6 +1410,7 @@ impl InsertionsView {
     fn save_macro_sector(&mut self) {
         if !self.macro_sector_form.nombre.trim().is_empty() {
             // Crear canal para comunicación asíncrona
+            self.last_insertion = None;
             let (tx, rx) = mpsc::unbounded_channel();
             self.insertion_receiver = Some(rx);
             
@@ -852,7 +1423,7 @@ impl InsertionsView {
                 
                 match result {
                     Ok(id) => {
-                        let _ = tx.send(Ok(format!("Macrosector guardado exitosamente con ID: {}", id)));
+                        let _ = tx.send(Ok((InsertionType::MacroSector, format!("Macrosector guardado exitosamente con ID: {}", id), id)));
                     }
                     Err(e) => {
                         let _ = tx.send(Err(format!("Error al guardar macrosector: {}", e)));
@@ -864,12 +1435,15 @@ impl InsertionsView {
 
     fn save_unidad_vecinal(&mut self) {
         if self.validate_unidad_vecinal_form() {
+            let Some(macro_sector_id) = self.unidad_vecinal_form.macro_sector_id else {
+                return;
+            };
             // Crear canal para comunicación asíncrona
+            self.last_insertion = None;
             let (tx, rx) = mpsc::unbounded_channel();
             self.insertion_receiver = Some(rx);
             
             let nombre = self.unidad_vecinal_form.nombre.trim().to_string();
-            let macro_sector_id = self.unidad_vecinal_form.macro_sector_id.unwrap_or(1);
             let db_manager = self.db_manager.clone();
             
             tokio::spawn(async move {
@@ -878,7 +1452,7 @@ impl InsertionsView {
                 
                 match result {
                     Ok(id) => {
-                        let _ = tx.send(Ok(format!("Unidad Vecinal guardada exitosamente con ID: {}", id)));
+                        let _ = tx.send(Ok((InsertionType::UnidadVecinal, format!("Unidad Vecinal guardada exitosamente con ID: {}", id), id)));
                     }
                     Err(e) => {
                         let _ = tx.send(Err(format!("Error al guardar unidad vecinal: {}", e)));
@@ -891,6 +1465,7 @@ impl InsertionsView {
     fn save_taller(&mut self) {
         if !self.taller_form.nombre.trim().is_empty() {
             // Crear canal para comunicación asíncrona
+            self.last_insertion = None;
             let (tx, rx) = mpsc::unbounded_channel();
             self.insertion_receiver = Some(rx);
             
@@ -903,7 +1478,7 @@ impl InsertionsView {
                 
                 match result {
                     Ok(id) => {
-                        let _ = tx.send(Ok(format!("Taller guardado exitosamente con ID: {}", id)));
+                        let _ = tx.send(Ok((InsertionType::Taller, format!("Taller guardado exitosamente con ID: {}", id), id)));
                     }
                     Err(e) => {
                         let _ = tx.send(Err(format!("Error al guardar taller: {}", e)));
@@ -913,45 +1488,241 @@ impl InsertionsView {
         }
     }
 
+    fn save_beneficio(&mut self) {
+        if self.validate_beneficio_form() {
+            // Crear canal para comunicación asíncrona
+            self.last_insertion = None;
+            let (tx, rx) = mpsc::unbounded_channel();
+            self.insertion_receiver = Some(rx);
+            
+            let codigo = self.beneficio_form.codigo.trim().to_string();
+            let descripcion = self.beneficio_form.descripcion.trim().to_string();
+            let db_manager = self.db_manager.clone();
+            
+            tokio::spawn(async move {
+                let db = db_manager.lock().await;
+                let result = db.insert_beneficio(&codigo, &descripcion).await;
+                
+                match result {
+                    Ok(id) => {
+                        let _ = tx.send(Ok((InsertionType::Beneficio, format!("Beneficio guardado exitosamente con ID: {}", id), id)));
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(format!("Error al guardar beneficio: {}", e)));
+                    }
+                }
+            });
+        }
+    }
+
+    fn save_centro(&mut self) {
+        if self.validate_centro_form() {
+            let Some(unidad_vecinal_id) = self.centro_form.unidad_vecinal_id else {
+                return;
+            };
+            // Crear canal para comunicación asíncrona
+            self.pending_recent_uv = self.centro_form.unidad_vecinal_id;
+            self.last_insertion = None;
+            let (tx, rx) = mpsc::unbounded_channel();
+            self.insertion_receiver = Some(rx);
+            
+            let centro = CentroComunitario {
+                cen_id: 0, // Se generará automáticamente
+                cen_nombre: self.centro_form.nombre.trim().to_string(),
+                cen_direccion: self.centro_form.direccion.trim().to_string(),
+                cen_uvid: unidad_vecinal_id,
+                uv_nombre: None,
+            };
+            
+            let db_manager = self.db_manager.clone();
+            tokio::spawn(async move {
+                let db = db_manager.lock().await;
+                let result = db.insert_centro(&centro).await;
+                
+                match result {
+                    Ok(id) => {
+                        let _ = tx.send(Ok((InsertionType::Centro, format!("Centro comunitario guardado exitosamente con ID: {}", id), id)));
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(format!("Error al guardar centro comunitario: {}", e)));
+                    }
+                }
+            });
+        }
+    }
+
+    // Errores de RUT, email y fecha de nacimiento; los campos vacíos no se marcan
+    fn persona_field_errors(&self) -> PersonaFieldErrors {
+        let mut errors = PersonaFieldErrors::default();
+
+        let rut = self.persona_form.rut.trim();
+        if !rut.is_empty() {
+            if !utils::validate_rut(rut) {
+                errors.rut = Some("Formato inválido, use 12345678-9");
+            } else if !utils::validate_rut_check_digit(rut) {
+                errors.rut = Some("Dígito verificador incorrecto");
+            }
+        }
+
+        // Email vacío es válido (será NULL)
+        let email = self.persona_form.email.trim();
+        if !email.is_empty() && !utils::validate_email(email) {
+            errors.email = Some("Email inválido");
+        }
+
+        let fecha = self.persona_form.fecha_nacimiento.trim();
+        if !fecha.is_empty() {
+            match utils::parse_date(fecha) {
+                None => errors.fecha_nacimiento = Some("Fecha inválida, use dd/mm/yyyy".to_string()),
+                Some(fecha) => errors.fecha_nacimiento = utils::birthdate_error(&fecha, self.min_age),
+            }
+        }
+
+        errors
+    }
+
+    // Campos obligatorios sin completar de cada formulario, en el orden en que aparecen
+    fn missing_persona_fields(&self) -> Vec<&'static str> {
+        let form = &self.persona_form;
+        let mut missing = Vec::new();
+        if form.rut.trim().is_empty() { missing.push("RUT"); }
+        if form.primer_nombre.trim().is_empty() { missing.push("Primer Nombre"); }
+        if form.primer_apellido.trim().is_empty() { missing.push("Primer Apellido"); }
+        if form.genero_id.is_none() { missing.push("Género"); }
+        if form.nacionalidad_id.is_none() { missing.push("Nacionalidad"); }
+        if form.fecha_nacimiento.trim().is_empty() { missing.push("Fecha de Nacimiento"); }
+        if form.direccion.trim().is_empty() { missing.push("Dirección"); }
+        if form.unidad_vecinal_id.is_none() { missing.push("Unidad Vecinal"); }
+        missing
+    }
+
+    fn missing_organizacion_fields(&self) -> Vec<&'static str> {
+        let form = &self.organizacion_form;
+        let mut missing = Vec::new();
+        if form.nombre.trim().is_empty() { missing.push("Nombre"); }
+        if form.direccion.trim().is_empty() { missing.push("Dirección"); }
+        if form.fecha_constitucion.trim().is_empty() { missing.push("Fecha Constitución"); }
+        if form.personalidad_juridica.trim().is_empty() { missing.push("Personalidad Jurídica"); }
+        if form.unidad_vecinal_id.is_none() { missing.push("Unidad Vecinal"); }
+        missing
+    }
+
+    fn missing_actividad_fields(&self) -> Vec<&'static str> {
+        let form = &self.actividad_form;
+        let mut missing = Vec::new();
+        if form.nombre.trim().is_empty() { missing.push("Nombre"); }
+        if form.fecha_inicio.trim().is_empty() { missing.push("Fecha Inicio"); }
+        if form.unidad_vecinal_id.is_none() { missing.push("Unidad Vecinal"); }
+        missing
+    }
+
+    fn missing_macro_sector_fields(&self) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        if self.macro_sector_form.nombre.trim().is_empty() { missing.push("Nombre"); }
+        missing
+    }
+
+    fn missing_unidad_vecinal_fields(&self) -> Vec<&'static str> {
+        let form = &self.unidad_vecinal_form;
+        let mut missing = Vec::new();
+        if form.nombre.trim().is_empty() { missing.push("Nombre"); }
+        if form.macro_sector_id.is_none() { missing.push("Macrosector"); }
+        missing
+    }
+
+    fn missing_taller_fields(&self) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        if self.taller_form.nombre.trim().is_empty() { missing.push("Nombre"); }
+        missing
+    }
+
+    fn missing_beneficio_fields(&self) -> Vec<&'static str> {
+        let form = &self.beneficio_form;
+        let mut missing = Vec::new();
+        if form.codigo.trim().is_empty() { missing.push("Código"); }
+        if form.descripcion.trim().is_empty() { missing.push("Descripción"); }
+        missing
+    }
+
+    fn missing_centro_fields(&self) -> Vec<&'static str> {
+        let form = &self.centro_form;
+        let mut missing = Vec::new();
+        if form.nombre.trim().is_empty() { missing.push("Nombre"); }
+        if form.direccion.trim().is_empty() { missing.push("Dirección"); }
+        if form.unidad_vecinal_id.is_none() { missing.push("Unidad Vecinal"); }
+        missing
+    }
+
     fn validate_persona_form(&self) -> bool {
-        let email_valid = if self.persona_form.email.trim().is_empty() {
-            true // Email vacío es válido (será NULL)
-        } else {
-            // Validación muy básica - debe tener @ y .
-            let email = self.persona_form.email.trim();
-            email.contains('@') && email.contains('.') && email.len() > 5
-        };
-        
-        // Validar formato de RUT chileno usando la función específica
-        let rut_valid = validate_rut_format(self.persona_form.rut.trim());
-        
-        rut_valid &&
-        !self.persona_form.primer_nombre.is_empty() &&
-        !self.persona_form.primer_apellido.is_empty() &&
-        self.persona_form.genero_id.is_some() &&
-        self.persona_form.nacionalidad_id.is_some() &&
-        !self.persona_form.fecha_nacimiento.is_empty() &&
-        !self.persona_form.direccion.is_empty() &&
-        self.persona_form.unidad_vecinal_id.is_some() &&
-        email_valid
+        self.persona_field_errors().is_empty() && self.missing_persona_fields().is_empty()
+    }
+
+    // Errores de las fechas de inicio y fin; el fin no puede ser anterior al inicio
+    fn actividad_date_errors(&self) -> (Option<&'static str>, Option<&'static str>) {
+        let form = &self.actividad_form;
+        let inicio_error = date_field_error(&form.fecha_inicio);
+        let fin_error = date_field_error(&form.fecha_fin).or_else(|| {
+            match (utils::parse_date(&form.fecha_inicio), utils::parse_date(&form.fecha_fin)) {
+                (Some(inicio), Some(fin)) if fin < inicio => Some("La fecha de fin no puede ser anterior al inicio"),
+                _ => None,
+            }
+        });
+        (inicio_error, fin_error)
     }
 
     fn validate_organizacion_form(&self) -> bool {
-        !self.organizacion_form.nombre.is_empty() &&
-        !self.organizacion_form.direccion.is_empty() &&
-        !self.organizacion_form.fecha_constitucion.is_empty() &&
-        !self.organizacion_form.personalidad_juridica.is_empty() &&
-        self.organizacion_form.unidad_vecinal_id.is_some()
+        date_field_error(&self.organizacion_form.fecha_constitucion).is_none()
+            && personalidad_juridica_error(&self.organizacion_form.personalidad_juridica).is_none()
+            && self.missing_organizacion_fields().is_empty()
     }
 
     fn validate_actividad_form(&self) -> bool {
-        !self.actividad_form.nombre.is_empty() &&
-        !self.actividad_form.fecha_inicio.is_empty() &&
-        self.actividad_form.unidad_vecinal_id.is_some()
+        self.actividad_date_errors() == (None, None) && self.missing_actividad_fields().is_empty()
     }
 
     fn validate_unidad_vecinal_form(&self) -> bool {
-        !self.unidad_vecinal_form.nombre.is_empty() &&
-        self.unidad_vecinal_form.macro_sector_id.is_some()
+        self.missing_unidad_vecinal_fields().is_empty()
+    }
+
+    fn validate_centro_form(&self) -> bool {
+        self.missing_centro_fields().is_empty()
+    }
+
+    fn validate_beneficio_form(&self) -> bool {
+        self.missing_beneficio_fields().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_rut;
+
+    #[test]
+    fn format_rut_solo_verificador() {
+        assert_eq!(format_rut("K"), "K");
+        assert_eq!(format_rut("k"), "K");
+    }
+
+    #[test]
+    fn format_rut_solo_separadores() {
+        assert_eq!(format_rut(""), "");
+        assert_eq!(format_rut(".-. -"), "");
+    }
+
+    #[test]
+    fn format_rut_verificador_k() {
+        assert_eq!(format_rut("5K"), "5-K");
+        assert_eq!(format_rut("12.345.678-k"), "12345678-K");
+        // Una K que no es el último carácter no es un dígito del número
+        assert_eq!(format_rut("1K23"), "12-3");
+    }
+
+    #[test]
+    fn format_rut_mantiene_rut_formateado() {
+        assert_eq!(format_rut("12345678-9"), "12345678-9");
+        for input in ["12.345.678-9", "5k", "k", "9 999 999 9", "1-", "--7"] {
+            let formatted = format_rut(input);
+            assert_eq!(format_rut(&formatted), formatted, "entrada {:?}", input);
+        }
     }
 }
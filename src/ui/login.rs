@@ -1,21 +1,201 @@
 use eframe::egui;
+use tokio::sync::mpsc;
+use crate::config::{AppConfig, ProfileFile};
 use crate::models::DatabaseConfig;
 use crate::ui::app::App;
 
 pub struct LoginView {
     pub config: DatabaseConfig,
     pub connecting: bool,
-    #[allow(dead_code)]
-    show_password: bool,
+    // Prueba de credenciales sin abandonar la pantalla de login
+    pub testing: bool,
+    pub test_message: Option<(bool, String)>,
+
+    // Perfiles de conexión guardados
+    pub profiles: Vec<(String, DatabaseConfig)>,
+    pub selected_profile: Option<String>,
+    // Nombre en edición para "Guardar como..." (None = sin diálogo abierto)
+    pub new_profile_name: Option<String>,
+
+    // Exportación / importación del perfil de conexión
+    pub include_password: bool,
+    pub include_search_history: bool,
+    pub profile_message: Option<(bool, String)>,
+    profile_export_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+    profile_import_receiver: Option<mpsc::UnboundedReceiver<Result<ProfileFile, String>>>,
+    pub show_password: bool,
 }
 
 impl LoginView {
     pub fn new() -> Self {
-        Self {
-            config: DatabaseConfig::default(),
+        let app_config = AppConfig::load();
+        // Reabrir en el último perfil usado, si todavía existe
+        let selected = app_config.last_profile
+            .and_then(|name| app_config.profiles.iter().find(|(n, _)| *n == name).cloned());
+        let config = selected.as_ref().map(|(_, config)| config.clone()).unwrap_or_default();
+        let has_stored_passwords = app_config.profiles.iter().any(|(_, config)| !config.password.is_empty());
+
+        let view = Self {
+            config,
+            profiles: app_config.profiles,
+            selected_profile: selected.map(|(name, _)| name),
+            new_profile_name: None,
             connecting: false,
+            testing: false,
+            test_message: None,
+            include_password: false,
+            include_search_history: false,
+            profile_message: None,
+            profile_export_receiver: None,
+            profile_import_receiver: None,
             show_password: false,
+        };
+        // Versiones anteriores guardaban la contraseña de cada perfil: se borra del archivo
+        if has_stored_passwords {
+            view.persist_profiles();
+        }
+        view
+    }
+
+    pub fn select_profile(&mut self, name: Option<String>) {
+        if let Some(config) = name.as_ref()
+            .and_then(|name| self.profiles.iter().find(|(n, _)| n == name))
+            .map(|(_, config)| config.clone())
+        {
+            self.config = config;
+        }
+        self.selected_profile = name;
+    }
+
+    // Guarda el formulario actual con el nombre dado (reemplaza si ya existe)
+    pub fn save_profile_as(&mut self, name: &str) {
+        let name = name.trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        match self.profiles.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, config)) => *config = self.config.clone(),
+            None => self.profiles.push((name.clone(), self.config.clone())),
+        }
+        self.selected_profile = Some(name);
+        self.persist_profiles();
+    }
+
+    pub fn delete_selected_profile(&mut self) {
+        if let Some(name) = self.selected_profile.take() {
+            self.profiles.retain(|(n, _)| *n != name);
+            self.persist_profiles();
+        }
+    }
+
+    // Tras conectar, el perfil seleccionado guarda los valores usados
+    pub fn on_connected(&mut self) {
+        if let Some(name) = &self.selected_profile {
+            if let Some((_, config)) = self.profiles.iter_mut().find(|(n, _)| n == name) {
+                *config = self.config.clone();
+            }
+        }
+        self.persist_profiles();
+    }
+
+    // Las contraseñas solo se conservan en memoria durante la sesión; app_config.json nunca
+    // las guarda (la exportación de perfil las omite también salvo que se pida)
+    fn persist_profiles(&self) {
+        let profiles = self.profiles.iter()
+            .map(|(name, config)| (name.clone(), DatabaseConfig { password: String::new(), ..config.clone() }))
+            .collect();
+        let last_profile = self.selected_profile.clone();
+        AppConfig::update(|config| {
+            config.profiles = profiles;
+            config.last_profile = last_profile;
+        });
+    }
+
+    pub fn profile_busy(&self) -> bool {
+        self.profile_export_receiver.is_some() || self.profile_import_receiver.is_some()
+    }
+
+    pub fn export_profile(&mut self) {
+        self.profile_message = None;
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.profile_export_receiver = Some(rx);
+
+        let config = self.config.clone();
+        let include_password = self.include_password;
+        let include_search_history = self.include_search_history;
+        tokio::spawn(async move {
+            let Some(handle) = rfd::AsyncFileDialog::new()
+                .set_title("Exportar perfil")
+                .set_file_name(format!("perfil_{}.json", config.database))
+                .add_filter("JSON", &["json"])
+                .save_file()
+                .await
+            else {
+                let _ = tx.send(Err("Exportación cancelada".to_string()));
+                return;
+            };
+            let path = handle.path().to_path_buf();
+            let result = ProfileFile::export(&config, include_password, include_search_history, &path)
+                .map(|_| format!("Perfil exportado a {}", path.display()))
+                .map_err(|e| format!("Error al exportar perfil: {:#}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    pub fn import_profile(&mut self) {
+        self.profile_message = None;
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.profile_import_receiver = Some(rx);
+
+        tokio::spawn(async move {
+            let Some(handle) = rfd::AsyncFileDialog::new()
+                .set_title("Importar perfil")
+                .add_filter("JSON", &["json"])
+                .pick_file()
+                .await
+            else {
+                let _ = tx.send(Err("Importación cancelada".to_string()));
+                return;
+            };
+            let result = ProfileFile::import(handle.path())
+                .map_err(|e| format!("Error al importar perfil: {:#}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    // Aplica los resultados de exportar/importar; el perfil importado solo rellena
+    // el formulario, no conecta
+    pub fn check_profile_results(&mut self) -> bool {
+        if let Some(receiver) = &mut self.profile_export_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.profile_export_receiver = None;
+                self.profile_message = Some(match result {
+                    Ok(message) => (true, message),
+                    Err(message) => (false, message),
+                });
+                return true;
+            }
+        }
+        if let Some(receiver) = &mut self.profile_import_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.profile_import_receiver = None;
+                self.profile_message = Some(match result {
+                    Ok(profile) => {
+                        let password = std::mem::take(&mut self.config.password);
+                        self.config = profile.connection.clone();
+                        // Un perfil sin contraseña conserva la que ya estaba escrita
+                        if self.config.password.is_empty() {
+                            self.config.password = password;
+                        }
+                        profile.apply_preferences();
+                        (true, "Perfil importado; revise los datos y conecte".to_string())
+                    }
+                    Err(message) => (false, message),
+                });
+                return true;
+            }
         }
+        false
     }
 
     #[allow(dead_code)]
@@ -124,9 +304,7 @@ impl LoginView {
         self.connecting = true;
         
         // Validar que todos los campos estén llenos
-        if self.config.host.is_empty() || 
-           self.config.username.is_empty() || 
-           self.config.database.is_empty() {
+        if self.config.validation_error().is_some() {
             self.connecting = false;
             return false;
         }
@@ -1,9 +1,24 @@
 pub mod app;
 pub mod login;
+pub mod setup_wizard;
 pub mod dashboard;
 pub mod sidebar;
+pub mod status_bar;
 pub mod queries;
 pub mod insertions;
+pub mod persona_import;
 pub mod about;
+pub mod sql_console;
+pub mod search;
+pub mod calendar;
+pub mod catalogs;
+pub mod catalog_admin;
 pub mod components;
+pub mod field_help;
+pub mod toasts;
 pub mod theme;
+pub mod telefonos;
+pub mod grupo_personas;
+pub mod detail;
+pub mod rut_jump;
+pub mod recent_records;
@@ -0,0 +1,298 @@
+use eframe::egui;
+use std::sync::Arc;
+use tokio::sync::{Mutex, mpsc};
+use crate::config::AppConfig;
+use crate::database::DatabaseManager;
+use crate::import::{self, ColumnMapping, CsvData, ImportCatalogs, PersonaField};
+use crate::models::{Genero, Nacionalidad, UnidadVecinal};
+
+// Errores de importación que se listan en la ventana; el resto solo se cuenta
+const MAX_LISTED_ERRORS: usize = 50;
+
+// Personas que se insertan por cada toma de la conexión; entre lotes se libera para que
+// las consultas y el keep-alive no esperen a que termine un archivo grande
+const IMPORT_BATCH_SIZE: usize = 50;
+
+#[derive(Debug, Clone, Default)]
+struct ImportSummary {
+    imported: usize,
+    // "Fila N: motivo", numeradas como en el archivo (el encabezado es la fila 1)
+    errors: Vec<String>,
+}
+
+// Importación de personas desde CSV: elegir archivo, asignar columnas a campos e insertar
+pub struct PersonaImportDialog {
+    db_manager: Arc<Mutex<DatabaseManager>>,
+    open: bool,
+
+    // Archivo elegido y asignación de sus columnas
+    file_name: String,
+    data: Option<CsvData>,
+    mapping: ColumnMapping,
+
+    // Estado
+    loading_file: bool,
+    importing: bool,
+    error_message: Option<String>,
+    summary: Option<ImportSummary>,
+
+    // Canales asíncronos
+    file_receiver: Option<mpsc::UnboundedReceiver<Result<(String, CsvData), String>>>,
+    import_receiver: Option<mpsc::UnboundedReceiver<ImportSummary>>,
+}
+
+impl PersonaImportDialog {
+    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>) -> Self {
+        Self {
+            db_manager,
+            open: false,
+            file_name: String::new(),
+            data: None,
+            mapping: Vec::new(),
+            loading_file: false,
+            importing: false,
+            error_message: None,
+            summary: None,
+            file_receiver: None,
+            import_receiver: None,
+        }
+    }
+
+    pub fn is_busy(&self) -> bool {
+        self.loading_file || self.importing
+    }
+
+    // Pide el archivo y lo lee fuera del hilo de la UI
+    pub fn pick_file(&mut self) {
+        self.loading_file = true;
+        self.error_message = None;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.file_receiver = Some(rx);
+
+        tokio::spawn(async move {
+            let Some(handle) = rfd::AsyncFileDialog::new()
+                .set_title("Importar personas")
+                .add_filter("CSV", &["csv"])
+                .pick_file()
+                .await
+            else {
+                let _ = tx.send(Err("Importación cancelada".to_string()));
+                return;
+            };
+            let path = handle.path().to_path_buf();
+            let file_name = handle.file_name();
+
+            let result = tokio::task::spawn_blocking(move || import::read_csv(&path)).await;
+            let result = match result {
+                Ok(Ok(data)) => Ok((file_name, data)),
+                Ok(Err(e)) => Err(format!("Error al leer el archivo: {}", e)),
+                Err(e) => Err(format!("Error al leer el archivo: {}", e)),
+            };
+            let _ = tx.send(result);
+        });
+    }
+
+    fn check_file_result(&mut self) {
+        if let Some(receiver) = &mut self.file_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.loading_file = false;
+                self.file_receiver = None;
+                match result {
+                    Ok((file_name, data)) => {
+                        // Un archivo con los mismos encabezados reutiliza el último mapeo
+                        let signature = import::header_signature(&data.headers);
+                        self.mapping = AppConfig::load().persona_csv_mappings
+                            .remove(&signature)
+                            .filter(|mapping| mapping.len() == data.headers.len())
+                            .unwrap_or_else(|| import::guess_mapping(&data.headers));
+                        self.file_name = file_name;
+                        self.data = Some(data);
+                        self.summary = None;
+                        self.open = true;
+                    }
+                    Err(error_msg) => {
+                        self.error_message = Some(error_msg);
+                    }
+                }
+            }
+        }
+    }
+
+    // Devuelve el resultado de una importación terminada para mostrarlo en la vista
+    fn check_import_result(&mut self) -> Option<(bool, String)> {
+        let receiver = self.import_receiver.as_mut()?;
+        let summary = receiver.try_recv().ok()?;
+        self.importing = false;
+        self.import_receiver = None;
+
+        let message = if summary.errors.is_empty() {
+            format!("{} personas importadas", summary.imported)
+        } else {
+            format!("{} personas importadas, {} filas con errores", summary.imported, summary.errors.len())
+        };
+        let success = summary.imported > 0 || summary.errors.is_empty();
+        self.summary = Some(summary);
+        Some((success, message))
+    }
+
+    pub fn show_error(&self, ui: &mut egui::Ui) {
+        if let Some(error) = &self.error_message {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+    }
+
+    pub fn show(
+        &mut self,
+        ctx: &egui::Context,
+        generos: &[Genero],
+        nacionalidades: &[Nacionalidad],
+        unidades_vecinales: &[UnidadVecinal],
+    ) -> Option<(bool, String)> {
+        self.check_file_result();
+        let result = self.check_import_result();
+
+        if !self.open {
+            return result;
+        }
+        let Some(data) = self.data.take() else {
+            self.open = false;
+            return result;
+        };
+
+        let mut open = self.open;
+        let mut start_import = false;
+        egui::Window::new(format!("Importar personas: {}", self.file_name))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.label(format!("{} filas detectadas. Asigne cada columna a un campo o déjela sin usar.", data.rows.len()));
+                ui.add_space(8.0);
+
+                egui::ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    egui::Grid::new("persona_import_mapping")
+                        .num_columns(3)
+                        .striped(true)
+                        .spacing([15.0, 6.0])
+                        .show(ui, |ui| {
+                            ui.strong("Columna");
+                            ui.strong("Ejemplo");
+                            ui.strong("Campo");
+                            ui.end_row();
+
+                            for (column, header) in data.headers.iter().enumerate() {
+                                ui.label(header);
+                                ui.weak(data.rows.first().and_then(|row| row.get(column)).map(String::as_str).unwrap_or(""));
+                                self.mapping_combo(ui, column);
+                                ui.end_row();
+                            }
+                        });
+                });
+
+                ui.add_space(8.0);
+                let missing = import::missing_required(&self.mapping);
+                if !missing.is_empty() {
+                    ui.colored_label(egui::Color32::from_rgb(255, 165, 0), format!("Faltan campos obligatorios: {}", missing.join(", ")));
+                }
+                ui.horizontal(|ui| {
+                    let can_import = missing.is_empty() && !self.importing && !data.rows.is_empty();
+                    if ui.add_enabled(can_import, egui::Button::new(format!("📥 Importar {} filas", data.rows.len()))).clicked() {
+                        start_import = true;
+                    }
+                    if self.importing {
+                        ui.add(egui::widgets::Spinner::new().size(16.0));
+                    }
+                });
+
+                if let Some(summary) = &self.summary {
+                    ui.add_space(8.0);
+                    ui.separator();
+                    ui.label(format!("{} personas importadas", summary.imported));
+                    if !summary.errors.is_empty() {
+                        ui.colored_label(egui::Color32::RED, format!("{} filas no se importaron:", summary.errors.len()));
+                        egui::ScrollArea::vertical().id_source("persona_import_errors").max_height(150.0).show(ui, |ui| {
+                            for error in summary.errors.iter().take(MAX_LISTED_ERRORS) {
+                                ui.small(error);
+                            }
+                            if summary.errors.len() > MAX_LISTED_ERRORS {
+                                ui.small(format!("... y {} más", summary.errors.len() - MAX_LISTED_ERRORS));
+                            }
+                        });
+                    }
+                }
+            });
+
+        if start_import {
+            let catalogs = ImportCatalogs { generos, nacionalidades, unidades_vecinales };
+            self.start_import(&data, &catalogs);
+        }
+        self.data = Some(data);
+        // La ventana sigue abierta mientras la importación está en curso
+        self.open = open || self.importing;
+        result
+    }
+
+    // Selector de campo para una columna; elegir un campo ya asignado lo quita de la otra columna
+    fn mapping_combo(&mut self, ui: &mut egui::Ui, column: usize) {
+        let before = self.mapping[column];
+        egui::ComboBox::from_id_source(("persona_import_field", column))
+            .selected_text(before.map(|field| field.label()).unwrap_or("(no importar)"))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.mapping[column], None, "(no importar)");
+                for field in PersonaField::ALL {
+                    ui.selectable_value(&mut self.mapping[column], Some(field), field.label());
+                }
+            });
+
+        let after = self.mapping[column];
+        if after != before && after.is_some() {
+            for (other, mapped) in self.mapping.iter_mut().enumerate() {
+                if other != column && *mapped == after {
+                    *mapped = None;
+                }
+            }
+        }
+    }
+
+    fn start_import(&mut self, data: &CsvData, catalogs: &ImportCatalogs) {
+        // Recordar el mapeo para el próximo archivo con los mismos encabezados
+        let signature = import::header_signature(&data.headers);
+        let mapping = self.mapping.clone();
+        AppConfig::update(|config| {
+            config.persona_csv_mappings.insert(signature, mapping);
+        });
+
+        let min_age = AppConfig::load().min_age();
+        let mut summary = ImportSummary::default();
+        let mut personas = Vec::new();
+        for (row, &line) in data.rows.iter().zip(&data.row_numbers) {
+            match import::build_persona(row, &self.mapping, catalogs, min_age) {
+                Ok(persona) => personas.push((line, persona)),
+                Err(error) => summary.errors.push(format!("Fila {}: {}", line, error)),
+            }
+        }
+
+        self.importing = true;
+        self.summary = None;
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.import_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            for batch in personas.chunks(IMPORT_BATCH_SIZE) {
+                let db = db_manager.lock().await;
+                for (line, persona) in batch {
+                    match db.insert_persona(persona).await {
+                        Ok(_) => summary.imported += 1,
+                        Err(e) => summary.errors.push(format!("Fila {}: {}", line, e)),
+                    }
+                }
+            }
+            summary.errors.sort_by_key(|error| {
+                error.trim_start_matches("Fila ").split(':').next().and_then(|n| n.parse::<usize>().ok())
+            });
+            let _ = tx.send(summary);
+        });
+    }
+}
@@ -1,24 +1,188 @@
 use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{Mutex, mpsc};
+use tokio::sync::mpsc::error::TryRecvError;
+use tokio_postgres::CancelToken;
+use crate::config::{AppConfig, FilterCriteria};
 use crate::database::DatabaseManager;
+use crate::export::{self, ExportSection, ExportableRows};
 use crate::models::*;
 use crate::utils;
+use crate::ui::calendar::ActivityCalendar;
+use crate::ui::catalogs::SharedCatalogs;
+use crate::ui::toasts::SharedToasts;
+use crate::ui::components::{self, ConfirmDialog, HistoryAction};
+use crate::ui::detail::{DetailPanel, SelectedRecord};
 
-#[derive(Debug, Clone, PartialEq)]
-enum QueryType {
+// Token de cancelación que la tarea de consulta publica al obtener la conexión
+type CancelSlot = Arc<std::sync::Mutex<Option<CancelToken>>>;
+
+// Pausa de escritura antes de lanzar la búsqueda en vivo
+const LIVE_SEARCH_DEBOUNCE: Duration = Duration::from_millis(400);
+
+// Antigüedad máxima de un resultado cacheado antes de volver a consultar la base
+const QUERY_CACHE_TTL: Duration = Duration::from_secs(30);
+
+// Columnas de las tablas con selector de columnas visibles. Los índices de estos arreglos
+// son los que usan las funciones show_*_results para dibujar cada celda.
+const PERSONA_COLUMNS: [&str; 8] = ["RUT", "Nombre", "Apellidos", "Edad", "Género", "Nacionalidad", "Email", "UV"];
+const ORGANIZACION_COLUMNS: [&str; 5] = ["Nombre", "Dirección", "Fecha Const.", "Email", "UV"];
+const ACTIVIDAD_COLUMNS: [&str; 6] = ["Nombre", "Fecha Inicio", "Fecha Fin", "Estado", "UV", "Taller"];
+
+// Valores distintos que se recuerdan por campo de búsqueda
+const MAX_SEARCH_HISTORY: usize = 10;
+
+// Campos del filtro de personas con historial; la clave identifica al campo en la config
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum HistoryField {
+    Nombre,
+    Apellido,
+    Rut,
+}
+
+impl HistoryField {
+    const ALL: [HistoryField; 3] = [HistoryField::Nombre, HistoryField::Apellido, HistoryField::Rut];
+
+    fn key(self) -> &'static str {
+        match self {
+            HistoryField::Nombre => "persona_nombre",
+            HistoryField::Apellido => "persona_apellido",
+            HistoryField::Rut => "persona_rut",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum QueryType {
     Personas,
     Organizaciones,
     Actividades,
     Viajes,
+    Beneficios,
+    Centros,
+    Talleres,
+}
+
+// Color de la etiqueta de estado de una actividad
+fn estado_color(estado: ActividadEstado) -> egui::Color32 {
+    match estado {
+        ActividadEstado::Proxima => egui::Color32::LIGHT_BLUE,
+        ActividadEstado::EnCurso => egui::Color32::LIGHT_GREEN,
+        ActividadEstado::Finalizada => egui::Color32::GRAY,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ExportFormat {
+    Csv,
+    Xlsx,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum QueryResult {
     Personas(Vec<PersonaMayor>),
     Organizaciones(Vec<OrganizacionComunitaria>),
     Actividades(Vec<Actividad>),
     Viajes(Vec<Viaje>),
+    Beneficios(Vec<Beneficio>),
+    Centros(Vec<CentroComunitario>),
+    Talleres(Vec<Taller>),
+}
+
+// Identifica un resultado en la caché: tipo de consulta, filtros y página. Los filtros se
+// comparan por su representación Debug; `None` es la carga automática sin filtros.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct QueryKey {
+    query_type: QueryType,
+    filters: Option<String>,
+    limit: Option<i64>,
+    offset: i64,
+}
+
+// Filtros de todos los tipos de consulta; la carga automática usa los vacíos
+#[derive(Debug, Clone, Default)]
+struct QueryFilters {
+    persona: PersonaFilter,
+    organizacion: OrganizacionFilter,
+    actividad: ActividadFilter,
+    centro: CentroFilter,
+    viaje: ViajeFilter,
+}
+
+// Escribe el archivo exportado fuera del hilo de la UI
+async fn write_export<T: ExportableRows + Send + 'static>(
+    sections: Vec<ExportSection<T>>,
+    nombre: &'static str,
+    format: ExportFormat,
+    path: std::path::PathBuf,
+) -> Result<String, String> {
+    let result = tokio::task::spawn_blocking(move || {
+        let written = match format {
+            ExportFormat::Csv => export::write_csv(&sections, &path),
+            ExportFormat::Xlsx => export::write_xlsx(&sections, nombre, &path),
+        };
+        let total: usize = sections.iter().map(|section| section.rows.len()).sum();
+        written
+            .map(|_| format!("{} registros exportados a {}", total, path.display()))
+            .map_err(|e| format!("Error al exportar: {}", e))
+    }).await;
+    result.unwrap_or_else(|e| Err(format!("Error al exportar: {}", e)))
+}
+
+// Página pedida de la consulta del tipo indicado, junto al total de filas del filtro
+async fn fetch_results(
+    db: &DatabaseManager,
+    query_type: &QueryType,
+    filters: &QueryFilters,
+    page: &PageRequest,
+) -> Result<(QueryResult, i64), String> {
+    match query_type {
+        QueryType::Personas => {
+            match db.get_personas_mayores(&filters.persona, page).await {
+                Ok(paged) => Ok((QueryResult::Personas(paged.rows), paged.total)),
+                Err(e) => Err(format!("Error al consultar personas: {}", e)),
+            }
+        }
+        QueryType::Organizaciones => {
+            match db.get_organizaciones(&filters.organizacion, page).await {
+                Ok(paged) => Ok((QueryResult::Organizaciones(paged.rows), paged.total)),
+                Err(e) => Err(format!("Error al consultar organizaciones: {}", e)),
+            }
+        }
+        QueryType::Actividades => {
+            match db.get_actividades(&filters.actividad, page).await {
+                Ok(paged) => Ok((QueryResult::Actividades(paged.rows), paged.total)),
+                Err(e) => Err(format!("Error al consultar actividades: {}", e)),
+            }
+        }
+        QueryType::Viajes => {
+            match db.get_viajes(&filters.viaje, page).await {
+                Ok(paged) => Ok((QueryResult::Viajes(paged.rows), paged.total)),
+                Err(e) => Err(format!("Error al consultar viajes: {}", e)),
+            }
+        }
+        QueryType::Beneficios => {
+            match db.get_beneficios(page).await {
+                Ok(paged) => Ok((QueryResult::Beneficios(paged.rows), paged.total)),
+                Err(e) => Err(format!("Error al consultar beneficios: {}", e)),
+            }
+        }
+        QueryType::Centros => {
+            match db.get_centros(&filters.centro, page).await {
+                Ok(paged) => Ok((QueryResult::Centros(paged.rows), paged.total)),
+                Err(e) => Err(format!("Error al consultar centros: {}", e)),
+            }
+        }
+        QueryType::Talleres => {
+            match db.get_talleres(page).await {
+                Ok(paged) => Ok((QueryResult::Talleres(paged.rows), paged.total)),
+                Err(e) => Err(format!("Error al consultar talleres: {}", e)),
+            }
+        }
+    }
 }
 
 pub struct QueriesView {
@@ -29,46 +193,181 @@ pub struct QueriesView {
     persona_filter: PersonaFilter,
     organizacion_filter: OrganizacionFilter,
     actividad_filter: ActividadFilter,
+    centro_filter: CentroFilter,
+    viaje_filter: ViajeFilter,
+    // Texto de las fechas del filtro de viajes (dd/mm/yyyy)
+    viaje_salida_desde: String,
+    viaje_salida_hasta: String,
+    // Texto de las fechas de constitución del filtro de organizaciones (dd/mm/yyyy)
+    org_const_desde: String,
+    org_const_hasta: String,
+    // Texto del rango de fechas de inicio del filtro de actividades (dd/mm/yyyy)
+    act_fecha_desde: String,
+    act_fecha_hasta: String,
     
     // Resultados
     personas_results: Vec<PersonaMayor>,
     organizaciones_results: Vec<OrganizacionComunitaria>,
     actividades_results: Vec<Actividad>,
     viajes_results: Vec<Viaje>,
+    beneficios_results: Vec<Beneficio>,
+    centros_results: Vec<CentroComunitario>,
+    talleres_results: Vec<Taller>,
+    
+    // Selección múltiple de personas y reasignación de UV en lote
+    selected_personas: HashSet<i32>,
+    reassign_open: bool,
+    reassign_uv: Option<i32>,
+    reassigning: bool,
+    reassign_message: Option<(bool, String)>,
+    reassign_receiver: Option<mpsc::UnboundedReceiver<Result<u64, String>>>,
+    
+    // Paginación: tamaño de página persistido, página actual (desde 0) y total de filas
+    page_size: i64,
+    page: i64,
+    total_count: i64,
+    show_all: bool,
+
+    // Con "Mostrar todos" se cuentan las filas antes de cargarlas; sobre `row_limit` se pide
+    // confirmación. `large_query_pending` indica si la consulta a repetir lleva filtros
+    row_limit: i64,
+    row_count_receiver: Option<mpsc::UnboundedReceiver<(bool, Result<i64, String>)>>,
+    large_query_confirm: ConfirmDialog,
+    large_query_pending: Option<bool>,
+    large_query_confirmed: bool,
+    
+    // Vista de actividades: lista o calendario mensual
+    actividades_calendar: bool,
+    calendar: ActivityCalendar,
     
-    // Catálogos para filtros
+    // Catálogos para filtros (copia local de los catálogos compartidos)
+    catalogs: SharedCatalogs,
+    catalogs_version: u64,
     generos: Vec<Genero>,
     nacionalidades: Vec<Nacionalidad>,
     unidades_vecinales: Vec<UnidadVecinal>,
     macro_sectores: Vec<MacroSector>,
+    talleres: Vec<Taller>,
+    
+    // Panel de detalle del registro seleccionado
+    detail_panel: DetailPanel,
     
     // Estado
     loading: bool,
-    catalogs_loaded: bool,
+    query_error: Option<String>,
+    // Avisos de exportaciones y criterios (cola compartida de App)
+    toasts: SharedToasts,
+    
+    // Búsqueda en vivo: se consulta tras una pausa al escribir en los filtros
+    live_search: bool,
+    // Exportar personas agrupadas y ordenadas por unidad vecinal
+    export_group_by_uv: bool,
+    last_filter_edit: Option<Instant>,
+    
+    // Duración de la última consulta, medida desde su envío hasta la llegada del resultado
+    query_started: Option<Instant>,
+    
+    // Solicitud de foco para el primer campo de filtro (Ctrl+F)
+    focus_filter: bool,
+    
+    // Historial de búsquedas de los campos de texto del filtro de personas
+    search_history: HashMap<String, Vec<String>>,
+    
+    // Columnas ocultas por tabla de resultados
+    hidden_columns: HashMap<String, Vec<String>>,
+    
+    // Exportación de resultados; el mensaje también informa al guardar o cargar criterios
+    exporting: bool,
+    export_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+    // Filtros de la consulta que produjo los resultados; la exportación la repite sin paginar
+    results_filters: QueryFilters,
+    
+    // Criterios de búsqueda guardados en archivo
+    criteria_save_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+    criteria_load_receiver: Option<mpsc::UnboundedReceiver<Result<FilterCriteria, String>>>,
+    last_query_duration: Option<Duration>,
+    
+    // Resultados recientes por consulta, para no repetirla al cambiar de tipo o de página
+    query_cache: HashMap<QueryKey, (Instant, QueryResult, i64)>,
+    pending_query: Option<QueryKey>,
+    from_cache: bool,
     
     // Canales asíncronos
-    query_receiver: Option<mpsc::UnboundedReceiver<Result<QueryResult, String>>>,
+    query_receiver: Option<mpsc::UnboundedReceiver<Result<(QueryResult, i64), String>>>,
+    query_cancel: Option<CancelSlot>,
 }
 
 impl QueriesView {
-    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>) -> Self {
+    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>, catalogs: SharedCatalogs, toasts: SharedToasts) -> Self {
+        let config = AppConfig::load();
+        let row_limit = config.row_limit_warning();
         let instance = Self {
+            detail_panel: DetailPanel::new(db_manager.clone(), toasts.clone()),
             db_manager,
-            query_type: QueryType::Personas,
+            query_type: config.query_type.unwrap_or(QueryType::Personas),
             persona_filter: PersonaFilter::default(),
             organizacion_filter: OrganizacionFilter::default(),
             actividad_filter: ActividadFilter::default(),
+            centro_filter: CentroFilter::default(),
+            viaje_filter: ViajeFilter::default(),
+            viaje_salida_desde: String::new(),
+            viaje_salida_hasta: String::new(),
+            org_const_desde: String::new(),
+            org_const_hasta: String::new(),
+            act_fecha_desde: String::new(),
+            act_fecha_hasta: String::new(),
             personas_results: Vec::new(),
             organizaciones_results: Vec::new(),
             actividades_results: Vec::new(),
             viajes_results: Vec::new(),
+            beneficios_results: Vec::new(),
+            centros_results: Vec::new(),
+            talleres_results: Vec::new(),
+            selected_personas: HashSet::new(),
+            reassign_open: false,
+            reassign_uv: None,
+            reassigning: false,
+            reassign_message: None,
+            reassign_receiver: None,
+            page_size: config.page_size.unwrap_or(DEFAULT_PAGE_SIZE),
+            page: 0,
+            total_count: 0,
+            show_all: false,
+            row_limit,
+            row_count_receiver: None,
+            large_query_confirm: ConfirmDialog::new(),
+            large_query_pending: None,
+            large_query_confirmed: false,
+            actividades_calendar: false,
+            calendar: ActivityCalendar::new(),
+            catalogs,
+            catalogs_version: 0,
             generos: Vec::new(),
             nacionalidades: Vec::new(),
             unidades_vecinales: Vec::new(),
             macro_sectores: Vec::new(),
+            talleres: Vec::new(),
             loading: false,
-            catalogs_loaded: false,
+            query_error: None,
+            toasts,
+            live_search: false,
+            export_group_by_uv: false,
+            last_filter_edit: None,
+            query_started: None,
+            focus_filter: false,
+            search_history: config.search_history,
+            hidden_columns: config.hidden_columns,
+            exporting: false,
+            export_receiver: None,
+            results_filters: QueryFilters::default(),
+            criteria_save_receiver: None,
+            criteria_load_receiver: None,
+            last_query_duration: None,
+            query_cache: HashMap::new(),
+            pending_query: None,
+            from_cache: false,
             query_receiver: None,
+            query_cancel: None,
         };
         
         // NO ejecutar consultas automáticas aquí - se harán cuando haya conexión
@@ -78,39 +377,44 @@ impl QueriesView {
 
     // Función pública para inicializar datos una vez conectado
     pub fn initialize_data(&mut self) {
-        self.load_catalogs();
-        self.execute_initial_query();
+        // Cargar sin filtros el último tipo de consulta usado
+        self.execute_auto_query();
     }
 
     pub fn check_query_result(&mut self) -> bool {
+        self.check_row_count_result();
         if let Some(receiver) = &mut self.query_receiver {
             if let Ok(result) = receiver.try_recv() {
                 self.loading = false;
+                self.query_cancel = None;
+                self.last_query_duration = self.query_started.take().map(|started| started.elapsed());
                 match result {
-                    Ok(query_result) => {
-                        match query_result {
-                            QueryResult::Personas(personas) => {
-                                self.personas_results = personas;
-                            }
-                            QueryResult::Organizaciones(organizaciones) => {
-                                self.organizaciones_results = organizaciones;
-                            }
-                            QueryResult::Actividades(actividades) => {
-                                self.actividades_results = actividades;
-                            }
-                            QueryResult::Viajes(viajes) => {
-                                self.viajes_results = viajes;
-                            }
+                    Ok((query_result, total)) => {
+                        if let Some(key) = self.pending_query.take() {
+                            self.query_cache.insert(key, (Instant::now(), query_result.clone(), total));
                         }
+                        self.apply_query_result(query_result, total);
                         self.query_receiver = None;
+                        // Si desde la última consulta se borraron filas, la página pedida puede
+                        // quedar fuera de rango: se pasa a la última página que tenga datos
+                        if self.page > 0 && !self.show_all && self.page * self.page_size >= total {
+                            self.page = ((total - 1) / self.page_size).max(0);
+                            self.run_query();
+                        }
                         return true;
                     }
-                    Err(_error_msg) => {
-                        // En caso de error, limpiar resultados
+                    Err(error_msg) => {
+                        // En caso de error, limpiar resultados y mostrar el motivo
+                        self.pending_query = None;
+                        self.query_error = Some(error_msg);
+                        self.total_count = 0;
                         self.personas_results.clear();
                         self.organizaciones_results.clear();
                         self.actividades_results.clear();
                         self.viajes_results.clear();
+                        self.beneficios_results.clear();
+                        self.centros_results.clear();
+                        self.talleres_results.clear();
                         self.query_receiver = None;
                         return true;
                     }
@@ -120,17 +424,234 @@ impl QueriesView {
         false
     }
 
-    pub fn show(&mut self, ui: &mut egui::Ui) {
+    fn check_row_count_result(&mut self) {
+        let Some(receiver) = &mut self.row_count_receiver else {
+            return;
+        };
+        let Ok((filtered, result)) = receiver.try_recv() else {
+            return;
+        };
+        self.row_count_receiver = None;
+        self.loading = false;
+        self.query_cancel = None;
+        self.query_started = None;
+        match result {
+            Ok(total) if total > self.row_limit => {
+                self.large_query_pending = Some(filtered);
+                self.large_query_confirm.open(
+                    "Consulta grande",
+                    format!(
+                        "La consulta devolverá {} filas, ¿continuar?\n\nSi cancela se muestra solo la primera página para que pueda acotar los filtros.",
+                        utils::format_number(total)
+                    ),
+                );
+            }
+            Ok(_) => self.rerun_counted_query(filtered),
+            Err(error_msg) => {
+                self.pending_query = None;
+                self.query_error = Some(error_msg);
+            }
+        }
+    }
+
+    // Repite la consulta ya contada, esta vez cargando todas las filas
+    fn rerun_counted_query(&mut self, filtered: bool) {
+        self.large_query_confirmed = true;
+        if filtered {
+            self.run_query();
+        } else {
+            self.execute_auto_query();
+        }
+    }
+
+    // Con "Mostrar todos" cuenta primero las filas de la consulta para no cargar por
+    // accidente una tabla completa. Devuelve true si la consulta queda esperando el conteo.
+    fn start_row_count(&mut self, filtered: bool) -> bool {
+        if !self.show_all || std::mem::take(&mut self.large_query_confirmed) {
+            return false;
+        }
+        self.loading = true;
+        self.query_error = None;
+        self.query_started = Some(Instant::now());
+        // El resultado de una consulta anterior aún en curso ya no corresponde
+        self.query_receiver = None;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.row_count_receiver = Some(rx);
+        let cancel_slot = self.new_cancel_slot();
+
+        let db_manager = self.db_manager.clone();
+        let query_type = self.query_type.clone();
+        let filters = if filtered { self.current_filters() } else { QueryFilters::default() };
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            *cancel_slot.lock().unwrap() = db.cancel_token();
+            // Una página vacía solo trae el COUNT del filtro
+            let count_page = PageRequest { limit: Some(0), offset: 0 };
+            let result = fetch_results(&db, &query_type, &filters, &count_page).await
+                .map(|(_, total)| total);
+            let _ = tx.send((filtered, result));
+        });
+        true
+    }
+
+    fn current_filters(&self) -> QueryFilters {
+        QueryFilters {
+            persona: self.persona_filter.clone(),
+            organizacion: self.organizacion_filter.clone(),
+            actividad: self.actividad_filter.clone(),
+            centro: self.centro_filter.clone(),
+            viaje: self.viaje_filter.clone(),
+        }
+    }
+
+    fn apply_query_result(&mut self, query_result: QueryResult, total: i64) {
+        self.total_count = total;
+        match query_result {
+            QueryResult::Personas(personas) => {
+                self.personas_results = personas;
+                self.selected_personas.clear();
+            }
+            QueryResult::Organizaciones(organizaciones) => {
+                self.organizaciones_results = organizaciones;
+            }
+            QueryResult::Actividades(actividades) => {
+                self.actividades_results = actividades;
+            }
+            QueryResult::Viajes(viajes) => {
+                self.viajes_results = viajes;
+            }
+            QueryResult::Beneficios(beneficios) => {
+                self.beneficios_results = beneficios;
+            }
+            QueryResult::Centros(centros) => {
+                self.centros_results = centros;
+            }
+            QueryResult::Talleres(talleres) => {
+                self.talleres_results = talleres;
+            }
+        }
+        self.query_error = None;
+    }
+
+    fn query_key(&self, filtered: bool) -> QueryKey {
+        let filters = filtered.then(|| match self.query_type {
+            QueryType::Personas => format!("{:?}", self.persona_filter),
+            QueryType::Organizaciones => format!("{:?}", self.organizacion_filter),
+            QueryType::Actividades => format!("{:?}", self.actividad_filter),
+            QueryType::Viajes => format!("{:?}", self.viaje_filter),
+            QueryType::Centros => format!("{:?}", self.centro_filter),
+            QueryType::Beneficios | QueryType::Talleres => String::new(),
+        });
+        let page = self.page_request();
+        QueryKey { query_type: self.query_type.clone(), filters, limit: page.limit, offset: page.offset }
+    }
+
+    // Muestra el resultado cacheado de la consulta si aún no vence; si no, la deja
+    // pendiente para guardarla cuando llegue. Devuelve true si no hace falta consultar.
+    fn use_cached(&mut self, key: QueryKey) -> bool {
+        self.query_cache.retain(|_, (cached_at, _, _)| cached_at.elapsed() < QUERY_CACHE_TTL);
+        if let Some((_, query_result, total)) = self.query_cache.get(&key).cloned() {
+            // Una consulta anterior aún en curso ya no corresponde a lo que se muestra
+            self.query_receiver = None;
+            self.query_cancel = None;
+            self.pending_query = None;
+            self.loading = false;
+            self.query_started = None;
+            self.last_query_duration = None;
+            self.from_cache = true;
+            self.apply_query_result(query_result, total);
+            return true;
+        }
+        self.pending_query = Some(key);
+        self.from_cache = false;
+        false
+    }
+
+    // Descarta los resultados cacheados de un tipo de consulta tras modificar sus datos
+    pub fn invalidate_cache(&mut self, query_type: &QueryType) {
+        self.query_cache.retain(|key, _| key.query_type != *query_type);
+    }
+
+    // Descarta toda la caché, p. ej. cuando cambia un catálogo cuyo nombre aparece en
+    // varios listados
+    pub fn clear_cache(&mut self) {
+        self.query_cache.clear();
+    }
+
+    // Copia los catálogos compartidos cuando cambian
+    fn sync_catalogs(&mut self) {
+        let catalogs = self.catalogs.lock().unwrap();
+        if catalogs.version != self.catalogs_version {
+            self.query_cache.clear();
+            self.catalogs_version = catalogs.version;
+            self.generos = catalogs.generos.clone();
+            self.nacionalidades = catalogs.nacionalidades.clone();
+            self.unidades_vecinales = catalogs.unidades_vecinales.clone();
+            self.macro_sectores = catalogs.macro_sectores.clone();
+            self.talleres = catalogs.talleres.clone();
+        }
+    }
+
+    // Devuelve true cuando se eliminó un registro, para actualizar los totales
+    pub fn show(&mut self, ui: &mut egui::Ui) -> bool {
+        self.sync_catalogs();
+
         // Check for async query results
         self.check_query_result();
+        self.check_export_result();
+        self.check_criteria_results();
+        self.check_reassign_result();
 
-        ui.heading("🔍 Consultas con Filtros");
-        ui.add_space(10.0);
+        match self.large_query_confirm.show(ui) {
+            Some(true) => {
+                if let Some(filtered) = self.large_query_pending.take() {
+                    self.rerun_counted_query(filtered);
+                }
+            }
+            Some(false) => {
+                // Se vuelve a la primera página en lugar de cargarlo todo
+                if let Some(filtered) = self.large_query_pending.take() {
+                    self.show_all = false;
+                    if filtered {
+                        self.execute_query();
+                    } else {
+                        self.execute_auto_query();
+                    }
+                }
+            }
+            None => {}
+        }
+
+        // Ejecutar la búsqueda en vivo pendiente una vez pasado el debounce
+        if let Some(last_edit) = self.last_filter_edit {
+            if last_edit.elapsed() >= LIVE_SEARCH_DEBOUNCE {
+                self.last_filter_edit = None;
+                self.execute_query();
+            } else {
+                ui.ctx().request_repaint_after(LIVE_SEARCH_DEBOUNCE - last_edit.elapsed());
+            }
+        }
 
-        if !self.catalogs_loaded {
-            self.load_catalogs();
+        // Panel lateral de detalle (debe agregarse antes del contenido central)
+        let mut deleted = false;
+        if self.detail_panel.is_open() {
+            egui::SidePanel::right("detail_panel")
+                .resizable(true)
+                .default_width(320.0)
+                .show_inside(ui, |ui| {
+                    deleted = self.detail_panel.show(ui);
+                });
+            if deleted {
+                self.invalidate_cache(&self.query_type.clone());
+                self.run_query();
+            }
         }
 
+        ui.heading("🔍 Consultas con Filtros");
+        ui.add_space(10.0);
+        self.detail_panel.show_message(ui);
+
         // Selector de tipo de consulta
         ui.horizontal(|ui| {
             ui.label("Tipo de consulta:");
@@ -142,10 +663,15 @@ impl QueriesView {
                     ui.selectable_value(&mut self.query_type, QueryType::Organizaciones, "Organizaciones");
                     ui.selectable_value(&mut self.query_type, QueryType::Actividades, "Actividades");
                     ui.selectable_value(&mut self.query_type, QueryType::Viajes, "Viajes");
+                    ui.selectable_value(&mut self.query_type, QueryType::Beneficios, "Beneficios");
+                    ui.selectable_value(&mut self.query_type, QueryType::Centros, "Centros Comunitarios");
+                    ui.selectable_value(&mut self.query_type, QueryType::Talleres, "Talleres");
                 });
             
             // Si cambió el tipo de consulta, ejecutar automáticamente
             if previous_query_type != self.query_type {
+                let query_type = self.query_type.clone();
+                AppConfig::update(|config| config.query_type = Some(query_type));
                 self.execute_auto_query();
             }
         });
@@ -155,12 +681,20 @@ impl QueriesView {
         // Panel de filtros
         egui::CollapsingHeader::new("🎛️ Filtros")
             .default_open(true)
+            .open(self.focus_filter.then_some(true))
             .show(ui, |ui| {
                 match self.query_type {
                     QueryType::Personas => self.show_persona_filters(ui),
                     QueryType::Organizaciones => self.show_organizacion_filters(ui),
                     QueryType::Actividades => self.show_actividad_filters(ui),
                     QueryType::Viajes => self.show_viaje_filters(ui),
+                    QueryType::Beneficios => {
+                        ui.label("Los beneficios se listan sin filtros.");
+                    }
+                    QueryType::Centros => self.show_centro_filters(ui),
+                    QueryType::Talleres => {
+                        ui.label("Los talleres se listan sin filtros.");
+                    }
                 }
             });
 
@@ -169,15 +703,50 @@ impl QueriesView {
         // Botón de búsqueda
         ui.horizontal(|ui| {
             if ui.button("🔍 Buscar").clicked() {
-                self.execute_query();
+                self.execute_search();
             }
             
             if ui.button("🧹 Limpiar filtros").clicked() {
                 self.clear_filters();
             }
 
+            let criteria_idle = self.criteria_save_receiver.is_none() && self.criteria_load_receiver.is_none();
+            if ui.add_enabled(criteria_idle && self.current_criteria().is_some(), egui::Button::new("💾 Guardar criterios"))
+                .on_hover_text("Guarda los filtros actuales en un archivo JSON")
+                .clicked()
+            {
+                self.save_criteria();
+            }
+            if ui.add_enabled(criteria_idle, egui::Button::new("📂 Cargar criterios"))
+                .on_hover_text("Restaura filtros guardados y repite la búsqueda")
+                .clicked()
+            {
+                self.load_criteria();
+            }
+
+            ui.checkbox(&mut self.live_search, "Búsqueda en vivo");
+
+            ui.separator();
+            let can_export = self.has_exportable_results() && !self.exporting;
+            if ui.add_enabled(can_export, egui::Button::new("📄 Exportar CSV")).clicked() {
+                self.export_results(ExportFormat::Csv);
+            }
+            if ui.add_enabled(can_export, egui::Button::new("📊 Exportar Excel")).clicked() {
+                self.export_results(ExportFormat::Xlsx);
+            }
+            if self.query_type == QueryType::Personas {
+                ui.checkbox(&mut self.export_group_by_uv, "Agrupar por unidad vecinal")
+                    .on_hover_text("Exporta una sección por UV, ordenada por UV y apellido, para planificar recorridos");
+            }
+            if self.exporting {
+                ui.add(egui::widgets::Spinner::new().size(16.0));
+            }
+
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if self.loading {
+                    if ui.button("✖ Cancelar").clicked() {
+                        self.cancel_query();
+                    }
                     ui.add(egui::widgets::Spinner::new().size(16.0));
                     ui.label("Buscando...");
                 }
@@ -190,6 +759,7 @@ impl QueriesView {
 
         // Resultados
         self.show_results(ui);
+        deleted
     }
 
     fn show_persona_filters(&mut self, ui: &mut egui::Ui) {
@@ -197,17 +767,19 @@ impl QueriesView {
             .num_columns(4)
             .spacing([10.0, 10.0])
             .show(ui, |ui| {
-                ui.label("Nombre:");
-                ui.text_edit_singleline(&mut self.persona_filter.nombre);
+                components::field_label(ui, "Nombre");
+                let response = self.history_filter_field(ui, HistoryField::Nombre);
+                self.apply_filter_focus(&response);
                 
-                ui.label("Apellido:");
-                ui.text_edit_singleline(&mut self.persona_filter.apellido);
+                components::field_label(ui, "Apellido");
+                self.history_filter_field(ui, HistoryField::Apellido);
                 ui.end_row();
 
-                ui.label("RUT:");
-                ui.text_edit_singleline(&mut self.persona_filter.rut);
+                components::field_label(ui, "RUT");
+                self.history_filter_field(ui, HistoryField::Rut);
                 
-                ui.label("Género:");
+                components::field_label(ui, "Género");
+                let before = self.persona_filter.genero_id;
                 egui::ComboBox::from_id_source("genero_filter")
                     .selected_text(
                         self.persona_filter.genero_id
@@ -225,51 +797,45 @@ impl QueriesView {
                             );
                         }
                     });
+                if self.persona_filter.genero_id != before {
+                    self.on_filter_text_changed();
+                }
                 ui.end_row();
 
-                ui.label("Macrosector:");
-                egui::ComboBox::from_id_source("macro_filter")
-                    .selected_text(
-                        self.persona_filter.macro_sector_id
-                            .and_then(|id| self.macro_sectores.iter().find(|m| m.mac_id == id))
-                            .map(|m| m.mac_nombre.clone())
-                            .unwrap_or_else(|| "Todos".to_string())
-                    )
-                    .show_ui(ui, |ui| {
-                        ui.selectable_value(&mut self.persona_filter.macro_sector_id, None, "Todos");
-                        for macro_sector in &self.macro_sectores {
-                            ui.selectable_value(
-                                &mut self.persona_filter.macro_sector_id,
-                                Some(macro_sector.mac_id),
-                                &macro_sector.mac_nombre
-                            );
-                        }
-                    });
+                components::field_label(ui, "Macrosector");
+                if components::macro_sector_combo(ui, "macro_filter", &mut self.persona_filter.macro_sector_id, &self.macro_sectores, "Todos") {
+                    components::clear_invalid_uv(&mut self.persona_filter.unidad_vecinal_id, &self.unidades_vecinales, self.persona_filter.macro_sector_id);
+                    self.on_filter_text_changed();
+                }
 
-                ui.label("Unidad Vecinal:");
-                egui::ComboBox::from_id_source("uv_filter")
+                components::field_label(ui, "Nacionalidad");
+                let before = self.persona_filter.nacionalidad_id;
+                egui::ComboBox::from_id_source("nacionalidad_filter")
                     .selected_text(
-                        self.persona_filter.unidad_vecinal_id
-                            .and_then(|id| self.unidades_vecinales.iter().find(|u| u.uv_id == id))
-                            .map(|u| u.uv_nombre.clone())
+                        self.persona_filter.nacionalidad_id
+                            .and_then(|id| self.nacionalidades.iter().find(|n| n.nac_id == id))
+                            .map(|n| n.nac_nacionalidad.clone())
                             .unwrap_or_else(|| "Todas".to_string())
                     )
                     .show_ui(ui, |ui| {
-                        ui.selectable_value(&mut self.persona_filter.unidad_vecinal_id, None, "Todas");
-                        for uv in &self.unidades_vecinales {
-                            // Filtrar por macrosector si está seleccionado
-                            if let Some(mac_id) = self.persona_filter.macro_sector_id {
-                                if uv.uv_macid != mac_id {
-                                    continue;
-                                }
-                            }
+                        ui.selectable_value(&mut self.persona_filter.nacionalidad_id, None, "Todas");
+                        for nacionalidad in &self.nacionalidades {
                             ui.selectable_value(
-                                &mut self.persona_filter.unidad_vecinal_id,
-                                Some(uv.uv_id),
-                                &uv.uv_nombre
+                                &mut self.persona_filter.nacionalidad_id,
+                                Some(nacionalidad.nac_id),
+                                &nacionalidad.nac_nacionalidad
                             );
                         }
                     });
+                if self.persona_filter.nacionalidad_id != before {
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+
+                components::field_label(ui, "Unidad Vecinal");
+                if components::uv_combo(ui, "uv_filter", &mut self.persona_filter.unidad_vecinal_id, &self.unidades_vecinales, self.persona_filter.macro_sector_id, "Todas") {
+                    self.on_filter_text_changed();
+                }
                 ui.end_row();
             });
     }
@@ -279,28 +845,37 @@ impl QueriesView {
             .num_columns(2)
             .spacing([10.0, 10.0])
             .show(ui, |ui| {
-                ui.label("Nombre:");
-                ui.text_edit_singleline(&mut self.organizacion_filter.nombre);
+                components::field_label(ui, "Nombre");
+                let response = ui.text_edit_singleline(&mut self.organizacion_filter.nombre);
+                self.apply_filter_focus(&response);
+                if response.changed() {
+                    self.on_filter_text_changed();
+                }
                 ui.end_row();
 
-                ui.label("Unidad Vecinal:");
-                egui::ComboBox::from_id_source("org_uv_filter")
-                    .selected_text(
-                        self.organizacion_filter.unidad_vecinal_id
-                            .and_then(|id| self.unidades_vecinales.iter().find(|u| u.uv_id == id))
-                            .map(|u| u.uv_nombre.clone())
-                            .unwrap_or_else(|| "Todas".to_string())
-                    )
-                    .show_ui(ui, |ui| {
-                        ui.selectable_value(&mut self.organizacion_filter.unidad_vecinal_id, None, "Todas");
-                        for uv in &self.unidades_vecinales {
-                            ui.selectable_value(
-                                &mut self.organizacion_filter.unidad_vecinal_id,
-                                Some(uv.uv_id),
-                                &uv.uv_nombre
-                            );
-                        }
-                    });
+                components::field_label(ui, "Macrosector");
+                if components::macro_sector_combo(ui, "org_macro_filter", &mut self.organizacion_filter.macro_sector_id, &self.macro_sectores, "Todos") {
+                    components::clear_invalid_uv(&mut self.organizacion_filter.unidad_vecinal_id, &self.unidades_vecinales, self.organizacion_filter.macro_sector_id);
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+
+                components::field_label(ui, "Unidad Vecinal");
+                if components::uv_combo(ui, "org_uv_filter", &mut self.organizacion_filter.unidad_vecinal_id, &self.unidades_vecinales, self.organizacion_filter.macro_sector_id, "Todas") {
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+
+                components::field_label(ui, "Constituida desde");
+                if components::filter_date_field(ui, &mut self.org_const_desde, &mut self.organizacion_filter.fecha_const_desde) {
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+
+                components::field_label(ui, "Constituida hasta");
+                if components::filter_date_field(ui, &mut self.org_const_hasta, &mut self.organizacion_filter.fecha_const_hasta) {
+                    self.on_filter_text_changed();
+                }
                 ui.end_row();
             });
     }
@@ -310,256 +885,1037 @@ impl QueriesView {
             .num_columns(2)
             .spacing([10.0, 10.0])
             .show(ui, |ui| {
-                ui.label("Nombre:");
-                ui.text_edit_singleline(&mut self.actividad_filter.nombre);
+                components::field_label(ui, "Nombre");
+                let response = ui.text_edit_singleline(&mut self.actividad_filter.nombre);
+                self.apply_filter_focus(&response);
+                if response.changed() {
+                    self.on_filter_text_changed();
+                }
                 ui.end_row();
 
-                ui.label("Unidad Vecinal:");
-                egui::ComboBox::from_id_source("act_uv_filter")
-                    .selected_text(
-                        self.actividad_filter.unidad_vecinal_id
-                            .and_then(|id| self.unidades_vecinales.iter().find(|u| u.uv_id == id))
-                            .map(|u| u.uv_nombre.clone())
-                            .unwrap_or_else(|| "Todas".to_string())
-                    )
+                components::field_label(ui, "Macrosector");
+                if components::macro_sector_combo(ui, "act_macro_filter", &mut self.actividad_filter.macro_sector_id, &self.macro_sectores, "Todos") {
+                    components::clear_invalid_uv(&mut self.actividad_filter.unidad_vecinal_id, &self.unidades_vecinales, self.actividad_filter.macro_sector_id);
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+
+                components::field_label(ui, "Unidad Vecinal");
+                if components::uv_combo(ui, "act_uv_filter", &mut self.actividad_filter.unidad_vecinal_id, &self.unidades_vecinales, self.actividad_filter.macro_sector_id, "Todas") {
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+
+                components::field_label(ui, "Inicio desde");
+                if components::filter_date_field(ui, &mut self.act_fecha_desde, &mut self.actividad_filter.fecha_desde) {
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+
+                components::field_label(ui, "Inicio hasta");
+                if components::filter_date_field(ui, &mut self.act_fecha_hasta, &mut self.actividad_filter.fecha_hasta) {
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+
+                components::field_label(ui, "Estado");
+                let before = self.actividad_filter.estado;
+                egui::ComboBox::from_id_source("act_estado_filter")
+                    .selected_text(self.actividad_filter.estado.map(|estado| estado.label()).unwrap_or("Todos"))
                     .show_ui(ui, |ui| {
-                        ui.selectable_value(&mut self.actividad_filter.unidad_vecinal_id, None, "Todas");
-                        for uv in &self.unidades_vecinales {
-                            ui.selectable_value(
-                                &mut self.actividad_filter.unidad_vecinal_id,
-                                Some(uv.uv_id),
-                                &uv.uv_nombre
-                            );
+                        ui.selectable_value(&mut self.actividad_filter.estado, None, "Todos");
+                        for estado in ActividadEstado::ALL {
+                            ui.selectable_value(&mut self.actividad_filter.estado, Some(estado), estado.label());
                         }
                     });
+                if self.actividad_filter.estado != before {
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+
+                components::field_label(ui, "Taller");
+                if components::taller_combo(ui, "act_taller_filter", &mut self.actividad_filter.taller_id, &self.talleres, "Todos") {
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+            });
+    }
+
+    fn show_centro_filters(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("cen_filters")
+            .num_columns(2)
+            .spacing([10.0, 10.0])
+            .show(ui, |ui| {
+                components::field_label(ui, "Nombre");
+                let response = ui.text_edit_singleline(&mut self.centro_filter.nombre);
+                self.apply_filter_focus(&response);
+                if response.changed() {
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+
+                components::field_label(ui, "Unidad Vecinal");
+                if components::uv_combo(ui, "cen_uv_filter", &mut self.centro_filter.unidad_vecinal_id, &self.unidades_vecinales, None, "Todas") {
+                    self.on_filter_text_changed();
+                }
                 ui.end_row();
             });
     }
 
     fn show_viaje_filters(&mut self, ui: &mut egui::Ui) {
-        ui.label("Filtros de viajes disponibles próximamente...");
+        egui::Grid::new("via_filters")
+            .num_columns(2)
+            .spacing([10.0, 10.0])
+            .show(ui, |ui| {
+                components::field_label(ui, "Nombre");
+                let response = ui.text_edit_singleline(&mut self.viaje_filter.nombre);
+                self.apply_filter_focus(&response);
+                if response.changed() {
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+
+                components::field_label(ui, "Destino");
+                if ui.text_edit_singleline(&mut self.viaje_filter.destino).changed() {
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+
+                components::field_label(ui, "Unidad Vecinal");
+                if components::uv_combo(ui, "via_uv_filter", &mut self.viaje_filter.unidad_vecinal_id, &self.unidades_vecinales, None, "Todas") {
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+
+                components::field_label(ui, "Salida desde");
+                if components::filter_date_field(ui, &mut self.viaje_salida_desde, &mut self.viaje_filter.fecha_salida_desde) {
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+
+                components::field_label(ui, "Salida hasta");
+                if components::filter_date_field(ui, &mut self.viaje_salida_hasta, &mut self.viaje_filter.fecha_salida_hasta) {
+                    self.on_filter_text_changed();
+                }
+                ui.end_row();
+            });
+    }
+
+    fn current_result_count(&self) -> usize {
+        match self.query_type {
+            QueryType::Personas => self.personas_results.len(),
+            QueryType::Organizaciones => self.organizaciones_results.len(),
+            QueryType::Actividades => self.actividades_results.len(),
+            QueryType::Viajes => self.viajes_results.len(),
+            QueryType::Beneficios => self.beneficios_results.len(),
+            QueryType::Centros => self.centros_results.len(),
+            QueryType::Talleres => self.talleres_results.len(),
+        }
     }
 
-    fn show_results(&self, ui: &mut egui::Ui) {
+    fn show_results(&mut self, ui: &mut egui::Ui) {
+        // Distinguir consulta en curso, error y resultado vacío antes de dibujar la tabla
+        if self.loading {
+            ui.vertical_centered(|ui| {
+                ui.add_space(40.0);
+                ui.add(egui::widgets::Spinner::new().size(32.0));
+                ui.add_space(8.0);
+                ui.label("Cargando resultados...");
+                ui.add_space(8.0);
+                if ui.button("✖ Cancelar").clicked() {
+                    self.cancel_query();
+                }
+            });
+            return;
+        }
+
+        if let Some(error) = self.query_error.clone() {
+            ui.vertical_centered(|ui| {
+                ui.add_space(40.0);
+                ui.colored_label(egui::Color32::RED, format!("❌ {}", error));
+                ui.add_space(8.0);
+                if ui.button("🔄 Reintentar").clicked() {
+                    self.run_query();
+                }
+            });
+            return;
+        }
+
+        if self.current_result_count() == 0 {
+            ui.vertical_centered(|ui| {
+                ui.add_space(40.0);
+                ui.label("No se encontraron resultados con estos filtros");
+            });
+            // Permite volver atrás si la página actual quedó vacía
+            if self.page > 0 {
+                self.show_pagination(ui);
+            }
+            return;
+        }
+
+        if self.query_type == QueryType::Personas {
+            self.show_persona_sort(ui);
+        }
+        self.show_pagination(ui);
+
         match self.query_type {
             QueryType::Personas => self.show_personas_results(ui),
             QueryType::Organizaciones => self.show_organizaciones_results(ui),
             QueryType::Actividades => self.show_actividades_results(ui),
             QueryType::Viajes => self.show_viajes_results(ui),
+            QueryType::Beneficios => self.show_beneficios_results(ui),
+            QueryType::Centros => self.show_centros_results(ui),
+            QueryType::Talleres => self.show_talleres_results(ui),
         }
     }
 
-    fn show_personas_results(&self, ui: &mut egui::Ui) {
-        ui.label(format!("Resultados: {} personas encontradas", self.personas_results.len()));
-        ui.add_space(10.0);
-
-        egui::ScrollArea::vertical().show(ui, |ui| {
-            egui::Grid::new("personas_results")
-                .striped(true)
-                .spacing([10.0, 8.0])
-                .show(ui, |ui| {
-                    // Encabezados
-                    ui.strong("RUT");
-                    ui.strong("Nombre");
-                    ui.strong("Apellidos");
-                    ui.strong("Edad");
-                    ui.strong("Género");
-                    ui.strong("UV");
-                    ui.end_row();
-
-                    // Datos
-                    for persona in &self.personas_results {
-                        ui.label(&persona.per_rut);
-                        ui.label(&persona.per_prinombre);
-                        ui.label(&format!("{} {}", 
-                            persona.per_priapellido, 
-                            persona.per_segapellido.as_deref().unwrap_or("")
-                        ));
-                        ui.label(utils::calculate_age(&persona.per_fechadenac).to_string());
-                        ui.label(persona.gen_genero.as_deref().unwrap_or("N/A"));
-                        ui.label(persona.uv_nombre.as_deref().unwrap_or("N/A"));
-                        ui.end_row();
+    // Orden del listado de personas; se aplica en el servidor, así que vale para todas las páginas
+    fn show_persona_sort(&mut self, ui: &mut egui::Ui) {
+        let previous = self.persona_filter.orden;
+        let orden = &mut self.persona_filter.orden;
+        ui.horizontal(|ui| {
+            ui.label("Ordenar por:");
+            egui::ComboBox::from_id_source("persona_sort_field")
+                .selected_text(orden.field.label())
+                .show_ui(ui, |ui| {
+                    for field in PersonaSortField::ALL {
+                        ui.selectable_value(&mut orden.field, field, field.label());
                     }
                 });
+            egui::ComboBox::from_id_source("persona_sort_dir")
+                .selected_text(if orden.descending { "Descendente" } else { "Ascendente" })
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut orden.descending, false, "Ascendente");
+                    ui.selectable_value(&mut orden.descending, true, "Descendente");
+                });
         });
+        if self.persona_filter.orden != previous {
+            self.execute_query();
+        }
     }
 
-    fn show_organizaciones_results(&self, ui: &mut egui::Ui) {
-        ui.label(format!("Resultados: {} organizaciones encontradas", self.organizaciones_results.len()));
-        ui.add_space(10.0);
-
-        egui::ScrollArea::vertical().show(ui, |ui| {
-            egui::Grid::new("org_results")
-                .striped(true)
-                .spacing([10.0, 8.0])
-                .show(ui, |ui| {
-                    // Encabezados
-                    ui.strong("Nombre");
-                    ui.strong("Dirección");
-                    ui.strong("Fecha Const.");
-                    ui.strong("UV");
-                    ui.end_row();
-
-                    // Datos
-                    for org in &self.organizaciones_results {
-                        ui.label(&org.org_nombre);
-                        ui.label(utils::truncate_text(&org.org_direccion, 30));
-                        ui.label(utils::format_date(&org.org_fechaconst));
-                        ui.label(org.uv_nombre.as_deref().unwrap_or("N/A"));
-                        ui.end_row();
-                    }
-                });
+    // Cerrar el diálogo de archivo sin elegir uno cierra el canal sin enviar nada
+    fn check_export_result(&mut self) {
+        if let Some(receiver) = &mut self.export_receiver {
+            let result = match receiver.try_recv() {
+                Ok(result) => Some(result),
+                Err(TryRecvError::Disconnected) => None,
+                Err(TryRecvError::Empty) => return,
+            };
+            self.exporting = false;
+            self.export_receiver = None;
+            if let Some(result) = result {
+                self.notify(result);
+            }
+        }
+    }
+
+    fn notify(&self, result: Result<String, String>) {
+        let mut toasts = self.toasts.lock().unwrap();
+        match result {
+            Ok(success_msg) => toasts.success(success_msg),
+            Err(error_msg) => toasts.error(error_msg),
+        }
+    }
+
+    // Filtros del listado actual para guardarlos; solo personas, organizaciones y actividades
+    fn current_criteria(&self) -> Option<FilterCriteria> {
+        match self.query_type {
+            QueryType::Personas => Some(FilterCriteria::Personas(self.persona_filter.clone())),
+            QueryType::Organizaciones => Some(FilterCriteria::Organizaciones(self.organizacion_filter.clone())),
+            QueryType::Actividades => Some(FilterCriteria::Actividades(self.actividad_filter.clone())),
+            _ => None,
+        }
+    }
+
+    fn save_criteria(&mut self) {
+        let Some(criteria) = self.current_criteria() else {
+            return;
+        };
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.criteria_save_receiver = Some(rx);
+
+        let file_name = format!(
+            "criterios_{}_{}.json",
+            format!("{:?}", self.query_type).to_lowercase(),
+            chrono::Local::now().format("%Y%m%d_%H%M%S"),
+        );
+        tokio::spawn(async move {
+            let Some(handle) = rfd::AsyncFileDialog::new()
+                .set_title("Guardar criterios de búsqueda")
+                .set_file_name(file_name)
+                .add_filter("JSON", &["json"])
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            let path = handle.path().to_path_buf();
+            let result = criteria.save(&path)
+                .map(|_| format!("Criterios guardados en {}", path.display()))
+                .map_err(|e| format!("Error al guardar criterios: {:#}", e));
+            let _ = tx.send(result);
         });
     }
 
-    fn show_actividades_results(&self, ui: &mut egui::Ui) {
-        ui.label(format!("Resultados: {} actividades encontradas", self.actividades_results.len()));
-        ui.add_space(10.0);
+    fn load_criteria(&mut self) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.criteria_load_receiver = Some(rx);
 
-        egui::ScrollArea::vertical().show(ui, |ui| {
-            egui::Grid::new("act_results")
-                .striped(true)
-                .spacing([10.0, 8.0])
-                .show(ui, |ui| {
-                    // Encabezados
-                    ui.strong("Nombre");
-                    ui.strong("Fecha Inicio");
-                    ui.strong("Fecha Fin");
-                    ui.strong("UV");
-                    ui.end_row();
-
-                    // Datos
-                    for actividad in &self.actividades_results {
-                        ui.label(&actividad.act_nombre);
-                        ui.label(utils::format_date(&actividad.act_fecha_ini));
-                        ui.label(utils::format_optional_date(&actividad.act_fecha_fin));
-                        ui.label(actividad.uv_nombre.as_deref().unwrap_or("N/A"));
-                        ui.end_row();
-                    }
-                });
+        tokio::spawn(async move {
+            let Some(handle) = rfd::AsyncFileDialog::new()
+                .set_title("Cargar criterios de búsqueda")
+                .add_filter("JSON", &["json"])
+                .pick_file()
+                .await
+            else {
+                return;
+            };
+            let result = FilterCriteria::load(handle.path())
+                .map_err(|e| format!("Error al cargar criterios: {:#}", e));
+            let _ = tx.send(result);
         });
     }
 
-    fn show_viajes_results(&self, ui: &mut egui::Ui) {
-        ui.label("Resultados de viajes mostrarán aquí...");
+    fn check_criteria_results(&mut self) {
+        // Como en la exportación, un canal cerrado sin resultado es un diálogo cancelado
+        if let Some(receiver) = &mut self.criteria_save_receiver {
+            match receiver.try_recv() {
+                Ok(result) => {
+                    self.criteria_save_receiver = None;
+                    self.notify(result);
+                }
+                Err(TryRecvError::Disconnected) => self.criteria_save_receiver = None,
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+        if let Some(receiver) = &mut self.criteria_load_receiver {
+            match receiver.try_recv() {
+                Ok(result) => {
+                    self.criteria_load_receiver = None;
+                    match result {
+                        Ok(criteria) => {
+                            self.apply_criteria(criteria);
+                            self.notify(Ok("Criterios cargados".to_string()));
+                        }
+                        Err(error_msg) => self.notify(Err(error_msg)),
+                    }
+                }
+                Err(TryRecvError::Disconnected) => self.criteria_load_receiver = None,
+                Err(TryRecvError::Empty) => {}
+            }
+        }
     }
 
-    fn load_catalogs(&mut self) {
-        self.catalogs_loaded = true;
-        
-        // Simular datos de catálogos para demo
-        self.generos = vec![
-            Genero { gen_id: 1, gen_genero: "Masculino".to_string() },
-            Genero { gen_id: 2, gen_genero: "Femenino".to_string() },
-            Genero { gen_id: 3, gen_genero: "Otro".to_string() },
-        ];
-
-        self.nacionalidades = vec![
-            Nacionalidad { nac_id: 1, nac_nacionalidad: "Chilena".to_string() },
-            Nacionalidad { nac_id: 2, nac_nacionalidad: "Peruana".to_string() },
-            Nacionalidad { nac_id: 3, nac_nacionalidad: "Boliviana".to_string() },
-        ];
-
-        self.macro_sectores = vec![
-            MacroSector { mac_id: 1, mac_nombre: "Centro".to_string() },
-            MacroSector { mac_id: 2, mac_nombre: "Norte".to_string() },
-            MacroSector { mac_id: 3, mac_nombre: "Sur".to_string() },
-        ];
-
-        self.unidades_vecinales = vec![
-            UnidadVecinal { uv_id: 1, uv_nombre: "Villa Los Álamos".to_string(), uv_macid: 1, mac_nombre: Some("Centro".to_string()) },
-            UnidadVecinal { uv_id: 2, uv_nombre: "Barrio Norte".to_string(), uv_macid: 2, mac_nombre: Some("Norte".to_string()) },
-            UnidadVecinal { uv_id: 3, uv_nombre: "Villa Sur".to_string(), uv_macid: 3, mac_nombre: Some("Sur".to_string()) },
-        ];
+    // Muestra el listado de los criterios, copia sus filtros (incluidos los campos de
+    // fecha escritos a mano) y repite la búsqueda
+    fn apply_criteria(&mut self, criteria: FilterCriteria) {
+        let format_date = |date: Option<chrono::NaiveDate>| date.map(|d| utils::format_date(&d)).unwrap_or_default();
+        let query_type = match criteria {
+            FilterCriteria::Personas(filter) => {
+                self.persona_filter = filter;
+                QueryType::Personas
+            }
+            FilterCriteria::Organizaciones(filter) => {
+                self.org_const_desde = format_date(filter.fecha_const_desde);
+                self.org_const_hasta = format_date(filter.fecha_const_hasta);
+                self.organizacion_filter = filter;
+                QueryType::Organizaciones
+            }
+            FilterCriteria::Actividades(filter) => {
+                self.act_fecha_desde = format_date(filter.fecha_desde);
+                self.act_fecha_hasta = format_date(filter.fecha_hasta);
+                self.actividad_filter = filter;
+                QueryType::Actividades
+            }
+        };
+        if query_type != self.query_type {
+            self.query_type = query_type.clone();
+            AppConfig::update(|config| config.query_type = Some(query_type));
+        }
+        self.execute_search();
     }
 
-    fn execute_query(&mut self) {
-        self.loading = true;
-        
+    fn check_reassign_result(&mut self) {
+        if let Some(receiver) = &mut self.reassign_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.reassigning = false;
+                self.reassign_receiver = None;
+                match result {
+                    Ok(updated) => {
+                        self.reassign_message = Some((true, format!("{} personas reasignadas", updated)));
+                        self.invalidate_cache(&QueryType::Personas);
+                        self.run_query();
+                    }
+                    Err(error_msg) => {
+                        self.reassign_message = Some((false, error_msg));
+                    }
+                }
+            }
+        }
+    }
+
+    fn reassign_selected_personas(&mut self, new_uv: i32) {
+        self.reassigning = true;
+        self.reassign_open = false;
+        self.reassign_message = None;
+
         let (tx, rx) = mpsc::unbounded_channel();
-        self.query_receiver = Some(rx);
-        
+        self.reassign_receiver = Some(rx);
+
+        let ids: Vec<i32> = self.selected_personas.iter().copied().collect();
         let db_manager = self.db_manager.clone();
-        let query_type = self.query_type.clone();
-        let persona_filter = self.persona_filter.clone();
-        let organizacion_filter = self.organizacion_filter.clone();
-        let actividad_filter = self.actividad_filter.clone();
-        
         tokio::spawn(async move {
             let db = db_manager.lock().await;
-            let result = match query_type {
-                QueryType::Personas => {
-                    match db.get_personas_mayores(&persona_filter).await {
-                        Ok(personas) => Ok(QueryResult::Personas(personas)),
-                        Err(e) => Err(format!("Error al consultar personas: {}", e)),
-                    }
+            let result = db.reassign_personas_uv(&ids, new_uv).await
+                .map_err(|e| format!("Error al reasignar UV: {}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    // Ventana para elegir la UV de destino de las personas seleccionadas
+    fn show_reassign_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.reassign_open;
+        let mut confirmed = None;
+        egui::Window::new("Reasignar UV")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!("{} personas seleccionadas", self.selected_personas.len()));
+                ui.add_space(5.0);
+                ui.horizontal(|ui| {
+                    ui.label("Nueva UV:");
+                    components::uv_combo(ui, "reassign_uv", &mut self.reassign_uv, &self.unidades_vecinales, None, "Seleccione...");
+                });
+                ui.add_space(5.0);
+                if ui.add_enabled(self.reassign_uv.is_some(), egui::Button::new("✅ Reasignar")).clicked() {
+                    confirmed = self.reassign_uv;
                 }
-                QueryType::Organizaciones => {
-                    match db.get_organizaciones(&organizacion_filter).await {
-                        Ok(organizaciones) => Ok(QueryResult::Organizaciones(organizaciones)),
-                        Err(e) => Err(format!("Error al consultar organizaciones: {}", e)),
-                    }
+            });
+        self.reassign_open = open;
+        if let Some(new_uv) = confirmed {
+            self.reassign_selected_personas(new_uv);
+        }
+    }
+
+    fn has_exportable_results(&self) -> bool {
+        match self.query_type {
+            QueryType::Personas => !self.personas_results.is_empty(),
+            QueryType::Organizaciones => !self.organizaciones_results.is_empty(),
+            QueryType::Actividades => !self.actividades_results.is_empty(),
+            _ => false,
+        }
+    }
+
+    // La tabla muestra solo una página: se exporta el resultado completo de la consulta
+    fn export_results(&mut self, format: ExportFormat) {
+        let nombre = match self.query_type {
+            QueryType::Personas => "personas",
+            QueryType::Organizaciones => "organizaciones",
+            QueryType::Actividades => "actividades",
+            _ => return,
+        };
+        self.exporting = true;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.export_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        let query_type = self.query_type.clone();
+        let filters = self.results_filters.clone();
+        let group_by_uv = self.export_group_by_uv;
+        tokio::spawn(async move {
+            let (extension, filter_name) = match format {
+                ExportFormat::Csv => ("csv", "CSV"),
+                ExportFormat::Xlsx => ("xlsx", "Excel"),
+            };
+            let Some(handle) = rfd::AsyncFileDialog::new()
+                .set_title("Exportar resultados")
+                .set_file_name(format!("{}.{}", nombre, extension))
+                .add_filter(filter_name, &[extension])
+                .save_file()
+                .await
+            else {
+                return;
+            };
+            let path = handle.path().to_path_buf();
+
+            let query_result = {
+                let db = db_manager.lock().await;
+                fetch_results(&db, &query_type, &filters, &PageRequest::all()).await
+            };
+            let result = match query_result {
+                Ok((QueryResult::Personas(personas), _)) => {
+                    let sections = if group_by_uv {
+                        export::group_personas_by_uv(personas)
+                    } else {
+                        ExportSection::ungrouped(personas)
+                    };
+                    write_export(sections, nombre, format, path).await
                 }
-                QueryType::Actividades => {
-                    match db.get_actividades(&actividad_filter).await {
-                        Ok(actividades) => Ok(QueryResult::Actividades(actividades)),
-                        Err(e) => Err(format!("Error al consultar actividades: {}", e)),
-                    }
+                Ok((QueryResult::Organizaciones(organizaciones), _)) => {
+                    write_export(ExportSection::ungrouped(organizaciones), nombre, format, path).await
                 }
-                QueryType::Viajes => {
-                    // Por ahora, devolver lista vacía
-                    Ok(QueryResult::Viajes(Vec::new()))
+                Ok((QueryResult::Actividades(actividades), _)) => {
+                    write_export(ExportSection::ungrouped(actividades), nombre, format, path).await
                 }
+                Ok(_) => return,
+                Err(error_msg) => Err(error_msg),
             };
-            
             let _ = tx.send(result);
         });
     }
 
-    // Función para cargar todos los datos inicialmente sin filtros
-    fn execute_initial_query(&mut self) {
+    // Muestra el tipo de consulta del registro y abre su detalle (usado por la búsqueda global)
+    pub fn open_record(&mut self, record: SelectedRecord) {
+        let query_type = match &record {
+            SelectedRecord::Persona(_) => QueryType::Personas,
+            SelectedRecord::Organizacion(_) => QueryType::Organizaciones,
+            SelectedRecord::Actividad(_) => QueryType::Actividades,
+        };
+        if query_type != self.query_type {
+            self.query_type = query_type.clone();
+            AppConfig::update(|config| config.query_type = Some(query_type));
+            self.execute_auto_query();
+        }
+        self.detail_panel.open(record);
+    }
+
+    // Lista las personas de una unidad vecinal (usado por el detalle de macrosectores
+    // del dashboard); reemplaza los filtros de personas salvo el orden
+    pub fn show_personas_in_uv(&mut self, uv_id: i32) {
+        self.persona_filter = PersonaFilter {
+            orden: self.persona_filter.orden,
+            unidad_vecinal_id: Some(uv_id),
+            ..PersonaFilter::default()
+        };
+        if self.query_type != QueryType::Personas {
+            self.query_type = QueryType::Personas;
+            AppConfig::update(|config| config.query_type = Some(QueryType::Personas));
+        }
+        self.execute_search();
+    }
+
+    // Pide foco para el primer campo de filtro en el próximo frame
+    pub fn focus_first_filter(&mut self) {
+        // Beneficios y talleres no tienen campos de filtro
+        self.focus_filter = !matches!(self.query_type, QueryType::Beneficios | QueryType::Talleres);
+    }
+
+    // Campo de texto del filtro de personas con su desplegable de búsquedas anteriores.
+    // Elegir una entrada del historial lanza la búsqueda de inmediato.
+    fn history_filter_field(&mut self, ui: &mut egui::Ui, field: HistoryField) -> egui::Response {
+        let history = self.search_history.get(field.key()).cloned().unwrap_or_default();
+        let value = match field {
+            HistoryField::Nombre => &mut self.persona_filter.nombre,
+            HistoryField::Apellido => &mut self.persona_filter.apellido,
+            HistoryField::Rut => &mut self.persona_filter.rut,
+        };
+        let (response, action) = components::history_text_edit(ui, value, &history);
+
+        match action {
+            HistoryAction::Picked => self.execute_search(),
+            HistoryAction::Clear => self.clear_search_history(field),
+            HistoryAction::None => {
+                if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    self.execute_search();
+                } else if response.changed() {
+                    self.on_filter_text_changed();
+                }
+            }
+        }
+        response
+    }
+
+    // Búsqueda pedida por el usuario (botón, Enter o historial). A diferencia de la
+    // búsqueda en vivo, sus valores quedan en el historial.
+    fn execute_search(&mut self) {
+        if self.query_type == QueryType::Personas {
+            self.record_search_history();
+        }
+        // Buscar siempre consulta la base, sin reutilizar la caché
+        self.invalidate_cache(&self.query_type.clone());
+        self.last_filter_edit = None;
+        self.execute_query();
+    }
+
+    // Guarda en el historial los valores usados en una búsqueda de personas
+    fn record_search_history(&mut self) {
+        let mut changed = false;
+        for field in HistoryField::ALL {
+            let value = match field {
+                HistoryField::Nombre => &self.persona_filter.nombre,
+                HistoryField::Apellido => &self.persona_filter.apellido,
+                HistoryField::Rut => &self.persona_filter.rut,
+            }.trim();
+            if value.is_empty() {
+                continue;
+            }
+            let history = self.search_history.entry(field.key().to_string()).or_default();
+            if history.first().map(String::as_str) == Some(value) {
+                continue;
+            }
+            history.retain(|entry| entry != value);
+            history.insert(0, value.to_string());
+            history.truncate(MAX_SEARCH_HISTORY);
+            changed = true;
+        }
+
+        if changed {
+            let search_history = self.search_history.clone();
+            AppConfig::update(|config| config.search_history = search_history);
+        }
+    }
+
+    // Borra solo el historial del campo cuyo desplegable se usó
+    fn clear_search_history(&mut self, field: HistoryField) {
+        self.search_history.remove(field.key());
+        AppConfig::update(|config| {
+            config.search_history.remove(field.key());
+        });
+    }
+
+    fn apply_filter_focus(&mut self, response: &egui::Response) {
+        if self.focus_filter {
+            response.request_focus();
+            self.focus_filter = false;
+        }
+    }
+
+    fn show_result_summary(&self, ui: &mut egui::Ui, count: usize) {
+        let resultados = if (count as i64) < self.total_count {
+            let desde = self.page_request().offset + 1;
+            format!(
+                "{}-{} de {} resultados",
+                utils::format_number(desde),
+                utils::format_number(desde + count as i64 - 1),
+                utils::format_number(self.total_count),
+            )
+        } else {
+            format!("{} resultados", utils::format_number(count as i64))
+        };
+        match self.last_query_duration {
+            Some(duration) => ui.label(format!("{} en {} ms", resultados, duration.as_millis())),
+            None if self.from_cache => ui.label(format!("{} (en caché)", resultados))
+                .on_hover_text("Resultado reciente reutilizado; use Buscar para consultar de nuevo"),
+            None => ui.label(resultados),
+        };
+    }
+
+    // Navegación entre páginas, tamaño de página y "Mostrar todos"
+    fn show_pagination(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let page_count = ((self.total_count + self.page_size - 1) / self.page_size).max(1);
+            if !self.show_all {
+                if ui.add_enabled(self.page > 0, egui::Button::new("◀ Anterior")).clicked() {
+                    self.page -= 1;
+                    self.run_query();
+                }
+                ui.label(format!("Página {} de {}", self.page + 1, page_count));
+                if ui.add_enabled(self.page + 1 < page_count, egui::Button::new("Siguiente ▶")).clicked() {
+                    self.page += 1;
+                    self.run_query();
+                }
+                ui.separator();
+            }
+
+            ui.label("Filas por página:");
+            let previous_page_size = self.page_size;
+            egui::ComboBox::from_id_source("page_size")
+                .selected_text(self.page_size.to_string())
+                .show_ui(ui, |ui| {
+                    for size in PAGE_SIZE_OPTIONS {
+                        ui.selectable_value(&mut self.page_size, size, size.to_string());
+                    }
+                });
+            if previous_page_size != self.page_size {
+                let page_size = self.page_size;
+                AppConfig::update(|config| config.page_size = Some(page_size));
+                if !self.show_all {
+                    self.execute_query();
+                }
+            }
+
+            let mut show_all = self.show_all;
+            if ui.checkbox(&mut show_all, "Mostrar todos")
+                .on_hover_text(format!("Sobre {} filas se pide confirmación antes de cargarlas", utils::format_number(self.row_limit)))
+                .changed()
+            {
+                self.set_show_all(show_all);
+            }
+        });
+
+        ui.add_space(5.0);
+    }
+
+    fn set_show_all(&mut self, show_all: bool) {
+        self.show_all = show_all;
+        self.execute_query();
+    }
+
+    fn show_personas_results(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            self.show_result_summary(ui, self.personas_results.len());
+            ui.separator();
+            let can_reassign = !self.selected_personas.is_empty() && !self.reassigning;
+            let label = format!("🏘 Reasignar UV ({})", self.selected_personas.len());
+            if ui.add_enabled(can_reassign, egui::Button::new(label)).clicked() {
+                self.reassign_open = true;
+                self.reassign_uv = None;
+            }
+            if self.reassigning {
+                ui.add(egui::widgets::Spinner::new().size(16.0));
+            }
+            ui.separator();
+            self.show_columns_menu(ui, "personas_results", &PERSONA_COLUMNS);
+        });
+        if let Some((success, ref text)) = self.reassign_message {
+            let color = if success { egui::Color32::GREEN } else { egui::Color32::RED };
+            ui.colored_label(color, text);
+        }
+        if self.reassign_open {
+            self.show_reassign_window(ui.ctx());
+        }
+        ui.add_space(10.0);
+
+        let mut seleccionado = None;
+        let selected_id = self.detail_panel.selected_persona_id();
+
+        // Clic en el RUT para ver el detalle
+        let columns = self.visible_columns("personas_results", &PERSONA_COLUMNS);
+        let headers: Vec<&str> = columns.iter().map(|&column| PERSONA_COLUMNS[column]).collect();
+        let row_ids: Vec<i32> = self.personas_results.iter().map(|persona| persona.per_id).collect();
+        components::selectable_results_table(ui, "personas_results", &headers, &row_ids, &mut self.selected_personas, |index, row| {
+            let persona = &self.personas_results[index];
+            for &column in &columns {
+                row.col(|ui| match column {
+                    0 => {
+                        if ui.selectable_label(selected_id == Some(persona.per_id), &persona.per_rut).clicked() {
+                            seleccionado = Some(SelectedRecord::Persona(persona.clone()));
+                        }
+                    }
+                    1 => {
+                        ui.label(&persona.per_prinombre);
+                    }
+                    2 => {
+                        ui.label(format!("{} {}", 
+                            persona.per_priapellido, 
+                            persona.per_segapellido.as_deref().unwrap_or("")
+                        ));
+                    }
+                    3 => {
+                        ui.label(utils::calculate_age(&persona.per_fechadenac).to_string());
+                    }
+                    4 => {
+                        ui.label(persona.gen_genero.as_deref().unwrap_or("N/A"));
+                    }
+                    5 => {
+                        ui.label(persona.nac_nacionalidad.as_deref().unwrap_or("N/A"));
+                    }
+                    6 => components::email_cell(ui, persona.per_email.as_deref()),
+                    _ => {
+                        ui.label(persona.uv_nombre.as_deref().unwrap_or("N/A"));
+                    }
+                });
+            }
+            components::copy_context_menu(row, || vec![
+                ("Copiar RUT", persona.per_rut.clone()),
+                ("Copiar nombre completo", persona.nombre_completo()),
+                ("Copiar fila (TSV)", export::tsv_row(persona)),
+            ]);
+        });
+
+        if let Some(record) = seleccionado {
+            self.detail_panel.open(record);
+        }
+    }
+
+    // Índices de `headers` que se muestran en la tabla `table`, en su orden original
+    fn visible_columns(&self, table: &str, headers: &[&str]) -> Vec<usize> {
+        let hidden = self.hidden_columns.get(table);
+        (0..headers.len())
+            .filter(|&column| column == 0 || !hidden.is_some_and(|hidden| hidden.iter().any(|h| h == headers[column])))
+            .collect()
+    }
+
+    fn show_columns_menu(&mut self, ui: &mut egui::Ui, table: &str, headers: &[&str]) {
+        let hidden = self.hidden_columns.entry(table.to_string()).or_default();
+        if components::columns_menu(ui, headers, hidden) {
+            let hidden_columns = self.hidden_columns.clone();
+            AppConfig::update(|config| config.hidden_columns = hidden_columns);
+        }
+    }
+
+    fn show_organizaciones_results(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            self.show_result_summary(ui, self.organizaciones_results.len());
+            ui.separator();
+            self.show_columns_menu(ui, "org_results", &ORGANIZACION_COLUMNS);
+        });
+        ui.add_space(10.0);
+
+        let mut seleccionado = None;
+        let selected_id = self.detail_panel.selected_organizacion_id();
+
+        // Clic en el nombre para ver el detalle
+        let columns = self.visible_columns("org_results", &ORGANIZACION_COLUMNS);
+        let headers: Vec<&str> = columns.iter().map(|&column| ORGANIZACION_COLUMNS[column]).collect();
+        components::results_table(ui, "org_results", &headers, self.organizaciones_results.len(), |index, row| {
+            let org = &self.organizaciones_results[index];
+            for &column in &columns {
+                row.col(|ui| match column {
+                    0 => {
+                        if ui.selectable_label(selected_id == Some(org.org_id), &org.org_nombre).clicked() {
+                            seleccionado = Some(SelectedRecord::Organizacion(org.clone()));
+                        }
+                    }
+                    1 => {
+                        ui.label(utils::truncate_text(&org.org_direccion, 30));
+                    }
+                    2 => {
+                        ui.label(utils::format_date(&org.org_fechaconst));
+                    }
+                    3 => components::email_cell(ui, org.org_email.as_deref()),
+                    _ => {
+                        ui.label(org.uv_nombre.as_deref().unwrap_or("N/A"));
+                    }
+                });
+            }
+            components::copy_context_menu(row, || vec![
+                ("Copiar nombre", org.org_nombre.clone()),
+                ("Copiar fila (TSV)", export::tsv_row(org)),
+            ]);
+        });
+
+        if let Some(record) = seleccionado {
+            self.detail_panel.open(record);
+        }
+    }
+
+    fn show_actividades_results(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            self.show_result_summary(ui, self.actividades_results.len());
+            ui.separator();
+            ui.label("Vista:");
+            ui.selectable_value(&mut self.actividades_calendar, false, "lista");
+            ui.selectable_value(&mut self.actividades_calendar, true, "calendario");
+            if !self.actividades_calendar {
+                ui.separator();
+                self.show_columns_menu(ui, "act_results", &ACTIVIDAD_COLUMNS);
+            }
+        });
+        ui.add_space(10.0);
+
+        let selected_id = self.detail_panel.selected_actividad_id();
+        if self.actividades_calendar {
+            if let Some(record) = self.calendar.show(ui, &self.actividades_results, selected_id) {
+                self.detail_panel.open(record);
+            }
+            return;
+        }
+
+        let mut seleccionado = None;
+        let hoy = chrono::Local::now().date_naive();
+
+        // Clic en el nombre para ver el detalle
+        let columns = self.visible_columns("act_results", &ACTIVIDAD_COLUMNS);
+        let headers: Vec<&str> = columns.iter().map(|&column| ACTIVIDAD_COLUMNS[column]).collect();
+        components::results_table(ui, "act_results", &headers, self.actividades_results.len(), |index, row| {
+            let actividad = &self.actividades_results[index];
+            for &column in &columns {
+                row.col(|ui| match column {
+                    0 => {
+                        if ui.selectable_label(selected_id == Some(actividad.act_id), &actividad.act_nombre).clicked() {
+                            seleccionado = Some(SelectedRecord::Actividad(actividad.clone()));
+                        }
+                    }
+                    1 => {
+                        ui.label(utils::format_date(&actividad.act_fecha_ini));
+                    }
+                    2 => {
+                        ui.label(utils::format_optional_date(&actividad.act_fecha_fin));
+                    }
+                    3 => {
+                        let estado = actividad.estado(hoy);
+                        components::pill(ui, estado.label(), estado_color(estado));
+                    }
+                    4 => {
+                        ui.label(actividad.uv_nombre.as_deref().unwrap_or("N/A"));
+                    }
+                    _ => {
+                        ui.label(actividad.tal_nombre.as_deref().unwrap_or("-"));
+                    }
+                });
+            }
+            components::copy_context_menu(row, || vec![
+                ("Copiar nombre", actividad.act_nombre.clone()),
+                ("Copiar fila (TSV)", export::tsv_row(actividad)),
+            ]);
+        });
+
+        if let Some(record) = seleccionado {
+            self.detail_panel.open(record);
+        }
+    }
+
+    fn show_viajes_results(&self, ui: &mut egui::Ui) {
+        self.show_result_summary(ui, self.viajes_results.len());
+        ui.add_space(10.0);
+
+        let headers = ["Nombre", "Destino", "Salida", "Regreso", "UV"];
+        components::results_table(ui, "via_results", &headers, self.viajes_results.len(), |index, row| {
+            let viaje = &self.viajes_results[index];
+            row.col(|ui| {
+                ui.label(&viaje.via_nombre);
+            });
+            row.col(|ui| {
+                ui.label(utils::truncate_text(&viaje.via_destino, 30));
+            });
+            row.col(|ui| {
+                ui.label(utils::format_date(&viaje.via_fecha_salida));
+            });
+            row.col(|ui| {
+                ui.label(utils::format_optional_date(&viaje.via_fecha_regreso));
+            });
+            row.col(|ui| {
+                ui.label(viaje.uv_nombre.as_deref().unwrap_or("N/A"));
+            });
+        });
+    }
+
+    fn show_beneficios_results(&self, ui: &mut egui::Ui) {
+        self.show_result_summary(ui, self.beneficios_results.len());
+        ui.add_space(10.0);
+
+        let headers = ["Código", "Descripción"];
+        components::results_table(ui, "ben_results", &headers, self.beneficios_results.len(), |index, row| {
+            let beneficio = &self.beneficios_results[index];
+            row.col(|ui| {
+                ui.label(&beneficio.ben_codigo);
+            });
+            row.col(|ui| {
+                ui.label(utils::truncate_text(&beneficio.ben_descripcion, 60));
+            });
+        });
+    }
+
+    fn show_centros_results(&self, ui: &mut egui::Ui) {
+        self.show_result_summary(ui, self.centros_results.len());
+        ui.add_space(10.0);
+
+        let headers = ["Nombre", "Dirección", "UV"];
+        components::results_table(ui, "cen_results", &headers, self.centros_results.len(), |index, row| {
+            let centro = &self.centros_results[index];
+            row.col(|ui| {
+                ui.label(&centro.cen_nombre);
+            });
+            row.col(|ui| {
+                ui.label(utils::truncate_text(&centro.cen_direccion, 30));
+            });
+            row.col(|ui| {
+                ui.label(centro.uv_nombre.as_deref().unwrap_or("N/A"));
+            });
+        });
+    }
+
+    fn show_talleres_results(&self, ui: &mut egui::Ui) {
+        self.show_result_summary(ui, self.talleres_results.len());
+        ui.add_space(10.0);
+
+        let headers = ["Id", "Nombre"];
+        components::results_table(ui, "tal_results", &headers, self.talleres_results.len(), |index, row| {
+            let taller = &self.talleres_results[index];
+            row.col(|ui| {
+                ui.label(taller.tal_id.to_string());
+            });
+            row.col(|ui| {
+                ui.label(&taller.tal_nombre);
+            });
+        });
+    }
+
+    // Nueva búsqueda: vuelve a la primera página
+    fn execute_query(&mut self) {
+        self.page = 0;
+        self.run_query();
+    }
+
+    fn page_request(&self) -> PageRequest {
+        if self.show_all {
+            PageRequest::all()
+        } else {
+            PageRequest { limit: Some(self.page_size), offset: self.page * self.page_size }
+        }
+    }
+
+    // Fechas de filtro con texto que no se pudo interpretar. Mientras haya alguna no se
+    // consulta: se buscaría sin ese límite y el resultado no correspondería a lo escrito
+    fn has_invalid_filter_dates(&self) -> bool {
+        let dates: &[&String] = match self.query_type {
+            QueryType::Organizaciones => &[&self.org_const_desde, &self.org_const_hasta],
+            QueryType::Actividades => &[&self.act_fecha_desde, &self.act_fecha_hasta],
+            QueryType::Viajes => &[&self.viaje_salida_desde, &self.viaje_salida_hasta],
+            _ => &[],
+        };
+        dates.iter().any(|text| components::filter_date_error(text).is_some())
+    }
+
+    // Consulta la página actual con los filtros vigentes
+    fn run_query(&mut self) {
+        if self.has_invalid_filter_dates() {
+            return;
+        }
+        self.results_filters = self.current_filters();
+        if self.use_cached(self.query_key(true)) || self.start_row_count(true) {
+            return;
+        }
         self.loading = true;
+        self.query_error = None;
+        self.query_started = Some(Instant::now());
         
+        // Reemplazar el receptor descarta el resultado de cualquier consulta anterior aún en curso
         let (tx, rx) = mpsc::unbounded_channel();
         self.query_receiver = Some(rx);
+        let cancel_slot = self.new_cancel_slot();
         
         let db_manager = self.db_manager.clone();
+        let query_type = self.query_type.clone();
+        let filters = self.current_filters();
+        let page = self.page_request();
         
         tokio::spawn(async move {
             let db = db_manager.lock().await;
-            
-            // Verificar si hay conexión antes de ejecutar la consulta
-            match db.test_connection().await {
-                Ok(false) | Err(_) => {
-                    let _ = tx.send(Err("No hay conexión a la base de datos".to_string()));
-                    return;
-                }
-                Ok(true) => {}
-            }
-            
-            // Cargar todos los datos de personas sin filtros
-            let empty_persona_filter = PersonaFilter::default();
-            let result = match db.get_personas_mayores(&empty_persona_filter).await {
-                Ok(personas) => Ok(QueryResult::Personas(personas)),
-                Err(e) => Err(format!("Error al cargar datos iniciales: {}", e)),
-            };
+            *cancel_slot.lock().unwrap() = db.cancel_token();
+            let result = fetch_results(&db, &query_type, &filters, &page).await;
             
             let _ = tx.send(result);
         });
     }
 
+    // Función para cargar todos los datos inicialmente sin filtros
     // Función para ejecutar consulta automática cuando cambia el tipo
     fn execute_auto_query(&mut self) {
+        self.page = 0;
+        self.results_filters = QueryFilters::default();
+        if self.use_cached(self.query_key(false)) || self.start_row_count(false) {
+            return;
+        }
         self.loading = true;
+        self.query_error = None;
+        self.query_started = Some(Instant::now());
         
         let (tx, rx) = mpsc::unbounded_channel();
         self.query_receiver = Some(rx);
+        let cancel_slot = self.new_cancel_slot();
         
         let db_manager = self.db_manager.clone();
         let query_type = self.query_type.clone();
+        let page = self.page_request();
         
         tokio::spawn(async move {
             let db = db_manager.lock().await;
+            *cancel_slot.lock().unwrap() = db.cancel_token();
             
             // Verificar si hay conexión antes de ejecutar la consulta
             match db.test_connection().await {
@@ -570,40 +1926,55 @@ impl QueriesView {
                 Ok(true) => {}
             }
             
-            let result = match query_type {
-                QueryType::Personas => {
-                    let empty_filter = PersonaFilter::default();
-                    match db.get_personas_mayores(&empty_filter).await {
-                        Ok(personas) => Ok(QueryResult::Personas(personas)),
-                        Err(e) => Err(format!("Error al consultar personas: {}", e)),
-                    }
-                }
-                QueryType::Organizaciones => {
-                    let empty_filter = OrganizacionFilter::default();
-                    match db.get_organizaciones(&empty_filter).await {
-                        Ok(organizaciones) => Ok(QueryResult::Organizaciones(organizaciones)),
-                        Err(e) => Err(format!("Error al consultar organizaciones: {}", e)),
-                    }
-                }
-                QueryType::Actividades => {
-                    let empty_filter = ActividadFilter::default();
-                    match db.get_actividades(&empty_filter).await {
-                        Ok(actividades) => Ok(QueryResult::Actividades(actividades)),
-                        Err(e) => Err(format!("Error al consultar actividades: {}", e)),
-                    }
-                }
-                QueryType::Viajes => {
-                    Ok(QueryResult::Viajes(Vec::new()))
-                }
-            };
+            let result = fetch_results(&db, &query_type, &QueryFilters::default(), &page).await;
             
             let _ = tx.send(result);
         });
     }
 
+    fn new_cancel_slot(&mut self) -> CancelSlot {
+        let slot = CancelSlot::default();
+        self.query_cancel = Some(slot.clone());
+        slot
+    }
+
+    // Abandona la consulta en curso: su resultado se descarta y, si ya se estaba
+    // ejecutando, se aborta en el servidor. Los resultados anteriores se conservan.
+    fn cancel_query(&mut self) {
+        self.query_receiver = None;
+        self.row_count_receiver = None;
+        self.pending_query = None;
+        self.loading = false;
+        self.query_started = None;
+        let token = self.query_cancel.take().and_then(|slot| slot.lock().unwrap().take());
+        if let Some(token) = token {
+            tokio::spawn(async move {
+                if let Err(e) = DatabaseManager::cancel_query(token).await {
+                    log::warn!("No se pudo cancelar la consulta: {:#}", e);
+                }
+            });
+        }
+    }
+
+    // Registra la edición de un filtro de texto para la búsqueda en vivo
+    fn on_filter_text_changed(&mut self) {
+        if self.live_search {
+            self.last_filter_edit = Some(Instant::now());
+        }
+    }
+
     fn clear_filters(&mut self) {
-        self.persona_filter = PersonaFilter::default();
+        // El orden elegido no es un filtro y se conserva
+        self.persona_filter = PersonaFilter { orden: self.persona_filter.orden, ..PersonaFilter::default() };
         self.organizacion_filter = OrganizacionFilter::default();
         self.actividad_filter = ActividadFilter::default();
+        self.centro_filter = CentroFilter::default();
+        self.viaje_filter = ViajeFilter::default();
+        self.viaje_salida_desde.clear();
+        self.viaje_salida_hasta.clear();
+        self.org_const_desde.clear();
+        self.org_const_hasta.clear();
+        self.act_fecha_desde.clear();
+        self.act_fecha_hasta.clear();
     }
 }
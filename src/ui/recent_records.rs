@@ -0,0 +1,132 @@
+use eframe::egui;
+use std::sync::Arc;
+use tokio::sync::{Mutex, mpsc};
+use crate::database::DatabaseManager;
+use crate::models::RecentRecord;
+use crate::ui::components;
+
+// Cantidades que se pueden elegir para el listado
+const LIMIT_OPTIONS: [i64; 3] = [10, 25, 50];
+
+// Últimos registros creados, para revisar lo ingresado al terminar una sesión de digitación
+pub struct RecentRecordsPanel {
+    db_manager: Arc<Mutex<DatabaseManager>>,
+    records: Vec<RecentRecord>,
+    limit: i64,
+    loaded: bool,
+    loading: bool,
+    error: Option<String>,
+    receiver: Option<mpsc::UnboundedReceiver<Result<Vec<RecentRecord>, String>>>,
+}
+
+impl RecentRecordsPanel {
+    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>) -> Self {
+        Self {
+            db_manager,
+            records: Vec::new(),
+            limit: LIMIT_OPTIONS[0],
+            loaded: false,
+            loading: false,
+            error: None,
+            receiver: None,
+        }
+    }
+
+    pub fn refresh(&mut self) {
+        self.loading = true;
+        self.error = None;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.receiver = Some(rx);
+
+        let limit = self.limit;
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = db.recent_records(limit).await
+                .map_err(|e| format!("Error al cargar registros recientes: {}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    // Tras una inserción o un deshacer; si el panel nunca se abrió no hace falta consultar
+    pub fn invalidate(&mut self) {
+        if self.loaded {
+            self.refresh();
+        }
+    }
+
+    fn check_result(&mut self) {
+        if let Some(receiver) = &mut self.receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.loading = false;
+                self.receiver = None;
+                match result {
+                    Ok(records) => self.records = records,
+                    Err(error_msg) => self.error = Some(error_msg),
+                }
+            }
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        self.check_result();
+        if self.loading {
+            ui.ctx().request_repaint_after(std::time::Duration::from_millis(250));
+        }
+
+        egui::CollapsingHeader::new("🕑 Registros recientes")
+            .id_source("recent_records")
+            .show(ui, |ui| {
+                // Se consulta la primera vez que se abre el panel
+                if !self.loaded {
+                    self.loaded = true;
+                    self.refresh();
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label("Mostrar:");
+                    let before = self.limit;
+                    egui::ComboBox::from_id_source("recent_records_limit")
+                        .selected_text(self.limit.to_string())
+                        .show_ui(ui, |ui| {
+                            for limit in LIMIT_OPTIONS {
+                                ui.selectable_value(&mut self.limit, limit, limit.to_string());
+                            }
+                        });
+                    if ui.add_enabled(!self.loading, egui::Button::new("🔄 Actualizar")).clicked() || self.limit != before {
+                        self.refresh();
+                    }
+                    if self.loading {
+                        ui.add(egui::widgets::Spinner::new().size(14.0));
+                    }
+                });
+
+                if let Some(error) = &self.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                } else if self.records.is_empty() && !self.loading {
+                    ui.label("No hay registros.");
+                } else {
+                    ui.add_space(5.0);
+                    let records = &self.records;
+                    components::results_table(ui, "recent_records_table", &["Tipo", "ID", "Nombre", "Creado"], records.len(), |index, row| {
+                        let record = &records[index];
+                        row.col(|ui| {
+                            components::pill(ui, record.kind.label(), egui::Color32::from_gray(70));
+                        });
+                        row.col(|ui| {
+                            ui.label(record.id.to_string());
+                        });
+                        row.col(|ui| {
+                            ui.label(&record.nombre);
+                        });
+                        row.col(|ui| {
+                            ui.label(record.creado
+                                .map(|creado| creado.format("%d/%m/%Y %H:%M").to_string())
+                                .unwrap_or_else(|| "-".to_string()));
+                        });
+                    });
+                }
+            });
+    }
+}
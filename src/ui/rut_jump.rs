@@ -0,0 +1,125 @@
+use eframe::egui;
+use std::sync::Arc;
+use tokio::sync::{Mutex, mpsc};
+use crate::database::DatabaseManager;
+use crate::models::PersonaMayor;
+use crate::utils;
+
+// Salto directo a una persona por su RUT (Ctrl+G), sin pasar por los filtros de Consultas
+pub struct RutJumpDialog {
+    db_manager: Arc<Mutex<DatabaseManager>>,
+    open: bool,
+    rut: String,
+    request_focus: bool,
+    searching: bool,
+    error: Option<String>,
+    receiver: Option<mpsc::UnboundedReceiver<Result<PersonaMayor, String>>>,
+}
+
+impl RutJumpDialog {
+    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>) -> Self {
+        Self {
+            db_manager,
+            open: false,
+            rut: String::new(),
+            request_focus: false,
+            searching: false,
+            error: None,
+            receiver: None,
+        }
+    }
+
+    pub fn open(&mut self) {
+        self.open = true;
+        self.request_focus = true;
+        self.error = None;
+    }
+
+    fn search(&mut self) {
+        let rut = utils::normalize_rut(&self.rut);
+        if rut.is_empty() {
+            return;
+        }
+        self.searching = true;
+        self.error = None;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = match db.get_persona_by_rut(&rut).await {
+                Ok(Some(persona)) => Ok(persona),
+                Ok(None) => Err(format!("No se encontró persona con RUT {}", rut)),
+                Err(e) => Err(format!("Error al buscar la persona: {}", e)),
+            };
+            let _ = tx.send(result);
+        });
+    }
+
+    // Devuelve la persona encontrada; el diálogo se cierra al encontrarla
+    pub fn show(&mut self, ctx: &egui::Context) -> Option<PersonaMayor> {
+        let mut found = None;
+        if let Some(receiver) = &mut self.receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.searching = false;
+                self.receiver = None;
+                match result {
+                    Ok(persona) => {
+                        self.open = false;
+                        self.rut.clear();
+                        found = Some(persona);
+                    }
+                    Err(error_msg) => {
+                        self.error = Some(error_msg);
+                        self.request_focus = true;
+                    }
+                }
+            }
+        }
+
+        if !self.open {
+            return found;
+        }
+        if self.searching {
+            ctx.request_repaint();
+        }
+
+        let mut open = true;
+        egui::Window::new("Ir a persona por RUT")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, egui::vec2(0.0, 80.0))
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let response = ui.add(
+                        egui::TextEdit::singleline(&mut self.rut)
+                            .hint_text("12.345.678-9")
+                            .desired_width(180.0)
+                    );
+                    if self.request_focus {
+                        response.request_focus();
+                        self.request_focus = false;
+                    }
+                    let enter = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    let can_search = !self.searching && !self.rut.trim().is_empty();
+                    if (ui.add_enabled(can_search, egui::Button::new("Ir")).clicked() || enter) && can_search {
+                        self.search();
+                    }
+                    if self.searching {
+                        ui.add(egui::widgets::Spinner::new().size(16.0));
+                    }
+                });
+                if let Some(error) = &self.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+            });
+
+        if !open || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            self.open = false;
+        }
+        found
+    }
+}
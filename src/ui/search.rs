@@ -0,0 +1,177 @@
+use eframe::egui;
+use std::sync::Arc;
+use tokio::sync::{Mutex, mpsc};
+use crate::database::DatabaseManager;
+use crate::models::{GlobalSearchResults, GLOBAL_SEARCH_LIMIT};
+use crate::utils;
+use crate::ui::detail::SelectedRecord;
+
+// Cantidad de resultados de una categoría; al llegar al tope puede haber más ("20+")
+fn count_label(count: usize) -> String {
+    if count as i64 >= GLOBAL_SEARCH_LIMIT {
+        format!("{}+", GLOBAL_SEARCH_LIMIT)
+    } else {
+        count.to_string()
+    }
+}
+
+// Búsqueda de un término en personas, organizaciones y actividades a la vez
+pub struct SearchView {
+    db_manager: Arc<Mutex<DatabaseManager>>,
+    term: String,
+    results: Option<GlobalSearchResults>,
+
+    // Estado
+    loading: bool,
+    error_message: Option<String>,
+
+    // Canal asíncrono
+    search_receiver: Option<mpsc::UnboundedReceiver<Result<GlobalSearchResults, String>>>,
+}
+
+impl SearchView {
+    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>) -> Self {
+        Self {
+            db_manager,
+            term: String::new(),
+            results: None,
+            loading: false,
+            error_message: None,
+            search_receiver: None,
+        }
+    }
+
+    fn check_search_result(&mut self) {
+        if let Some(receiver) = &mut self.search_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.loading = false;
+                self.search_receiver = None;
+                match result {
+                    Ok(results) => {
+                        self.results = Some(results);
+                    }
+                    Err(error_msg) => {
+                        self.results = None;
+                        self.error_message = Some(error_msg);
+                    }
+                }
+            }
+        }
+    }
+
+    // Devuelve el registro elegido con "Ver" para abrir su detalle en Consultas
+    pub fn show(&mut self, ui: &mut egui::Ui) -> Option<SelectedRecord> {
+        self.check_search_result();
+
+        ui.heading("🔎 Búsqueda Global");
+        ui.label("Busca por nombre, apellido o RUT en personas, organizaciones y actividades.");
+        ui.add_space(10.0);
+
+        ui.horizontal(|ui| {
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.term)
+                    .hint_text("Término de búsqueda")
+                    .desired_width(300.0)
+            );
+            let enter = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+            let can_search = !self.loading && !self.term.trim().is_empty();
+            if (ui.add_enabled(can_search, egui::Button::new("🔍 Buscar")).clicked() || enter) && can_search {
+                self.execute();
+            }
+            if self.loading {
+                ui.add(egui::widgets::Spinner::new().size(16.0));
+            }
+        });
+
+        if let Some(error) = &self.error_message {
+            ui.add_space(5.0);
+            ui.colored_label(egui::Color32::RED, error);
+        }
+
+        ui.add_space(10.0);
+        ui.separator();
+
+        let results = self.results.as_ref()?;
+
+        ui.horizontal(|ui| {
+            ui.label(format!("👤 Personas: {}", count_label(results.personas.len())));
+            ui.separator();
+            ui.label(format!("🏢 Organizaciones: {}", count_label(results.organizaciones.len())));
+            ui.separator();
+            ui.label(format!("📅 Actividades: {}", count_label(results.actividades.len())));
+        });
+        ui.add_space(10.0);
+
+        if results.personas.is_empty() && results.organizaciones.is_empty() && results.actividades.is_empty() {
+            ui.label("No se encontraron resultados.");
+            return None;
+        }
+
+        let mut seleccionado = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("global_search_results")
+                .striped(true)
+                .spacing([10.0, 8.0])
+                .show(ui, |ui| {
+                    ui.strong("Tipo");
+                    ui.strong("Nombre");
+                    ui.strong("Detalle");
+                    ui.strong("Unidad Vecinal");
+                    ui.strong("");
+                    ui.end_row();
+
+                    for persona in &results.personas {
+                        ui.label("👤 Persona");
+                        ui.label(format!("{} {}", persona.per_prinombre, persona.per_priapellido));
+                        ui.label(&persona.per_rut);
+                        ui.label(persona.uv_nombre.as_deref().unwrap_or("-"));
+                        if ui.small_button("Ver").clicked() {
+                            seleccionado = Some(SelectedRecord::Persona(persona.clone()));
+                        }
+                        ui.end_row();
+                    }
+
+                    for org in &results.organizaciones {
+                        ui.label("🏢 Organización");
+                        ui.label(&org.org_nombre);
+                        ui.label(&org.org_direccion);
+                        ui.label(org.uv_nombre.as_deref().unwrap_or("-"));
+                        if ui.small_button("Ver").clicked() {
+                            seleccionado = Some(SelectedRecord::Organizacion(org.clone()));
+                        }
+                        ui.end_row();
+                    }
+
+                    for actividad in &results.actividades {
+                        ui.label("📅 Actividad");
+                        ui.label(&actividad.act_nombre);
+                        ui.label(utils::format_date(&actividad.act_fecha_ini));
+                        ui.label(actividad.uv_nombre.as_deref().unwrap_or("-"));
+                        if ui.small_button("Ver").clicked() {
+                            seleccionado = Some(SelectedRecord::Actividad(actividad.clone()));
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+
+        seleccionado
+    }
+
+    fn execute(&mut self) {
+        self.loading = true;
+        self.error_message = None;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.search_receiver = Some(rx);
+
+        let term = self.term.clone();
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = db.global_search(&term).await
+                .map_err(|e| format!("Error en la búsqueda: {}", e));
+            let _ = tx.send(result);
+        });
+    }
+}
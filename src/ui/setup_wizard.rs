@@ -0,0 +1,351 @@
+use eframe::egui;
+use std::sync::Arc;
+use tokio::sync::{Mutex, mpsc};
+use crate::database::DatabaseManager;
+use crate::models::DatabaseConfig;
+use crate::ui::components;
+use crate::ui::theme::AppleMusicStyle;
+
+// Pasos del asistente de primera ejecución, en orden
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum WizardStep {
+    Conexion,
+    Prueba,
+    Esquema,
+    Listo,
+}
+
+impl WizardStep {
+    const ALL: [WizardStep; 4] = [WizardStep::Conexion, WizardStep::Prueba, WizardStep::Esquema, WizardStep::Listo];
+
+    fn label(self) -> &'static str {
+        match self {
+            WizardStep::Conexion => "Conexión",
+            WizardStep::Prueba => "Prueba",
+            WizardStep::Esquema => "Esquema",
+            WizardStep::Listo => "Listo",
+        }
+    }
+}
+
+// Cómo terminó el asistente
+pub enum WizardOutcome {
+    // El usuario prefirió el formulario de login habitual
+    Skipped,
+    // Conectado con el esquema listo; se guarda como perfil con este nombre
+    Finished(String, DatabaseConfig),
+}
+
+// Asistente que guía la primera conexión: datos de conexión → prueba → verificación o
+// creación del esquema → fin. Se muestra en lugar del login cuando no hay archivo de
+// configuración.
+pub struct SetupWizard {
+    db_manager: Arc<Mutex<DatabaseManager>>,
+    step: WizardStep,
+    config: DatabaseConfig,
+    profile_name: String,
+    show_password: bool,
+
+    // Estado del paso actual
+    working: bool,
+    message: Option<(bool, String)>,
+    // Tablas que faltan en la base; vacío cuando el esquema está completo
+    missing_tables: Vec<String>,
+    // El DatabaseManager quedó conectado durante el paso de esquema
+    connected: bool,
+
+    // Canales asíncronos
+    test_receiver: Option<mpsc::UnboundedReceiver<Result<(), String>>>,
+    schema_receiver: Option<mpsc::UnboundedReceiver<Result<Vec<String>, String>>>,
+}
+
+impl SetupWizard {
+    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>) -> Self {
+        Self {
+            db_manager,
+            step: WizardStep::Conexion,
+            config: DatabaseConfig::default(),
+            profile_name: "Principal".to_string(),
+            show_password: false,
+            working: false,
+            message: None,
+            missing_tables: Vec::new(),
+            connected: false,
+            test_receiver: None,
+            schema_receiver: None,
+        }
+    }
+
+    fn go_to(&mut self, step: WizardStep) {
+        self.step = step;
+        self.message = None;
+        match step {
+            WizardStep::Prueba => self.start_test(),
+            WizardStep::Esquema => self.start_schema_check(),
+            _ => {}
+        }
+    }
+
+    fn start_test(&mut self) {
+        self.working = true;
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.test_receiver = Some(rx);
+
+        let config = self.config.clone();
+        tokio::spawn(async move {
+            let result = match DatabaseManager::probe_connection(&config).await {
+                Ok(true) => Ok(()),
+                Ok(false) => Err("Error al probar la conexión".to_string()),
+                Err(e) => Err(format!("Error de conexión: {:#}", e)),
+            };
+            let _ = tx.send(result);
+        });
+    }
+
+    // Conecta el DatabaseManager y devuelve las tablas del esquema que faltan
+    fn start_schema_check(&mut self) {
+        self.working = true;
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.schema_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        let config = self.config.clone();
+        tokio::spawn(async move {
+            let mut db = db_manager.lock().await;
+            let result = db.connect(&config).await
+                .map_err(|e| format!("Error de conexión: {:#}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    fn start_schema_creation(&mut self) {
+        self.working = true;
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.schema_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let mut db = db_manager.lock().await;
+            let result = db.create_schema().await
+                .map(|_| Vec::new())
+                .map_err(|e| format!("Error al crear el esquema: {:#}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    // Procesa los resultados pendientes; devuelve true si llegó alguno
+    fn check_results(&mut self) -> bool {
+        if let Some(receiver) = &mut self.test_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.working = false;
+                self.test_receiver = None;
+                self.message = Some(match result {
+                    Ok(()) => (true, "Conexión exitosa".to_string()),
+                    Err(error_msg) => (false, error_msg),
+                });
+                return true;
+            }
+        }
+
+        if let Some(receiver) = &mut self.schema_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.working = false;
+                self.schema_receiver = None;
+                match result {
+                    Ok(missing) => {
+                        self.connected = true;
+                        self.message = Some(if missing.is_empty() {
+                            (true, "El esquema de la aplicación está completo".to_string())
+                        } else {
+                            (false, format!("Faltan las tablas: {}", missing.join(", ")))
+                        });
+                        self.missing_tables = missing;
+                    }
+                    Err(error_msg) => self.message = Some((false, error_msg)),
+                }
+                return true;
+            }
+        }
+        false
+    }
+
+    // Al omitir con la base ya conectada se cierra esa conexión; el login abre la suya
+    fn disconnect_if_connected(&mut self) {
+        if !self.connected {
+            return;
+        }
+        self.connected = false;
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            db_manager.lock().await.disconnect().await;
+        });
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) -> Option<WizardOutcome> {
+        self.check_results();
+        if self.working {
+            ui.ctx().request_repaint();
+        }
+
+        let mut outcome = None;
+        ui.vertical_centered(|ui| {
+            ui.add_space(50.0);
+            ui.add(egui::Label::new(AppleMusicStyle::header_text("Bienvenido")));
+            ui.add_space(10.0);
+            ui.add(egui::Label::new(AppleMusicStyle::secondary_text(
+                "Configuremos la conexión a la base de datos comunitaria (PostgreSQL)",
+            )));
+            ui.add_space(20.0);
+
+            // Indicador de pasos
+            ui.horizontal(|ui| {
+                for (index, step) in WizardStep::ALL.into_iter().enumerate() {
+                    if index > 0 {
+                        ui.label("→");
+                    }
+                    let text = format!("{}. {}", index + 1, step.label());
+                    if step == self.step {
+                        ui.strong(text);
+                    } else {
+                        ui.label(AppleMusicStyle::secondary_text(&text));
+                    }
+                }
+            });
+            ui.add_space(20.0);
+
+            AppleMusicStyle::card_frame().show(ui, |ui| {
+                ui.set_max_width(420.0);
+                match self.step {
+                    WizardStep::Conexion => self.show_connection_step(ui),
+                    WizardStep::Prueba => {
+                        ui.label(format!("Probando la conexión a {}", self.config.sanitized_dsn()));
+                    }
+                    WizardStep::Esquema => self.show_schema_step(ui),
+                    WizardStep::Listo => {
+                        ui.label("Todo listo. La conexión se guardará como perfil y se abrirá el dashboard.");
+                        ui.horizontal(|ui| {
+                            ui.label("Nombre del perfil:");
+                            ui.text_edit_singleline(&mut self.profile_name);
+                        });
+                    }
+                }
+
+                ui.add_space(10.0);
+                if self.working {
+                    ui.add(egui::widgets::Spinner::new().size(16.0));
+                }
+                if let Some((success, ref text)) = self.message {
+                    let color = if success { egui::Color32::GREEN } else { egui::Color32::RED };
+                    ui.colored_label(color, text);
+                }
+
+                ui.add_space(16.0);
+                ui.separator();
+                outcome = self.show_navigation(ui);
+            });
+        });
+        outcome
+    }
+
+    fn show_connection_step(&mut self, ui: &mut egui::Ui) {
+        ui.label("Ingrese los datos del servidor PostgreSQL. La base de datos puede estar vacía: el asistente puede crear las tablas.");
+        ui.add_space(10.0);
+        egui::Grid::new("wizard_connection_grid")
+            .num_columns(2)
+            .spacing([16.0, 12.0])
+            .show(ui, |ui| {
+                ui.label("Host:");
+                ui.text_edit_singleline(&mut self.config.host);
+                ui.end_row();
+
+                ui.label("Puerto:");
+                components::port_field(ui, &mut self.config);
+                ui.end_row();
+
+                ui.label("Base de Datos:");
+                ui.text_edit_singleline(&mut self.config.database);
+                ui.end_row();
+
+                ui.label("Usuario:");
+                ui.text_edit_singleline(&mut self.config.username);
+                ui.end_row();
+
+                ui.label("Contraseña:");
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut self.config.password).password(!self.show_password));
+                    if ui.small_button(if self.show_password { "🙈" } else { "👁" }).clicked() {
+                        self.show_password = !self.show_password;
+                    }
+                });
+                ui.end_row();
+            });
+    }
+
+    fn show_schema_step(&mut self, ui: &mut egui::Ui) {
+        ui.label("Se verifica que la base tenga las tablas de la aplicación.");
+        if self.connected && !self.missing_tables.is_empty() {
+            ui.add_space(10.0);
+            let button = egui::Button::new("🛠 Crear esquema");
+            if ui.add_enabled(!self.working, button)
+                .on_hover_text("Crea todas las tablas de la aplicación. Use una base de datos vacía: si alguna tabla ya existe no se aplica ningún cambio.")
+                .clicked()
+            {
+                self.start_schema_creation();
+            }
+        }
+    }
+
+    fn show_navigation(&mut self, ui: &mut egui::Ui) -> Option<WizardOutcome> {
+        let mut outcome = None;
+        ui.horizontal(|ui| {
+            if ui.add_enabled(!self.working, egui::Button::new("Omitir"))
+                .on_hover_text("Ir directamente al formulario de conexión")
+                .clicked()
+            {
+                self.disconnect_if_connected();
+                outcome = Some(WizardOutcome::Skipped);
+            }
+
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                let step_ok = match self.step {
+                    WizardStep::Conexion => {
+                        self.config.validation_error().is_none()
+                    }
+                    WizardStep::Prueba => matches!(self.message, Some((true, _))),
+                    WizardStep::Esquema => self.connected && self.missing_tables.is_empty(),
+                    WizardStep::Listo => !self.profile_name.trim().is_empty(),
+                };
+                let next_label = if self.step == WizardStep::Listo { "Finalizar" } else { "Siguiente" };
+                if ui.add_enabled(step_ok && !self.working, egui::Button::new(next_label)).clicked() {
+                    match self.step {
+                        WizardStep::Conexion => self.go_to(WizardStep::Prueba),
+                        WizardStep::Prueba => self.go_to(WizardStep::Esquema),
+                        WizardStep::Esquema => self.go_to(WizardStep::Listo),
+                        WizardStep::Listo => {
+                            outcome = Some(WizardOutcome::Finished(self.profile_name.trim().to_string(), self.config.clone()));
+                        }
+                    }
+                }
+
+                let can_retry = !self.working && matches!(self.message, Some((false, _)))
+                    && matches!(self.step, WizardStep::Prueba | WizardStep::Esquema);
+                if can_retry && ui.button("Reintentar").clicked() {
+                    self.go_to(self.step);
+                }
+
+                let previous = match self.step {
+                    WizardStep::Conexion => None,
+                    WizardStep::Prueba | WizardStep::Esquema => Some(WizardStep::Conexion),
+                    WizardStep::Listo => Some(WizardStep::Esquema),
+                };
+                if let Some(previous) = previous {
+                    // Si ya se conectó, volver a verificar el esquema reemplaza esa conexión
+                    if ui.add_enabled(!self.working, egui::Button::new("Atrás")).clicked() {
+                        self.go_to(previous);
+                    }
+                }
+            });
+        });
+        outcome
+    }
+}
@@ -1,6 +1,7 @@
 use eframe::egui;
 use crate::ui::app::AppState;
-use crate::ui::theme::AppleMusicStyle;
+use crate::config::AppConfig;
+use crate::ui::theme::{AppleMusicStyle, UiDensity};
 
 pub struct Sidebar {
     // Estado del sidebar si es necesario
@@ -11,7 +12,9 @@ impl Sidebar {
         Self {}
     }
 
-    pub fn show(&mut self, ui: &mut egui::Ui, current_state: &AppState) -> Option<AppState> {
+    // `density` se cambia desde el selector inferior y se guarda en la configuración;
+    // `is_connected` es el estado que mantiene el keep-alive, igual que en la barra de estado
+    pub fn show(&mut self, ui: &mut egui::Ui, current_state: &AppState, density: &mut UiDensity, is_connected: bool) -> Option<AppState> {
         let mut new_state = None;
         
         // Aplicar frame de sidebar estilo Apple Music
@@ -46,6 +49,21 @@ impl Sidebar {
                     new_state = Some(AppState::Insertions);
                 }
 
+                let catalogs_button = AppleMusicStyle::nav_button("Catálogos", *current_state == AppState::CatalogAdmin);
+                if ui.add(catalogs_button).clicked() {
+                    new_state = Some(AppState::CatalogAdmin);
+                }
+
+                let search_button = AppleMusicStyle::nav_button("Búsqueda", *current_state == AppState::Search);
+                if ui.add(search_button).clicked() {
+                    new_state = Some(AppState::Search);
+                }
+
+                let sql_button = AppleMusicStyle::nav_button("Consola SQL", *current_state == AppState::SqlConsole);
+                if ui.add(sql_button).clicked() {
+                    new_state = Some(AppState::SqlConsole);
+                }
+
                 let about_button = AppleMusicStyle::nav_button("About", *current_state == AppState::About);
                 if ui.add(about_button).clicked() {
                     new_state = Some(AppState::About);
@@ -69,13 +87,32 @@ impl Sidebar {
                     new_state = Some(AppState::Login);
                 }
                 
+                ui.add_space(16.0);
+
+                // Densidad de la interfaz
+                ui.horizontal(|ui| {
+                    let before = *density;
+                    ui.label(AppleMusicStyle::secondary_text("Densidad:"));
+                    ui.selectable_value(density, UiDensity::Comoda, UiDensity::Comoda.label());
+                    ui.selectable_value(density, UiDensity::Compacta, UiDensity::Compacta.label());
+                    if *density != before {
+                        let density = *density;
+                        AppConfig::update(|config| config.density = density);
+                    }
+                });
+
                 ui.add_space(16.0);
                 
                 // Estado de conexión
                 ui.horizontal(|ui| {
-                    ui.add(egui::widgets::Spinner::new().size(12.0).color(AppleMusicStyle::PRIMARY_BLUE));
+                    let (color, text) = if is_connected {
+                        (egui::Color32::GREEN, "Conectado")
+                    } else {
+                        (egui::Color32::RED, "Sin conexión")
+                    };
+                    ui.colored_label(color, "●");
                     ui.add_space(8.0);
-                    ui.add(egui::Label::new(AppleMusicStyle::secondary_text("Conectado")));
+                    ui.add(egui::Label::new(AppleMusicStyle::secondary_text(text)));
                 });
                 
                 ui.add_space(20.0);
@@ -0,0 +1,177 @@
+use eframe::egui;
+use std::sync::Arc;
+use tokio::sync::{Mutex, mpsc};
+use crate::database::{DatabaseManager, SQL_CONSOLE_TIMEOUT_SECS};
+use crate::models::ReadonlyQueryResult;
+use crate::ui::components;
+use crate::utils;
+
+// Filas que se traen como máximo; el resto se descarta con un aviso
+const MAX_ROWS: usize = 1000;
+
+type CancelSlot = Arc<std::sync::Mutex<Option<tokio_postgres::CancelToken>>>;
+
+// Consola para ejecutar consultas SELECT de solo lectura
+pub struct SqlConsoleView {
+    db_manager: Arc<Mutex<DatabaseManager>>,
+    sql: String,
+
+    // Último resultado
+    result: ReadonlyQueryResult,
+
+    // Estado
+    loading: bool,
+    error_message: Option<String>,
+
+    // Canal asíncrono y token para abortar la consulta en el servidor
+    query_receiver: Option<mpsc::UnboundedReceiver<Result<ReadonlyQueryResult, String>>>,
+    query_cancel: Option<CancelSlot>,
+}
+
+impl SqlConsoleView {
+    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>) -> Self {
+        Self {
+            db_manager,
+            sql: String::new(),
+            result: ReadonlyQueryResult::default(),
+            loading: false,
+            error_message: None,
+            query_receiver: None,
+            query_cancel: None,
+        }
+    }
+
+    fn check_query_result(&mut self) {
+        if let Some(receiver) = &mut self.query_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.loading = false;
+                self.query_receiver = None;
+                self.query_cancel = None;
+                match result {
+                    Ok(result) => {
+                        self.result = result;
+                    }
+                    Err(error_msg) => {
+                        self.error_message = Some(error_msg);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        self.check_query_result();
+        if self.loading {
+            ui.ctx().request_repaint_after(std::time::Duration::from_millis(250));
+        }
+
+        ui.heading("🖥 Consola SQL");
+        ui.label("Solo se permiten consultas SELECT; se ejecutan en una transacción de solo lectura.");
+        ui.label(format!(
+            "Se muestran hasta {} filas y la consulta se aborta si tarda más de {} segundos.",
+            utils::format_number(MAX_ROWS as i64),
+            SQL_CONSOLE_TIMEOUT_SECS
+        ));
+        ui.add_space(10.0);
+
+        ui.add(
+            egui::TextEdit::multiline(&mut self.sql)
+                .code_editor()
+                .desired_rows(6)
+                .desired_width(f32::INFINITY)
+                .hint_text("SELECT * FROM per_personasmayores LIMIT 10")
+        );
+
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            let can_run = !self.loading && !self.sql.trim().is_empty();
+            if ui.add_enabled(can_run, egui::Button::new("▶ Ejecutar")).clicked() {
+                self.execute();
+            }
+            if ui.button("🗑 Limpiar").clicked() {
+                self.sql.clear();
+                self.result = ReadonlyQueryResult::default();
+                self.error_message = None;
+            }
+            if self.loading {
+                ui.add(egui::widgets::Spinner::new().size(16.0));
+                if ui.button("✖ Cancelar").clicked() {
+                    self.cancel();
+                }
+            }
+        });
+
+        if let Some(error) = &self.error_message {
+            ui.add_space(5.0);
+            ui.colored_label(egui::Color32::RED, error);
+        }
+
+        ui.add_space(10.0);
+        ui.separator();
+
+        if self.result.columns.is_empty() {
+            return;
+        }
+
+        if self.result.truncated {
+            ui.colored_label(
+                egui::Color32::from_rgb(255, 165, 0),
+                format!("Resultado truncado: se muestran las primeras {} filas", utils::format_number(MAX_ROWS as i64)),
+            );
+        } else {
+            ui.label(format!("{} filas", self.result.rows.len()));
+        }
+        ui.add_space(5.0);
+
+        let headers: Vec<&str> = self.result.columns.iter().map(String::as_str).collect();
+        let rows = &self.result.rows;
+        egui::ScrollArea::horizontal().show(ui, |ui| {
+            components::results_table(ui, "sql_console_results", &headers, rows.len(), |index, row| {
+                for value in &rows[index] {
+                    row.col(|ui| {
+                        ui.label(value);
+                    });
+                }
+            });
+        });
+    }
+
+    fn execute(&mut self) {
+        self.loading = true;
+        self.error_message = None;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.query_receiver = Some(rx);
+        let cancel_slot = CancelSlot::default();
+        self.query_cancel = Some(cancel_slot.clone());
+
+        let sql = self.sql.clone();
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            *cancel_slot.lock().unwrap() = db.cancel_token();
+            let result = db.run_readonly_query(&sql, MAX_ROWS).await
+                .map_err(|e| format!("Error al ejecutar consulta: {}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    // Aborta la consulta en el servidor; su error llega por el canal como cualquier otro.
+    // Si aún esperaba la conexión, solo se descarta su resultado.
+    fn cancel(&mut self) {
+        let token = self.query_cancel.take().and_then(|slot| slot.lock().unwrap().take());
+        match token {
+            Some(token) => {
+                tokio::spawn(async move {
+                    if let Err(e) = DatabaseManager::cancel_query(token).await {
+                        log::warn!("No se pudo cancelar la consulta: {:#}", e);
+                    }
+                });
+            }
+            None => {
+                self.query_receiver = None;
+                self.loading = false;
+            }
+        }
+    }
+}
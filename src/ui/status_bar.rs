@@ -0,0 +1,113 @@
+use eframe::egui;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, mpsc};
+use crate::database::DatabaseManager;
+use crate::models::DashboardStats;
+use crate::ui::theme::AppleMusicStyle;
+use crate::utils;
+
+// Cada cuánto se vuelven a pedir los totales mientras la app está abierta
+const STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+// Barra inferior con los totales por entidad y el estado de la conexión
+pub struct StatusBar {
+    db_manager: Arc<Mutex<DatabaseManager>>,
+    stats: Option<DashboardStats>,
+    loading: bool,
+    last_refresh: Option<Instant>,
+    stats_receiver: Option<mpsc::UnboundedReceiver<Result<DashboardStats, String>>>,
+}
+
+impl StatusBar {
+    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>) -> Self {
+        Self {
+            db_manager,
+            stats: None,
+            loading: false,
+            last_refresh: None,
+            stats_receiver: None,
+        }
+    }
+
+    // Pide los totales de nuevo, por ejemplo después de insertar o eliminar
+    pub fn refresh(&mut self) {
+        self.loading = true;
+        self.last_refresh = Some(Instant::now());
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.stats_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = db.get_dashboard_stats().await
+                .map_err(|e| format!("Error al cargar totales: {}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    // Al desconectar se descartan los totales de la base anterior
+    pub fn clear(&mut self) {
+        self.stats = None;
+        self.loading = false;
+        self.last_refresh = None;
+        self.stats_receiver = None;
+    }
+
+    fn check_stats_result(&mut self) {
+        if let Some(receiver) = &mut self.stats_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.loading = false;
+                self.stats_receiver = None;
+                // Si falla se conservan los últimos totales conocidos
+                if let Ok(stats) = result {
+                    self.stats = Some(stats);
+                }
+            }
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui, is_connected: bool) {
+        self.check_stats_result();
+
+        if is_connected && !self.loading {
+            match self.last_refresh {
+                Some(last) if last.elapsed() < STATUS_REFRESH_INTERVAL => {
+                    ui.ctx().request_repaint_after(STATUS_REFRESH_INTERVAL - last.elapsed());
+                }
+                _ => self.refresh(),
+            }
+        }
+
+        ui.horizontal(|ui| {
+            let (color, text) = if is_connected {
+                (egui::Color32::GREEN, "Conectado")
+            } else {
+                (egui::Color32::RED, "Sin conexión")
+            };
+            ui.colored_label(color, "●");
+            ui.label(AppleMusicStyle::secondary_text(text));
+            ui.separator();
+
+            match &self.stats {
+                Some(stats) => {
+                    ui.label(format!("👥 {} personas", utils::format_number(stats.total_personas)));
+                    ui.separator();
+                    ui.label(format!("🏢 {} organizaciones", utils::format_number(stats.total_organizaciones)));
+                    ui.separator();
+                    ui.label(format!("🎯 {} actividades", utils::format_number(stats.total_actividades)));
+                    ui.separator();
+                    ui.label(format!("🚌 {} viajes", utils::format_number(stats.total_viajes)));
+                }
+                None => {
+                    ui.label(AppleMusicStyle::secondary_text("Totales no disponibles"));
+                }
+            }
+
+            if self.loading {
+                ui.add(egui::widgets::Spinner::new().size(12.0));
+            }
+        });
+    }
+}
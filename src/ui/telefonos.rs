@@ -0,0 +1,223 @@
+use eframe::egui;
+use std::sync::Arc;
+use tokio::sync::{Mutex, mpsc};
+use crate::database::DatabaseManager;
+use crate::models::{Telefono, TelefonoEntidad, TIPOS_TELEFONO};
+use crate::utils;
+
+// Sub-panel para administrar los teléfonos de una persona u organización
+pub struct TelefonosPanel {
+    db_manager: Arc<Mutex<DatabaseManager>>,
+
+    // Entidad seleccionada: (tipo, id, nombre a mostrar)
+    entity: Option<(TelefonoEntidad, i32, String)>,
+    telefonos: Vec<Telefono>,
+
+    // Formulario de nuevo teléfono
+    nuevo_tipo: String,
+    nuevo_numero: String,
+
+    // Estado
+    loading: bool,
+    message: Option<(bool, String)>,
+
+    // Canales asíncronos
+    list_receiver: Option<mpsc::UnboundedReceiver<Result<Vec<Telefono>, String>>>,
+    action_receiver: Option<mpsc::UnboundedReceiver<Result<String, String>>>,
+}
+
+impl TelefonosPanel {
+    pub fn new(db_manager: Arc<Mutex<DatabaseManager>>) -> Self {
+        Self {
+            db_manager,
+            entity: None,
+            telefonos: Vec::new(),
+            nuevo_tipo: TIPOS_TELEFONO[0].to_string(),
+            nuevo_numero: String::new(),
+            loading: false,
+            message: None,
+            list_receiver: None,
+            action_receiver: None,
+        }
+    }
+
+    pub fn open(&mut self, entity_type: TelefonoEntidad, entity_id: i32, nombre: String) {
+        self.entity = Some((entity_type, entity_id, nombre));
+        self.telefonos.clear();
+        self.nuevo_numero.clear();
+        self.message = None;
+        self.load_telefonos();
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.entity.is_some()
+    }
+
+    pub fn close(&mut self) {
+        self.entity = None;
+        self.list_receiver = None;
+        self.action_receiver = None;
+        self.loading = false;
+    }
+
+    fn check_results(&mut self) {
+        if let Some(receiver) = &mut self.list_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.loading = false;
+                self.list_receiver = None;
+                match result {
+                    Ok(telefonos) => self.telefonos = telefonos,
+                    Err(error_msg) => self.message = Some((false, error_msg)),
+                }
+            }
+        }
+
+        if let Some(receiver) = &mut self.action_receiver {
+            if let Ok(result) = receiver.try_recv() {
+                self.action_receiver = None;
+                match result {
+                    Ok(success_msg) => {
+                        self.message = Some((true, success_msg));
+                        self.nuevo_numero.clear();
+                        self.load_telefonos();
+                    }
+                    Err(error_msg) => {
+                        self.loading = false;
+                        self.message = Some((false, error_msg));
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn show(&mut self, ui: &mut egui::Ui) {
+        self.check_results();
+
+        let Some((_, _, nombre)) = self.entity.clone() else {
+            return;
+        };
+
+        ui.label(egui::RichText::new(format!("📞 Teléfonos de {}", nombre)).strong());
+        ui.add_space(5.0);
+
+        if let Some((success, ref text)) = self.message {
+            let color = if success { egui::Color32::GREEN } else { egui::Color32::RED };
+            ui.colored_label(color, text);
+        }
+
+        // Lista de teléfonos existentes
+        let mut to_delete = None;
+        if self.telefonos.is_empty() && !self.loading {
+            ui.label("Sin teléfonos registrados");
+        } else {
+            egui::Grid::new("telefonos_list")
+                .striped(true)
+                .spacing([10.0, 6.0])
+                .show(ui, |ui| {
+                    for telefono in &self.telefonos {
+                        ui.label(&telefono.tipo);
+                        ui.label(&telefono.numero);
+                        if ui.small_button("🗑").on_hover_text("Eliminar teléfono").clicked() {
+                            to_delete = Some(telefono.id);
+                        }
+                        ui.end_row();
+                    }
+                });
+        }
+
+        if let Some(telefono_id) = to_delete {
+            self.delete_telefono(telefono_id);
+        }
+
+        ui.add_space(8.0);
+
+        // Formulario para agregar un nuevo número
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_source("telefono_tipo")
+                .selected_text(self.nuevo_tipo.clone())
+                .show_ui(ui, |ui| {
+                    for tipo in TIPOS_TELEFONO {
+                        ui.selectable_value(&mut self.nuevo_tipo, tipo.to_string(), tipo);
+                    }
+                });
+            ui.add(egui::TextEdit::singleline(&mut self.nuevo_numero).hint_text("+56 9 12345678").desired_width(140.0));
+
+            let valid = utils::validate_telefono(&self.nuevo_tipo, &self.nuevo_numero);
+            if ui.add_enabled(valid && !self.loading, egui::Button::new("➕ Agregar")).clicked() {
+                self.insert_telefono();
+            }
+            if self.loading {
+                ui.add(egui::widgets::Spinner::new().size(14.0));
+            }
+        });
+
+        if !self.nuevo_numero.trim().is_empty() && !utils::validate_telefono(&self.nuevo_tipo, &self.nuevo_numero) {
+            ui.colored_label(egui::Color32::RED, "Formato inválido (móvil: +56 9 XXXXXXXX, fijo: +56 2 XXXXXXXX)");
+        }
+    }
+
+    fn load_telefonos(&mut self) {
+        let Some((entity_type, entity_id, _)) = self.entity.clone() else {
+            return;
+        };
+        self.loading = true;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.list_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = db.get_telefonos_for(entity_type, entity_id).await
+                .map_err(|e| format!("Error al cargar teléfonos: {}", e));
+            let _ = tx.send(result);
+        });
+    }
+
+    fn insert_telefono(&mut self) {
+        let Some((entity_type, entity_id, _)) = self.entity.clone() else {
+            return;
+        };
+        self.loading = true;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.action_receiver = Some(rx);
+
+        let telefono = Telefono {
+            id: 0, // Se asignará automáticamente
+            entity_id,
+            tipo: self.nuevo_tipo.clone(),
+            numero: utils::normalize_telefono(&self.nuevo_numero),
+        };
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = match db.insert_telefono(entity_type, &telefono).await {
+                Ok(_) => Ok(format!("Teléfono {} agregado", telefono.numero)),
+                Err(e) => Err(format!("Error al agregar teléfono: {}", e)),
+            };
+            let _ = tx.send(result);
+        });
+    }
+
+    fn delete_telefono(&mut self, telefono_id: i32) {
+        let Some((entity_type, _, _)) = self.entity.clone() else {
+            return;
+        };
+        self.loading = true;
+
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.action_receiver = Some(rx);
+
+        let db_manager = self.db_manager.clone();
+        tokio::spawn(async move {
+            let db = db_manager.lock().await;
+            let result = match db.delete_telefono(entity_type, telefono_id).await {
+                Ok(_) => Ok("Teléfono eliminado".to_string()),
+                Err(e) => Err(format!("Error al eliminar teléfono: {}", e)),
+            };
+            let _ = tx.send(result);
+        });
+    }
+}
@@ -1,4 +1,22 @@
 use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+// Densidad de la interfaz: la compacta reduce márgenes y alto de filas para pantallas pequeñas
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum UiDensity {
+    #[default]
+    Comoda,
+    Compacta,
+}
+
+impl UiDensity {
+    pub fn label(&self) -> &'static str {
+        match self {
+            UiDensity::Comoda => "cómoda",
+            UiDensity::Compacta => "compacta",
+        }
+    }
+}
 
 pub struct AppleMusicStyle;
 
@@ -18,7 +36,7 @@ impl AppleMusicStyle {
     pub const HOVER_BLUE: egui::Color32 = egui::Color32::from_rgb(20, 140, 255);         // Azul hover
     pub const CARD_BG: egui::Color32 = egui::Color32::from_rgb(35, 35, 40);              // Fondo de tarjetas
 
-    pub fn apply_style(ctx: &egui::Context) {
+    pub fn apply_style(ctx: &egui::Context, density: UiDensity) {
         let mut style = (*ctx.style()).clone();
         
         // Configurar colores globales
@@ -40,9 +58,19 @@ impl AppleMusicStyle {
         style.visuals.widgets.hovered.rounding = egui::Rounding::same(8.0);
         style.visuals.widgets.active.rounding = egui::Rounding::same(8.0);
         
-        // Espaciado
-        style.spacing.button_padding = egui::vec2(16.0, 12.0);
-        style.spacing.item_spacing = egui::vec2(12.0, 8.0);
+        // Espaciado; el alto de interacción también define el alto de las filas de resultados
+        match density {
+            UiDensity::Comoda => {
+                style.spacing.button_padding = egui::vec2(16.0, 12.0);
+                style.spacing.item_spacing = egui::vec2(12.0, 8.0);
+                style.spacing.interact_size.y = 18.0;
+            }
+            UiDensity::Compacta => {
+                style.spacing.button_padding = egui::vec2(8.0, 3.0);
+                style.spacing.item_spacing = egui::vec2(8.0, 3.0);
+                style.spacing.interact_size.y = 14.0;
+            }
+        }
         style.spacing.window_margin = egui::Margin::same(0.0);
         style.spacing.menu_margin = egui::Margin::same(8.0);
         
@@ -0,0 +1,111 @@
+use eframe::egui;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+// Tiempo en pantalla de los avisos que se cierran solos
+const TOAST_DURATION: Duration = Duration::from_secs(4);
+// Máximo de avisos apilados; al superarlo se descarta el más antiguo
+const MAX_TOASTS: usize = 5;
+
+// Cola de avisos compartida entre vistas; App es dueño de la instancia y la dibuja
+pub type SharedToasts = Arc<std::sync::Mutex<Toasts>>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ToastKind {
+    Success,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub kind: ToastKind,
+    pub text: String,
+    pub created: Instant,
+}
+
+impl Toast {
+    // Los errores quedan hasta que el usuario los cierra
+    fn is_sticky(&self) -> bool {
+        self.kind == ToastKind::Error
+    }
+
+    fn expires_at(&self) -> Option<Instant> {
+        (!self.is_sticky()).then(|| self.created + TOAST_DURATION)
+    }
+}
+
+// Avisos apilados en la esquina superior derecha, el más reciente abajo
+#[derive(Debug, Default)]
+pub struct Toasts {
+    queue: VecDeque<Toast>,
+}
+
+impl Toasts {
+    pub fn new_shared() -> SharedToasts {
+        Arc::new(std::sync::Mutex::new(Toasts::default()))
+    }
+
+    pub fn success(&mut self, text: impl Into<String>) {
+        self.push(ToastKind::Success, text.into());
+    }
+
+    pub fn error(&mut self, text: impl Into<String>) {
+        self.push(ToastKind::Error, text.into());
+    }
+
+    fn push(&mut self, kind: ToastKind, text: String) {
+        // Un mensaje repetido reemplaza al anterior en lugar de apilarse
+        self.queue.retain(|toast| toast.text != text);
+        self.queue.push_back(Toast { kind, text, created: Instant::now() });
+        while self.queue.len() > MAX_TOASTS {
+            self.queue.pop_front();
+        }
+    }
+
+    // Descarta los errores pendientes (p. ej. los del login una vez conectado)
+    pub fn dismiss_errors(&mut self) {
+        self.queue.retain(|toast| toast.kind != ToastKind::Error);
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) {
+        let now = Instant::now();
+        self.queue.retain(|toast| toast.expires_at().is_none_or(|at| at > now));
+        if self.queue.is_empty() {
+            return;
+        }
+
+        let mut dismissed = None;
+        egui::Area::new(egui::Id::new("toasts"))
+            .anchor(egui::Align2::RIGHT_TOP, egui::vec2(-12.0, 12.0))
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.set_max_width(360.0);
+                for (index, toast) in self.queue.iter().enumerate() {
+                    let (icon, fill) = match toast.kind {
+                        ToastKind::Success => ("✅", egui::Color32::from_rgb(20, 60, 20)),
+                        ToastKind::Error => ("❌", egui::Color32::from_rgb(70, 20, 20)),
+                    };
+                    egui::Frame::popup(ui.style())
+                        .fill(fill)
+                        .show(ui, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(icon);
+                                ui.add(egui::Label::new(&toast.text).wrap());
+                                if ui.small_button("✖").on_hover_text("Cerrar").clicked() {
+                                    dismissed = Some(index);
+                                }
+                            });
+                        });
+                    ui.add_space(6.0);
+                }
+            });
+
+        if let Some(index) = dismissed {
+            self.queue.remove(index);
+        }
+        if let Some(next) = self.queue.iter().filter_map(Toast::expires_at).min() {
+            ctx.request_repaint_after(next.saturating_duration_since(now));
+        }
+    }
+}
@@ -1,33 +1,167 @@
 use chrono::{NaiveDate, Datelike};
+use std::sync::LazyLock;
 
 pub fn format_date(date: &NaiveDate) -> String {
     date.format("%d/%m/%Y").to_string()
 }
 
-#[allow(dead_code)]
+// Acepta dd/mm/yyyy o yyyy-mm-dd; las fechas imposibles (31/02, 29/02 en año no bisiesto)
+// devuelven None
 pub fn parse_date(date_str: &str) -> Option<NaiveDate> {
+    let date_str = date_str.trim();
     NaiveDate::parse_from_str(date_str, "%d/%m/%Y").ok()
         .or_else(|| NaiveDate::parse_from_str(date_str, "%Y-%m-%d").ok())
 }
 
-#[allow(dead_code)]
+// Los formularios validan en cada frame mientras se escribe: los patrones se compilan una vez
+static RUT_FORMATO: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"^[0-9]{7,8}-[0-9Kk]$").unwrap());
+static EMAIL_FORMATO: LazyLock<regex::Regex> =
+    LazyLock::new(|| regex::Regex::new(r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}$").unwrap());
+
+// Formato de RUT chileno: 7-8 dígitos, guión y dígito verificador (0-9 o K)
 pub fn validate_rut(rut: &str) -> bool {
-    let re = regex::Regex::new(r"^[0-9]{7,8}-[0-9Kk]$").unwrap();
-    re.is_match(rut)
+    RUT_FORMATO.is_match(rut)
+}
+
+// Forma canónica de un RUT: sin puntos ni espacios y con el dígito verificador en mayúscula
+// (ej. "12.345.678-k" -> "12345678-K")
+pub fn normalize_rut(rut: &str) -> String {
+    rut.chars()
+        .filter(|c| *c != '.' && !c.is_whitespace())
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+// Forma de comparación para búsquedas por RUT: normalizado y sin guion, de modo que
+// "12.345.678", "123456789" y "12345678-9" coincidan con el mismo registro
+pub fn rut_search_key(rut: &str) -> String {
+    normalize_rut(rut).replace('-', "")
+}
+
+// Verifica el dígito verificador (módulo 11) de un RUT con formato 12345678-9
+pub fn validate_rut_check_digit(rut: &str) -> bool {
+    let Some((numero, dv)) = rut.split_once('-') else {
+        return false;
+    };
+    if numero.is_empty() || !numero.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    let suma: u32 = numero.chars().rev()
+        .filter_map(|c| c.to_digit(10))
+        .zip([2, 3, 4, 5, 6, 7].iter().cycle())
+        .map(|(digito, factor)| digito * factor)
+        .sum();
+    let esperado = match 11 - suma % 11 {
+        11 => '0',
+        10 => 'K',
+        n => char::from_digit(n, 10).unwrap_or('0'),
+    };
+
+    dv.eq_ignore_ascii_case(&esperado.to_string())
 }
 
-#[allow(dead_code)]
 pub fn validate_email(email: &str) -> bool {
-    let re = regex::Regex::new(r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}$").unwrap();
-    re.is_match(email)
+    EMAIL_FORMATO.is_match(email)
+}
+
+static TELEFONO_MOVIL: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"^\+569[0-9]{8}$").unwrap());
+static TELEFONO_FIJO: LazyLock<regex::Regex> = LazyLock::new(|| regex::Regex::new(r"^\+56[2-8][0-9]{8}$").unwrap());
+
+// Forma en que se guarda un teléfono: sin espacios ni guiones (ej. "+56 9 1234-5678" ->
+// "+56912345678"). Cabe en VARCHAR(20) y cumple chk_pt_numero_formato
+pub fn normalize_telefono(numero: &str) -> String {
+    numero.chars().filter(|c| !c.is_whitespace() && *c != '-').collect()
+}
+
+// Valida formato de teléfono chileno según su tipo, ignorando espacios y guiones
+// móvil: +56 9 XXXXXXXX | fijo: +56 + código de área + número (9 dígitos en total)
+pub fn validate_telefono(tipo: &str, numero: &str) -> bool {
+    let numero = normalize_telefono(numero);
+    match tipo {
+        "móvil" => TELEFONO_MOVIL.is_match(&numero),
+        "fijo" => TELEFONO_FIJO.is_match(&numero),
+        _ => TELEFONO_MOVIL.is_match(&numero) || TELEFONO_FIJO.is_match(&numero),
+    }
 }
 
+// Patrón ILIKE que busca `term` como texto literal dentro de la columna: se escapan los
+// comodines % y _ (y la barra invertida, que es el carácter de escape por defecto)
+pub fn contains_pattern(term: &str) -> String {
+    let mut pattern = String::with_capacity(term.len() + 2);
+    pattern.push('%');
+    for c in term.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            pattern.push('\\');
+        }
+        pattern.push(c);
+    }
+    pattern.push('%');
+    pattern
+}
+
+// Recorta a `max_len` caracteres (no bytes, para no cortar una ñ o un acento por la mitad)
 pub fn truncate_text(text: &str, max_len: usize) -> String {
-    if text.len() <= max_len {
+    if text.chars().count() <= max_len {
         text.to_string()
     } else {
-        format!("{}...", &text[..max_len.saturating_sub(3)])
+        let truncated: String = text.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", truncated)
+    }
+}
+
+// Entero con separador de miles chileno: 1234567 → "1.234.567"
+pub fn format_number(n: i64) -> String {
+    let digits = n.unsigned_abs().to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3 + 1);
+    if n < 0 {
+        grouped.push('-');
+    }
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push('.');
+        }
+        grouped.push(digit);
     }
+    grouped
+}
+
+// Tiempo transcurrido en la unidad más grande que aplique: "45s", "3m 20s", "1h 15m", "2d".
+// Pasada una semana ya no aporta precisión y se muestra como "más de 7d"
+pub fn format_elapsed(elapsed: std::time::Duration) -> String {
+    let secs = elapsed.as_secs();
+    let (days, hours, minutes, seconds) = (secs / 86_400, secs / 3_600 % 24, secs / 60 % 60, secs % 60);
+
+    match (days, hours, minutes) {
+        (d, _, _) if d >= 7 => "más de 7d".to_string(),
+        (d, 0, _) if d > 0 => format!("{}d", d),
+        (d, h, _) if d > 0 => format!("{}d {}h", d, h),
+        (_, h, m) if h > 0 => format!("{}h {}m", h, m),
+        (_, _, m) if m > 0 => format!("{}m {}s", m, seconds),
+        _ => format!("{}s", seconds),
+    }
+}
+
+// Personalidad jurídica en el formato esperado: número de registro con sufijo de letras
+// opcional, "1234" o "1234-R". Se aceptan espacios, minúsculas y los prefijos habituales
+// ("PJ 1234", "N° 1234-r"); devuelve None si el resto no sigue el formato
+pub fn normalize_personalidad_juridica(value: &str) -> Option<String> {
+    let upper = value.trim().to_uppercase();
+    let mut rest = upper.as_str();
+    for prefix in ["P.J.", "PJ", "N°", "Nº", "NO.", "#"] {
+        if let Some(stripped) = rest.strip_prefix(prefix) {
+            rest = stripped.trim_start();
+        }
+    }
+
+    let compact: String = rest.chars().filter(|c| !c.is_whitespace()).collect();
+    let (number, suffix) = match compact.split_once('-') {
+        Some((number, suffix)) => (number, Some(suffix)),
+        None => (compact.as_str(), None),
+    };
+    let number_ok = (1..=8).contains(&number.len()) && number.chars().all(|c| c.is_ascii_digit());
+    let suffix_ok = suffix.is_none_or(|s| (1..=3).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphabetic()));
+    (number_ok && suffix_ok).then_some(compact)
 }
 
 pub fn format_optional_date(date: &Option<NaiveDate>) -> String {
@@ -37,6 +171,18 @@ pub fn format_optional_date(date: &Option<NaiveDate>) -> String {
     }
 }
 
+// Motivo por el que una fecha de nacimiento no es válida para el registro: fecha futura
+// o persona menor que `min_age`
+pub fn birthdate_error(birth_date: &NaiveDate, min_age: i32) -> Option<String> {
+    if *birth_date > chrono::Local::now().date_naive() {
+        Some("La fecha de nacimiento no puede ser futura".to_string())
+    } else if calculate_age(birth_date) < min_age {
+        Some(format!("La persona debe tener al menos {} años", min_age))
+    } else {
+        None
+    }
+}
+
 pub fn calculate_age(birth_date: &NaiveDate) -> i32 {
     let today = chrono::Local::now().date_naive();
     let mut age = today.year() - birth_date.year();
@@ -48,3 +194,92 @@ pub fn calculate_age(birth_date: &NaiveDate) -> i32 {
     
     age
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_date_acepta_ambos_formatos() {
+        let expected = NaiveDate::from_ymd_opt(2024, 3, 15);
+        assert_eq!(parse_date("15/03/2024"), expected);
+        assert_eq!(parse_date("2024-03-15"), expected);
+        assert_eq!(parse_date("  15/03/2024 "), expected);
+    }
+
+    #[test]
+    fn parse_date_rechaza_fechas_inexistentes() {
+        assert_eq!(parse_date("31/02/2024"), None);
+        assert_eq!(parse_date("29/02/2023"), None);
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(parse_date(""), None);
+    }
+
+    #[test]
+    fn parse_date_acepta_bisiesto() {
+        assert_eq!(parse_date("29/02/2024"), NaiveDate::from_ymd_opt(2024, 2, 29));
+        assert_eq!(parse_date("2024-02-29"), NaiveDate::from_ymd_opt(2024, 2, 29));
+    }
+
+    #[test]
+    fn format_number_agrupa_miles() {
+        assert_eq!(format_number(0), "0");
+        assert_eq!(format_number(999), "999");
+        assert_eq!(format_number(1000), "1.000");
+        assert_eq!(format_number(1234567), "1.234.567");
+        assert_eq!(format_number(-1234567), "-1.234.567");
+        assert_eq!(format_number(i64::MIN), "-9.223.372.036.854.775.808");
+    }
+
+    #[test]
+    fn validate_telefono_ignora_separadores() {
+        assert!(validate_telefono("móvil", "+56 9 1234 5678"));
+        assert!(validate_telefono("móvil", "+56912345678"));
+        assert!(validate_telefono("fijo", "+56 2 1 2 3 4 5 6 7 8"));
+        assert!(validate_telefono("fijo", "+56 32 123-4567"));
+        assert!(!validate_telefono("móvil", "+56 2 12345678"));
+        assert!(!validate_telefono("fijo", "+56 2 1234567"));
+        // Lo que se guarda cumple el largo y el formato de la columna
+        let numero = normalize_telefono("+56 2 1 2 3 4 5 6 7 8");
+        assert_eq!(numero, "+56212345678");
+        assert!(numero.len() <= 20);
+    }
+
+    #[test]
+    fn contains_pattern_escapa_comodines() {
+        assert_eq!(contains_pattern("ana"), "%ana%");
+        assert_eq!(contains_pattern("50%"), "%50\\%%");
+        assert_eq!(contains_pattern("a_b\\c"), "%a\\_b\\\\c%");
+    }
+
+    #[test]
+    fn normalize_personalidad_juridica_formas_validas() {
+        assert_eq!(normalize_personalidad_juridica("  1234 "), Some("1234".to_string()));
+        assert_eq!(normalize_personalidad_juridica("pj n° 1234-r"), Some("1234-R".to_string()));
+        assert_eq!(normalize_personalidad_juridica("P.J. 12 34-ab"), Some("1234-AB".to_string()));
+    }
+
+    #[test]
+    fn normalize_personalidad_juridica_formas_invalidas() {
+        assert_eq!(normalize_personalidad_juridica("   "), None);
+        assert_eq!(normalize_personalidad_juridica("1234-R2"), None);
+        assert_eq!(normalize_personalidad_juridica("1234-ABCD"), None);
+        assert_eq!(normalize_personalidad_juridica("1234-"), None);
+        assert_eq!(normalize_personalidad_juridica("123456789"), None);
+    }
+
+    #[test]
+    fn format_elapsed_por_unidad() {
+        use std::time::Duration;
+        assert_eq!(format_elapsed(Duration::from_millis(0)), "0s");
+        assert_eq!(format_elapsed(Duration::from_millis(999)), "0s");
+        assert_eq!(format_elapsed(Duration::from_secs(45)), "45s");
+        assert_eq!(format_elapsed(Duration::from_secs(59)), "59s");
+        assert_eq!(format_elapsed(Duration::from_secs(60)), "1m 0s");
+        assert_eq!(format_elapsed(Duration::from_secs(200)), "3m 20s");
+        assert_eq!(format_elapsed(Duration::from_secs(4_500)), "1h 15m");
+        assert_eq!(format_elapsed(Duration::from_secs(2 * 86_400)), "2d");
+        assert_eq!(format_elapsed(Duration::from_secs(86_400 + 3 * 3_600)), "1d 3h");
+        assert_eq!(format_elapsed(Duration::from_secs(8 * 86_400)), "más de 7d");
+    }
+}